@@ -0,0 +1,78 @@
+// Simple benchmarks without external dependencies. Run them with:
+//
+// cargo bench --bench fingerprint
+
+use ion_binary_rs::{FingerprintedIonValue, IonValue};
+use std::collections::HashMap;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 200;
+
+fn build_document(fields: usize, last: i64) -> IonValue {
+    let mut values = HashMap::new();
+
+    for index in 0..fields {
+        let value = IonValue::List(vec![
+            IonValue::Integer(index as i64),
+            IonValue::String(format!("Value number {}", index)),
+        ]);
+
+        values.insert(format!("field_{}", index).into(), value);
+    }
+
+    values.insert("last".into(), IonValue::Integer(last));
+
+    IonValue::Struct(values)
+}
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    // Warm up
+    f();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+
+    println!(
+        "{}: {:?} per iteration ({} iterations)",
+        name,
+        elapsed / ITERATIONS,
+        ITERATIONS
+    );
+}
+
+fn main() {
+    let document = build_document(1000, 1);
+    let equal = build_document(1000, 1);
+    let different = build_document(1000, 2);
+
+    bench("IonValue == on equal 1000 field structs", || {
+        assert!(document == equal);
+    });
+
+    bench("IonValue == on different 1000 field structs", || {
+        assert!(document != different);
+    });
+
+    let document = FingerprintedIonValue::new(document);
+    let equal = FingerprintedIonValue::new(equal);
+    let different = FingerprintedIonValue::new(different);
+
+    bench(
+        "FingerprintedIonValue == on equal 1000 field structs",
+        || {
+            assert!(document == equal);
+        },
+    );
+
+    bench(
+        "FingerprintedIonValue == on different 1000 field structs",
+        || {
+            assert!(document != different);
+        },
+    );
+}
@@ -0,0 +1,55 @@
+// Simple benchmarks without external dependencies. Run them with:
+//
+// cargo bench --bench ion_hash
+
+use ion_binary_rs::{IonHash, IonValue};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 200;
+
+fn build_struct(fields: usize) -> IonValue {
+    let mut values = HashMap::new();
+
+    for index in 0..fields {
+        let value = if index % 2 == 0 {
+            IonValue::Integer(index as i64)
+        } else {
+            IonValue::String(format!("Value number {}", index))
+        };
+
+        values.insert(format!("field_{}", index).into(), value);
+    }
+
+    IonValue::Struct(values)
+}
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    // Warm up
+    f();
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+
+    println!(
+        "{}: {:?} per iteration ({} iterations)",
+        name,
+        elapsed / ITERATIONS,
+        ITERATIONS
+    );
+}
+
+fn main() {
+    let value = build_struct(1000);
+
+    bench("IonHash::digest on a 1000 field struct", || {
+        let hash = IonHash::digest::<Sha256>(&value);
+        assert_eq!(hash.len(), 32);
+    });
+}
@@ -0,0 +1,78 @@
+// Simple benchmarks without external dependencies. Run them with:
+//
+// cargo bench --bench symbols
+//
+// Besides the time, they print the allocations done by each iteration. The
+// symbols share the text of the symbol table, so the document of symbols
+// needs far fewer allocations than the same document with strings.
+
+use ion_binary_rs::{IonEncoder, IonParser, IonValue};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+const ITERATIONS: u32 = 200;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn build_document(values: usize, value: fn(String) -> IonValue) -> Vec<u8> {
+    let list = (0..values)
+        .map(|index| value(format!("category_{}", index % 10)))
+        .collect();
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::List(list));
+    encoder.encode()
+}
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    // Warm up
+    f();
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{}: {:?} and {} allocations per iteration ({} iterations)",
+        name,
+        elapsed / ITERATIONS,
+        allocations / ITERATIONS as usize,
+        ITERATIONS
+    );
+}
+
+fn main() {
+    let symbols = build_document(10_000, |text| IonValue::Symbol(text.into()));
+    let strings = build_document(10_000, IonValue::String);
+
+    bench("IonParser::consume_value on 10000 symbols", || {
+        IonParser::new(&symbols[..]).consume_value().unwrap();
+    });
+
+    bench("IonParser::consume_value on 10000 strings", || {
+        IonParser::new(&strings[..]).consume_value().unwrap();
+    });
+}
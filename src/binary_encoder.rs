@@ -1,6 +1,6 @@
-use crate::NullIonValue;
+use crate::{IonTimestamp, NullIonValue, TimestampPrecision};
 use bigdecimal::{BigDecimal, Zero};
-use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, Timelike};
 use num_bigint::{BigInt, BigUint, Sign};
 use std::convert::TryFrom;
 
@@ -23,6 +23,8 @@ pub fn encode_ion_value(value: &IonValue) -> Vec<u8> {
         IonValue::Clob(value) => encode_blob(9, value),
         IonValue::Blob(value) => encode_blob(10, value),
         IonValue::DateTime(value) => encode_datetime(value),
+        IonValue::DateTimeUnknownOffset(value) => encode_datetime_unknown_offset(value),
+        IonValue::Timestamp(value) => encode_ion_timestamp(value),
         _ => unreachable!(),
     }
 }
@@ -54,9 +56,54 @@ pub fn encode_null(value: &NullIonValue) -> Vec<u8> {
     }
 }
 
+// NOP padding of exactly `len` bytes. Up to 14 bytes the length fits in the
+// header, longer ones need the length field. For the few lengths where the
+// size of the length field makes it impossible, a one byte pad goes first.
+pub fn encode_nop_padding(len: usize) -> Vec<u8> {
+    if len == 0 {
+        return vec![];
+    }
+
+    if len <= ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED.into() {
+        let mut buffer = vec![0u8; len];
+        buffer[0] = u8::try_from(len - 1).unwrap();
+        return buffer;
+    }
+
+    for length_field_len in 1..len {
+        let body_len = len - 1 - length_field_len;
+        let length_field = encode_varuint(&body_len.to_be_bytes());
+
+        if length_field.len() == length_field_len {
+            let mut buffer = vec![ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED];
+            buffer.extend(length_field);
+            buffer.resize(len, 0);
+            return buffer;
+        }
+
+        if length_field.len() < length_field_len {
+            break;
+        }
+    }
+
+    let mut buffer = vec![0x00];
+    buffer.append(&mut encode_nop_padding(len - 1));
+    buffer
+}
+
 pub fn encode_datetime_representation(value: &DateTime<FixedOffset>) -> Vec<u8> {
-    let datetime = value.naive_utc();
+    let offset = value.offset().local_minus_utc() / 60;
+
+    encode_timestamp_representation(&value.naive_utc(), Some(offset))
+}
 
+// With an unknown offset the time is stored as UTC and the offset is -0.
+pub fn encode_datetime_unknown_offset_representation(value: &NaiveDateTime) -> Vec<u8> {
+    encode_timestamp_representation(value, None)
+}
+
+// The offset is in minutes, `None` being the unknown offset.
+fn encode_timestamp_representation(datetime: &NaiveDateTime, offset: Option<i32>) -> Vec<u8> {
     let year = datetime.year();
     let month = datetime.month();
     let day = datetime.day();
@@ -98,13 +145,17 @@ pub fn encode_datetime_representation(value: &DateTime<FixedOffset>) -> Vec<u8>
 
     let (exponent_sign, exponent_bytes) = BigInt::from(exponent).to_bytes_be();
 
-    let offset = value.offset().local_minus_utc() / 60;
-
-    let unsigned_offset = (offset.abs() as u32).to_be_bytes();
-
     let mut buffer: Vec<u8> = vec![];
 
-    buffer.append(&mut encode_varint(&unsigned_offset, offset.is_negative()));
+    match offset {
+        Some(offset) => {
+            let unsigned_offset = (offset.abs() as u32).to_be_bytes();
+            buffer.append(&mut encode_varint(&unsigned_offset, offset.is_negative()));
+        }
+        // -0 in VarInt 0b1_1_00_0000
+        None => buffer.push(0xC0),
+    }
+
     buffer.append(&mut encode_varuint(&year.to_be_bytes()));
     buffer.append(&mut encode_varuint(&month.to_be_bytes()));
     buffer.append(&mut encode_varuint(&day.to_be_bytes()));
@@ -132,9 +183,66 @@ pub fn encode_datetime_representation(value: &DateTime<FixedOffset>) -> Vec<u8>
     buffer
 }
 
+// Only the components up to the precision are written.
+pub fn encode_ion_timestamp_representation(value: &IonTimestamp) -> Vec<u8> {
+    let datetime = value.utc();
+    let precision = value.precision();
+
+    let mut buffer: Vec<u8> = vec![];
+
+    match value.offset_minutes() {
+        Some(offset) => {
+            let unsigned_offset = offset.unsigned_abs().to_be_bytes();
+            buffer.append(&mut encode_varint(&unsigned_offset, offset.is_negative()));
+        }
+        None => buffer.push(0xC0),
+    }
+
+    buffer.append(&mut encode_varuint(&datetime.year().to_be_bytes()));
+
+    if precision >= TimestampPrecision::Month {
+        buffer.append(&mut encode_varuint(&datetime.month().to_be_bytes()));
+    }
+
+    if precision >= TimestampPrecision::Day {
+        buffer.append(&mut encode_varuint(&datetime.day().to_be_bytes()));
+    }
+
+    if precision >= TimestampPrecision::Minute {
+        buffer.append(&mut encode_varuint(&datetime.hour().to_be_bytes()));
+        buffer.append(&mut encode_varuint(&datetime.minute().to_be_bytes()));
+    }
+
+    if precision >= TimestampPrecision::Second {
+        buffer.append(&mut encode_varuint(&datetime.second().to_be_bytes()));
+    }
+
+    if let TimestampPrecision::Fraction(digits) = precision {
+        buffer.append(&mut encode_varint(&digits.to_be_bytes(), true));
+
+        let coefficient = value.fraction_coefficient(digits);
+
+        if coefficient != 0 {
+            buffer.append(&mut encode_int(&BigInt::from(coefficient)));
+        }
+    }
+
+    buffer
+}
+
+pub fn encode_ion_timestamp(value: &IonTimestamp) -> Vec<u8> {
+    encode_timestamp(encode_ion_timestamp_representation(value))
+}
+
 pub fn encode_datetime(value: &DateTime<FixedOffset>) -> Vec<u8> {
-    let mut buffer = encode_datetime_representation(value);
+    encode_timestamp(encode_datetime_representation(value))
+}
+
+pub fn encode_datetime_unknown_offset(value: &NaiveDateTime) -> Vec<u8> {
+    encode_timestamp(encode_datetime_unknown_offset_representation(value))
+}
 
+fn encode_timestamp(mut buffer: Vec<u8>) -> Vec<u8> {
     let len = buffer.len();
     let mut len_bytes = filter_significant_bytes(&len.to_be_bytes());
 
@@ -6,18 +6,123 @@ use std::io::Read;
 pub struct IonBinaryParser<T: Read> {
     reader: T,
     current_ion_version: Option<(u8, u8)>,
+    consumed_bytes: usize,
+    scratch: Vec<u8>,
+    recording: Option<Vec<u8>>,
 }
 
 impl<T: Read> IonBinaryParser<T> {
     pub fn new(reader: T) -> IonBinaryParser<T> {
+        IonBinaryParser::with_scratch(reader, Vec::new())
+    }
+
+    // The scratch buffer is used for the bytes that are only needed while
+    // decoding a value, so its allocation is reused between values.
+    pub fn with_scratch(reader: T, scratch: Vec<u8>) -> IonBinaryParser<T> {
         IonBinaryParser {
             reader,
             current_ion_version: None,
+            consumed_bytes: 0,
+            scratch,
+            recording: None,
         }
     }
 
+    pub fn into_scratch(self) -> Vec<u8> {
+        self.scratch
+    }
+
+    // Readers like sockets can return less bytes than requested before the
+    // end of the stream, so it keeps reading until the buffer is full or the
+    // reader has nothing else. Less bytes than the buffer len means EOF.
     fn read(&mut self, buffer: &mut [u8]) -> Result<usize, std::io::Error> {
-        self.reader.read(buffer)
+        let mut read_bytes = 0;
+
+        while read_bytes < buffer.len() {
+            match self.reader.read(&mut buffer[read_bytes..]) {
+                Ok(0) => break,
+                Ok(len) => read_bytes += len,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.consumed_bytes += read_bytes;
+                    return Err(e);
+                }
+            }
+        }
+
+        self.consumed_bytes += read_bytes;
+
+        if let Some(recording) = &mut self.recording {
+            recording.extend_from_slice(&buffer[..read_bytes]);
+        }
+
+        Ok(read_bytes)
+    }
+
+    // While recording, every byte read is also copied to the recording, so
+    // the exact bytes of a value can be recovered. Starting it again drops
+    // what was recorded so far.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn stop_recording(&mut self) -> Option<Vec<u8>> {
+        self.recording.take()
+    }
+
+    // Total of bytes read from the reader since the parser was created.
+    pub fn get_consumed_bytes(&self) -> usize {
+        self.consumed_bytes
+    }
+
+    pub fn skip_bytes(&mut self, len: usize) -> Result<(), ParsingError> {
+        const CHUNK_SIZE: usize = 4096;
+
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let chunk_len = std::cmp::min(remaining, CHUNK_SIZE);
+            self.read_to_scratch(chunk_len)?;
+            remaining -= chunk_len;
+        }
+
+        Ok(())
+    }
+
+    // Reads `len` bytes into the scratch buffer, growing it if needed.
+    pub fn read_to_scratch(&mut self, len: usize) -> Result<&mut [u8], ParsingError> {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.clear();
+        scratch.resize(len, 0);
+
+        let result = self.read_bytes(&mut scratch);
+        self.scratch = scratch;
+        result?;
+
+        Ok(&mut self.scratch[..])
+    }
+
+    // Reads byte by byte until it finds an ion version marker, leaving the
+    // reader just after it.
+    pub fn skip_to_version_marker(&mut self) -> Result<(), ParsingError> {
+        let mut window = [0u8; 4];
+        let mut byte = [0u8; 1];
+
+        loop {
+            self.read_bytes(&mut byte)?;
+
+            window.rotate_left(1);
+            window[3] = byte[0];
+
+            if window[0] == 0xE0 && window[3] == 0xEA {
+                self.set_current_ion_version((window[1], window[2]));
+                return Ok(());
+            }
+        }
     }
 
     //             7                       0
@@ -36,11 +141,9 @@ impl<T: Read> IonBinaryParser<T> {
             return Err(ParsingError::CannotReadZeroBytes);
         }
 
-        let mut buffer = vec![0u8; octets];
+        let buffer = self.read_to_scratch(octets)?;
 
-        self.read_bytes(&mut buffer)?;
-
-        let number = BigUint::from_bytes_be(&buffer);
+        let number = BigUint::from_bytes_be(buffer);
 
         Ok(number)
     }
@@ -81,15 +184,13 @@ impl<T: Read> IonBinaryParser<T> {
             return Err(ParsingError::CannotReadZeroBytes);
         }
 
-        let mut buffer = vec![0u8; octets];
-
-        self.read_bytes(&mut buffer)?;
+        let buffer = self.read_to_scratch(octets)?;
 
         let is_negative = (buffer[0] & 0b1000_0000) > 0;
 
         buffer[0] &= 0b0111_1111;
 
-        let mut number = BigInt::from_bytes_be(Sign::Plus, &buffer);
+        let mut number = BigInt::from_bytes_be(Sign::Plus, buffer);
 
         if is_negative {
             number = -number;
@@ -138,6 +239,14 @@ impl<T: Read> IonBinaryParser<T> {
     //                                 |
     //                                 +--sign
     pub fn consume_varint(&mut self) -> Result<(BigInt, usize), ParsingError> {
+        let (number, len, _) = self.consume_varint_with_sign()?;
+
+        Ok((number, len))
+    }
+
+    // Same as `consume_varint`, but it also returns if the sign bit is set,
+    // as -0 has its own meaning in the offset of the timestamps.
+    pub fn consume_varint_with_sign(&mut self) -> Result<(BigInt, usize, bool), ParsingError> {
         let found_bytes = self.consume_var_number()?;
 
         let mut bytes: Vec<u8> = found_bytes
@@ -158,7 +267,7 @@ impl<T: Read> IonBinaryParser<T> {
             number = -number;
         }
 
-        Ok((number, bytes.len()))
+        Ok((number, bytes.len(), is_negative))
     }
 
     // Note: Guarantees to return at least one byte if it succeed
@@ -202,15 +311,28 @@ impl<T: Read> IonBinaryParser<T> {
             Ok(_) => {
                 let byte = byte[0];
 
+                if byte == 0xEF && self.consumed_bytes == 1 {
+                    return self.consume_byte_order_mark();
+                }
+
                 // If the byte has T as E (annotation) with a L of 0 (invalid)
                 // it means that this is a ion version header, so we read it
                 // and set the decoder to the new version.
                 if byte == 0xE0 {
                     let version = self.consume_ion_version_once_identified()?;
                     self.set_current_ion_version(version);
+
+                    if self.is_recording() {
+                        self.start_recording();
+                    }
+
                     return self.consume_value_header();
                 }
 
+                if self.current_ion_version.is_none() {
+                    return Err(ParsingError::MissingVersionMarker);
+                }
+
                 let value_type = (byte & 0b1111_0000) >> 4;
 
                 let value_length = byte & 0b0000_1111;
@@ -219,7 +341,7 @@ impl<T: Read> IonBinaryParser<T> {
                 let value_length = self.get_field_length(value_length);
                 match (value_type, value_length) {
                     (Ok(mut r#type), Ok(length)) => {
-                        self.verify_header(&r#type, &length)?;
+                        self.verify_header(byte, &r#type, &length)?;
 
                         self.if_nop_fill_nop_padding(&mut r#type, &length);
 
@@ -242,7 +364,12 @@ impl<T: Read> IonBinaryParser<T> {
         }
     }
 
-    fn verify_header(&self, valtype: &ValueType, length: &ValueLength) -> Result<(), ParsingError> {
+    fn verify_header(
+        &self,
+        byte: u8,
+        valtype: &ValueType,
+        length: &ValueLength,
+    ) -> Result<(), ParsingError> {
         use ValueLength::*;
         use ValueType::*;
 
@@ -258,10 +385,39 @@ impl<T: Read> IonBinaryParser<T> {
                     Ok(())
                 }
             }
+            // A timestamp needs at least the offset and the year, one byte
+            // each, so shorter lengths would make us read the next value.
+            Timestamp => match length {
+                ShortLength(0) | ShortLength(1) => Err(ParsingError::InvalidTypeDescriptor(byte)),
+                _ => Ok(()),
+            },
             _ => Ok(()),
         }
     }
 
+    // Some producers prepend an UTF-8 BOM (EF BB BF) to the stream. It is
+    // skipped when it is the first thing in the stream and it is followed by
+    // an Ion Version Marker. A 0xEF header would be a null annotation, which
+    // is not valid, so anything else starting that way is rejected.
+    fn consume_byte_order_mark(&mut self) -> Result<ValueHeader, ParsingError> {
+        let mut bytes = [0u8; 3];
+
+        match self.read_bytes(&mut bytes) {
+            Ok(()) if bytes == [0xBB, 0xBF, 0xE0] => {}
+            Ok(())
+            | Err(ParsingError::NoDataToRead)
+            | Err(ParsingError::NotEnoughtDataToRead(_)) => {
+                return Err(ParsingError::InvalidStreamStart)
+            }
+            Err(e) => return Err(e),
+        }
+
+        let version = self.consume_ion_version_once_identified()?;
+        self.set_current_ion_version(version);
+
+        self.consume_value_header()
+    }
+
     //                        7    0 7     0 7     0 7    0
     //                       +------+-------+-------+------+
     // binary version marker | 0xE0 | major | minor | 0xEA |
@@ -276,6 +432,7 @@ impl<T: Read> IonBinaryParser<T> {
         match read_bytes {
             Ok(0) => Err(ParsingError::NoDataToRead),
             Err(e) => Err(ParsingError::ErrorReadingData(e.to_string())),
+            Ok(len) if len < byte.len() => Err(ParsingError::NotEnoughtDataToRead(len)),
             Ok(_) => {
                 if byte[2] != 0xEA {
                     return Err(ParsingError::BadFormedVersionHeader);
@@ -286,10 +443,14 @@ impl<T: Read> IonBinaryParser<T> {
         }
     }
 
-    fn set_current_ion_version(&mut self, version: (u8, u8)) {
+    pub fn set_current_ion_version(&mut self, version: (u8, u8)) {
         self.current_ion_version = Some(version);
     }
 
+    pub fn get_current_ion_version(&self) -> Option<(u8, u8)> {
+        self.current_ion_version
+    }
+
     fn get_field_type(&mut self, id: u8) -> Result<ValueType, ParsingError> {
         match id {
             0 => Ok(ValueType::Null),
@@ -35,7 +35,7 @@ pub enum ValueLength {
     NullValue,       // L = 15
 }
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
 pub enum ValueType {
     Null,        // T = 0   : 0000
     Nop,         // T = 0   : 0000 (with length < 15)
@@ -64,6 +64,8 @@ pub enum ParsingError {
     InvalidHeaderType,
     #[error("Header length not valid")]
     InvalidHeaderLength,
+    #[error("The type descriptor {0:#04x} is not valid")]
+    InvalidTypeDescriptor(u8),
     #[error("Reached end of the ion stream")]
     NoDataToRead,
     #[error("There is not enough data to read, provably a premature ion stream end")]
@@ -72,8 +74,18 @@ pub enum ParsingError {
     ErrorReadingData(String),
     #[error("Trying to read 0 bytes")]
     CannotReadZeroBytes,
+    #[error("Negative ints cannot be zero")]
+    NegativeZeroInt,
+    #[error("The sign of the integer doesn't match its type code")]
+    IntSignMismatch,
+    #[error("The value is longer than the container that holds it")]
+    ChildExceedsParent,
     #[error("Ion Stream Header is wrong")]
     BadFormedVersionHeader,
+    #[error("The stream starts with stray bytes instead of an Ion Version Marker")]
+    InvalidStreamStart,
+    #[error("The stream doesn't start with an Ion Version Marker and no version was assumed")]
+    MissingVersionMarker,
     #[error("Null cannot have len")]
     InvalidNullLength(ValueLength),
     #[error("Annotation cannot be shorter than 3 bytes")]
@@ -0,0 +1,350 @@
+use crate::{IonParser, IonParserError, IonValue};
+use num_traits::ToPrimitive;
+use serde::de::value::{MapDeserializer, SeqDeserializer, StringDeserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IgnoredAny, IntoDeserializer, MapAccess, Visitor,
+};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer};
+use std::fmt;
+use std::io::Read;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+// Name used by `Annotated` to ask the deserializer (and the serializer) for
+// the annotations.
+pub(crate) const ANNOTATED_STRUCT: &str = "$ion_binary_rs::Annotated";
+const ANNOTATED_FIELDS: &[&str] = &["annotations", "value"];
+
+/// Deserializes the only value of a single document stream, with the same
+/// rules as `IonParser::parse_single`.
+///
+/// Structs are deserialized as maps or Rust structs, lists and sexps as
+/// sequences, symbols and strings as `String`, blobs and clobs as bytes
+/// (`Vec<u8>` too), nulls as `None` or `()` and timestamps as RFC 3339
+/// strings, or Ion text when they are less precise than seconds. Enums are
+/// strings for unit variants or structs with one field, the variant name,
+/// for the other variants. Annotations are skipped unless the value is
+/// deserialized as an `Annotated`.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonEncoder, IonValue};
+///
+/// let mut encoder = IonEncoder::new();
+/// encoder.add(IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]));
+/// let bytes = encoder.encode();
+///
+/// let value: Vec<i64> = ion_binary_rs::from_reader(&bytes[..]).unwrap();
+///
+/// assert_eq!(value, vec![1, 2]);
+/// ```
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T, IonParserError> {
+    from_value(IonParser::new(reader).parse_single()?)
+}
+
+/// Deserializes an already parsed value, following the same rules as
+/// `from_reader`.
+pub fn from_value<T: DeserializeOwned>(value: IonValue) -> Result<T, IonParserError> {
+    T::deserialize(value)
+}
+
+/// A value together with its annotations. The annotations are empty when
+/// the value is not annotated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotated<T> {
+    pub annotations: Vec<String>,
+    pub value: T,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Annotated<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct(
+            ANNOTATED_STRUCT,
+            ANNOTATED_FIELDS,
+            AnnotatedVisitor(PhantomData),
+        )
+    }
+}
+
+struct AnnotatedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for AnnotatedVisitor<T> {
+    type Value = Annotated<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an annotated value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut annotations = None;
+        let mut value = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "annotations" => annotations = Some(map.next_value()?),
+                "value" => value = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(Annotated {
+            annotations: annotations.unwrap_or_default(),
+            value: value.ok_or_else(|| de::Error::missing_field("value"))?,
+        })
+    }
+}
+
+impl de::Error for IonParserError {
+    fn custom<M: fmt::Display>(msg: M) -> Self {
+        IonParserError::Deserialization(msg.to_string())
+    }
+}
+
+impl<'de> IntoDeserializer<'de, IonParserError> for IonValue {
+    type Deserializer = IonValue;
+
+    fn into_deserializer(self) -> IonValue {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for IonValue {
+    type Error = IonParserError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, IonParserError> {
+        match self {
+            IonValue::Null(_) => visitor.visit_unit(),
+            IonValue::Bool(value) => visitor.visit_bool(value),
+            IonValue::Integer(value) => visitor.visit_i64(value),
+            IonValue::BigInteger(value) => match (value.to_u64(), value.to_i128()) {
+                (Some(value), _) => visitor.visit_u64(value),
+                (_, Some(value)) => visitor.visit_i128(value),
+                _ => Err(de::Error::custom("the integer doesn't fit in 128 bits")),
+            },
+            IonValue::Float(value) => visitor.visit_f64(value),
+            IonValue::Decimal(value) => match value.to_f64() {
+                Some(value) => visitor.visit_f64(value),
+                None => Err(de::Error::custom("the decimal doesn't fit in a float")),
+            },
+            value @ IonValue::DateTime(_)
+            | value @ IonValue::DateTimeUnknownOffset(_)
+            | value @ IonValue::Timestamp(_) => visitor.visit_string(
+                value
+                    .to_rfc3339()
+                    .or_else(|| value.to_ion_text())
+                    .unwrap_or_default(),
+            ),
+            IonValue::String(value) => visitor.visit_string(value),
+            IonValue::Symbol(symbol) => match symbol.text {
+                Some(text) => visitor.visit_string(text.to_string()),
+                None => Err(IonParserError::SymbolNotFoundInTable),
+            },
+            IonValue::Clob(value) | IonValue::Blob(value) => visitor.visit_byte_buf(value),
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                visit_seq(SeqDeserializer::new(values.into_iter()), visitor)
+            }
+            IonValue::Struct(fields) => {
+                visit_map(MapDeserializer::new(fields.into_iter().map(field)), visitor)
+            }
+            IonValue::OrderedStruct(fields) => {
+                visit_map(MapDeserializer::new(fields.into_iter().map(field)), visitor)
+            }
+            IonValue::Annotation(_, value) => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, IonParserError> {
+        match without_annotations(self) {
+            IonValue::Null(_) => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    // Lobs are sequences of bytes too, as `Vec<u8>` is deserialized as a
+    // sequence.
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, IonParserError> {
+        match without_annotations(self) {
+            IonValue::Clob(value) | IonValue::Blob(value) => {
+                visit_seq(SeqDeserializer::new(value.into_iter()), visitor)
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, IonParserError> {
+        if name != ANNOTATED_STRUCT {
+            return without_annotations(self).deserialize_any(visitor);
+        }
+
+        let (annotations, value) = match self {
+            IonValue::Annotation(annotations, value) => (annotations, *value),
+            value => (vec![], value),
+        };
+
+        let annotations = annotations
+            .into_iter()
+            .map(|annotation| IonValue::String(annotation.to_string()))
+            .collect();
+        let entries = ANNOTATED_FIELDS
+            .iter()
+            .copied()
+            .zip(vec![IonValue::List(annotations), value]);
+
+        visit_map(MapDeserializer::new(entries), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, IonParserError> {
+        let (variant, value) = match without_annotations(self) {
+            IonValue::String(variant) => return visitor.visit_enum(variant_name(variant)),
+            IonValue::Symbol(symbol) => match symbol.text {
+                Some(variant) => return visitor.visit_enum(variant_name(variant.to_string())),
+                None => return Err(IonParserError::SymbolNotFoundInTable),
+            },
+            IonValue::Struct(fields) => single_field(fields.into_iter().map(field))?,
+            IonValue::OrderedStruct(fields) => single_field(fields.into_iter().map(field))?,
+            _ => return Err(enum_error()),
+        };
+
+        visitor.visit_enum(EnumDeserializer { variant, value })
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, IonParserError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, IonParserError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple tuple_struct map identifier
+    }
+}
+
+fn without_annotations(value: IonValue) -> IonValue {
+    match value {
+        IonValue::Annotation(_, value) => *value,
+        value => value,
+    }
+}
+
+// Field names are deserialized as strings.
+fn field((name, value): (Arc<str>, IonValue)) -> (String, IonValue) {
+    (name.to_string(), value)
+}
+
+fn single_field(
+    mut fields: impl Iterator<Item = (String, IonValue)>,
+) -> Result<(String, IonValue), IonParserError> {
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => Ok(field),
+        _ => Err(enum_error()),
+    }
+}
+
+fn enum_error() -> IonParserError {
+    de::Error::custom("an enum needs to be a string or a struct with one field")
+}
+
+fn variant_name(variant: String) -> StringDeserializer<IonParserError> {
+    variant.into_deserializer()
+}
+
+fn visit_seq<'de, I, V>(
+    mut seq: SeqDeserializer<I, IonParserError>,
+    visitor: V,
+) -> Result<V::Value, IonParserError>
+where
+    I: Iterator,
+    I::Item: IntoDeserializer<'de, IonParserError>,
+    V: Visitor<'de>,
+{
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+fn visit_map<'de, I, K, V, M>(
+    mut map: MapDeserializer<'de, I, IonParserError>,
+    visitor: M,
+) -> Result<M::Value, IonParserError>
+where
+    I: Iterator<Item = (K, V)>,
+    K: IntoDeserializer<'de, IonParserError>,
+    V: IntoDeserializer<'de, IonParserError>,
+    M: Visitor<'de>,
+{
+    let value = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(value)
+}
+
+// Enum variants with data, from a struct with a single field.
+struct EnumDeserializer {
+    variant: String,
+    value: IonValue,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = IonParserError;
+    type Variant = IonValue;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, IonValue), IonParserError> {
+        let variant = seed.deserialize(variant_name(self.variant))?;
+
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for IonValue {
+    type Error = IonParserError;
+
+    fn unit_variant(self) -> Result<(), IonParserError> {
+        <()>::deserialize(self)
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, IonParserError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, IonParserError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, IonParserError> {
+        self.deserialize_any(visitor)
+    }
+}
@@ -1,13 +1,17 @@
 use crate::binary_encoder::{
-    encode_blob, encode_bool, encode_datetime, encode_decimal, encode_float64, encode_integer,
-    encode_null, encode_uint, encode_varuint, ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED,
+    encode_blob, encode_bool, encode_datetime, encode_datetime_unknown_offset, encode_decimal,
+    encode_float64, encode_integer, encode_ion_timestamp, encode_nop_padding, encode_null,
+    encode_uint, encode_varuint, ION_LEN_ON_HEADER_WHEN_EXTRA_LEN_FIELD_REQUIRED,
 };
 use crate::binary_parser_types::{SystemSymbolIds, SYSTEM_SYMBOL_TABLE};
-use crate::symbol_table::SymbolContext;
-use crate::IonValue;
+use crate::symbol_table::{Import, Symbol, SymbolContext, SymbolContextError, SymbolToken};
+use crate::{IonParserError, IonValue};
+use chrono::Datelike;
 use num_bigint::{BigInt, BigUint};
-use std::collections::HashMap;
+use std::collections::BTreeSet;
 use std::convert::TryFrom;
+use std::io::Write;
+use std::sync::Arc;
 
 /// Allows to binary encode one or multiple IonValue.
 ///
@@ -25,15 +29,15 @@ use std::convert::TryFrom;
 ///
 /// let mut ion_struct = HashMap::new();
 ///
-/// ion_struct.insert("Model".to_string(), IonValue::String("CLK 350".to_string()));
-/// ion_struct.insert("Type".to_string(), IonValue::String("Sedan".to_string()));
-/// ion_struct.insert("Color".to_string(), IonValue::String("White".to_string()));
+/// ion_struct.insert("Model".into(), IonValue::String("CLK 350".to_string()));
+/// ion_struct.insert("Type".into(), IonValue::String("Sedan".to_string()));
+/// ion_struct.insert("Color".into(), IonValue::String("White".to_string()));
 /// ion_struct.insert(
-///     "VIN".to_string(),
+///     "VIN".into(),
 ///     IonValue::String("1C4RJFAG0FC625797".to_string()),
 /// );
-/// ion_struct.insert("Make".to_string(), IonValue::String("Mercedes".to_string()));
-/// ion_struct.insert("Year".to_string(), IonValue::Integer(2019));
+/// ion_struct.insert("Make".into(), IonValue::String("Mercedes".to_string()));
+/// ion_struct.insert("Year".into(), IonValue::Integer(2019));
 ///
 /// let ion_value = IonValue::Struct(ion_struct);
 ///
@@ -46,10 +50,48 @@ use std::convert::TryFrom;
 ///
 /// assert_eq!(ion_value, resulting_ion_value);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IonEncoder {
     current_buffer: Vec<IonValue>,
     symbol_table: SymbolContext,
+    mode: EncodeMode,
+    symbol_order: EncodeSymbolOrder,
+    write_ivm: bool,
+    padding: usize,
+    imports: Vec<Import>,
+}
+
+/// Binary Ion always refers to symbols by id, so every encoded stream has a
+/// local symbol table. This decides what goes in it.
+///
+/// - `WithTable` writes every symbol of the encoder's symbol context,
+///   including the ones from previous `encode` calls and the ones of the
+///   context given in `with_symbol_context`. This is the default.
+/// - `MinimalTable` encodes every `encode` call with a copy of the symbol
+///   context that only keeps the system and imported symbols, so the table
+///   only contains the symbols used by the values being encoded. The
+///   encoder's context, like the one given in `with_symbol_context`, is
+///   left untouched and used again if the mode goes back to `WithTable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeMode {
+    WithTable,
+    MinimalTable,
+}
+
+/// The order in which the new symbols get their ids.
+///
+/// - `FirstUse` assigns the ids as the symbols are found while encoding.
+///   As `Struct` fields have no order, two equal structs built separately
+///   can be encoded differently. This is the default.
+/// - `Sorted` assigns the ids in alphabetical order before encoding and
+///   writes the `Struct` fields sorted by name, so equal values always give
+///   the same bytes. `OrderedStruct` fields keep their order.
+///
+/// Symbols already in the symbol table keep their ids in both cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeSymbolOrder {
+    FirstUse,
+    Sorted,
 }
 
 impl Default for IonEncoder {
@@ -63,18 +105,154 @@ impl IonEncoder {
         IonEncoder {
             current_buffer: vec![],
             symbol_table: SymbolContext::new(),
+            mode: EncodeMode::WithTable,
+            symbol_order: EncodeSymbolOrder::FirstUse,
+            write_ivm: true,
+            padding: 0,
+            imports: vec![],
+        }
+    }
+
+    /// Creates an encoder that interns symbols against the provided symbol
+    /// context. Symbols (including struct keys and annotations) already in
+    /// the context are encoded with their existing id and the missing ones are
+    /// appended to it. The resulting local symbol table contains every symbol
+    /// of the context, so the output can be decoded without it.
+    pub fn with_symbol_context(context: SymbolContext) -> IonEncoder {
+        IonEncoder {
+            symbol_table: context,
+            ..IonEncoder::new()
         }
     }
 
+    /// Imports a shared table, same as `IonParser::with_shared_table`. Its
+    /// symbols are encoded with the ids of the table and the local symbol
+    /// table declares the import (name, version and max_id) instead of
+    /// listing them, so the reader needs to know the table too. Imports are
+    /// declared in the order they are added and go before the symbols that
+    /// are only in the local table.
+    pub fn with_shared_table(
+        &mut self,
+        name: String,
+        version: u32,
+        symbols: &[String],
+    ) -> Result<(), SymbolContextError> {
+        let symbols: Vec<Symbol> = symbols
+            .iter()
+            .map(|s| Symbol::Symbol(Arc::from(s.as_str())))
+            .collect();
+
+        // The symbols only in the local table are kept after the imports.
+        let local_symbols: Vec<Symbol> = self
+            .symbol_table
+            .dump_all_local_symbols()
+            .into_iter()
+            .skip(self.imported_symbols_len())
+            .map(|s| Symbol::Symbol(Arc::from(s)))
+            .collect();
+
+        self.symbol_table
+            .add_shared_table(name.clone(), version, &symbols)?;
+
+        self.imports.push(Import {
+            name,
+            version: Some(version),
+            max_len: Some(symbols.len()),
+        });
+
+        self.symbol_table
+            .set_new_table(&self.imports, &local_symbols)
+    }
+
+    pub fn set_encode_mode(&mut self, mode: EncodeMode) {
+        self.mode = mode;
+    }
+
+    pub fn set_symbol_order(&mut self, symbol_order: EncodeSymbolOrder) {
+        self.symbol_order = symbol_order;
+    }
+
+    /// Allows to skip the Ion Version Marker (the 4 bytes at the start of
+    /// the output) when the version is already known by other means, like
+    /// the container the Ion data is embedded in. It is written by default.
+    /// Read the output with `IonParser::assume_version`.
+    pub fn with_ivm(&mut self, write_ivm: bool) {
+        self.write_ivm = write_ivm;
+    }
+
     pub fn add(&mut self, value: IonValue) {
         self.current_buffer.push(value);
     }
 
+    /// Makes the next `encode` add NOP padding after the values so the
+    /// output is exactly `target_len` bytes long. The parser skips the
+    /// padding. The size is computed with the values added so far, so call
+    /// it after adding all of them. It fails if the output is already longer
+    /// than the target.
+    pub fn pad_to(&mut self, target_len: usize) -> Result<(), IonParserError> {
+        let mut encoder = self.clone();
+        encoder.padding = 0;
+
+        let len = encoder.encode().len();
+
+        if len > target_len {
+            return Err(IonParserError::PadTargetTooSmall {
+                len,
+                target: target_len,
+            });
+        }
+
+        self.padding = target_len - len;
+
+        Ok(())
+    }
+
+    /// Same as `encode` but it checks the values first and fails, without
+    /// encoding anything, if one of them can't be represented in Ion binary:
+    /// a symbol without text and id, a symbol without text whose id is not
+    /// a system or imported symbol, an annotation without annotations, an
+    /// annotation of an annotation or a timestamp year outside 1..=9999.
+    /// `encode` would write those anyway, giving bytes that don't read back
+    /// to the same value. The values are kept in the encoder on failure.
+    pub fn try_encode(&mut self) -> Result<Vec<u8>, IonParserError> {
+        let max_symbol_id = self.max_id_only_symbol();
+
+        for value in &self.current_buffer {
+            validate_value(value, max_symbol_id)?;
+        }
+
+        Ok(self.encode())
+    }
+
     pub fn encode(&mut self) -> Vec<u8> {
         let mut values = vec![];
 
         values.append(&mut self.current_buffer);
 
+        // The minimal table is built on a copy, so the encoder's context is
+        // left as it was.
+        let context = if self.mode == EncodeMode::MinimalTable {
+            let mut minimal_table = self.symbol_table.clone();
+            let len = SYSTEM_SYMBOL_TABLE.len() + self.imported_symbols_len();
+            minimal_table.truncate_current_table(len);
+
+            Some(std::mem::replace(&mut self.symbol_table, minimal_table))
+        } else {
+            None
+        };
+
+        if self.symbol_order == EncodeSymbolOrder::Sorted {
+            let mut symbols = BTreeSet::new();
+
+            for value in &values {
+                collect_symbols(value, &mut symbols);
+            }
+
+            for symbol in symbols {
+                self.symbol_table.insert_symbol(symbol);
+            }
+        }
+
         let mut values_buffer: Vec<u8> = values
             .into_iter()
             .map(|value| self.encode_value(&value))
@@ -83,14 +261,33 @@ impl IonEncoder {
 
         let mut symbol_table = self.encode_current_symbol_table();
 
-        let mut buffer = IonEncoder::get_ion_1_0_header();
+        if let Some(context) = context {
+            self.symbol_table = context;
+        }
+
+        let mut buffer = if self.write_ivm {
+            IonEncoder::get_ion_1_0_header()
+        } else {
+            vec![]
+        };
 
         buffer.append(&mut symbol_table);
         buffer.append(&mut values_buffer);
+        buffer.append(&mut encode_nop_padding(self.padding));
+
+        self.padding = 0;
 
         buffer
     }
 
+    /// Same as `encode` but the output is written to `writer`, like a file
+    /// or a socket. It returns the number of bytes written.
+    pub fn encode_to<W: Write>(&mut self, writer: &mut W) -> std::io::Result<usize> {
+        let bytes = self.encode();
+        writer.write_all(&bytes)?;
+        Ok(bytes.len())
+    }
+
     fn get_ion_1_0_header() -> Vec<u8> {
         vec![0xE0, 0x01, 0x00, 0xEA]
     }
@@ -107,20 +304,46 @@ impl IonEncoder {
             IonValue::Clob(value) => encode_blob(9, value),
             IonValue::Blob(value) => encode_blob(10, value),
             IonValue::DateTime(value) => encode_datetime(value),
+            IonValue::DateTimeUnknownOffset(value) => encode_datetime_unknown_offset(value),
+            IonValue::Timestamp(value) => encode_ion_timestamp(value),
             IonValue::List(value) => self.encode_list(value, false),
             IonValue::SExpr(value) => self.encode_list(value, true),
             IonValue::Symbol(symbol) => self.encode_symbol(symbol),
-            IonValue::Struct(value) => self.encode_struct(value),
+            IonValue::Struct(value) if self.symbol_order == EncodeSymbolOrder::Sorted => {
+                let mut fields: Vec<_> = value.iter().collect();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
+
+                self.encode_struct(fields.into_iter())
+            }
+            IonValue::Struct(value) => self.encode_struct(value.iter()),
+            IonValue::OrderedStruct(value) => {
+                self.encode_struct(value.iter().map(|(key, value)| (key, value)))
+            }
             IonValue::Annotation(annotations, value) => self.encode_annotation(annotations, value),
         }
     }
 
-    pub(crate) fn encode_symbol(&mut self, symbol: &str) -> Vec<u8> {
+    pub(crate) fn encode_symbol(&mut self, symbol: &SymbolToken) -> Vec<u8> {
         let mut buffer: Vec<u8> = vec![];
 
         let mut header: u8 = 0x70;
 
-        let id = self.symbol_table.insert_symbol(symbol);
+        let id = match symbol {
+            // Symbols built with `IonValue::symbol_in` already have the id.
+            SymbolToken {
+                text: Some(text),
+                id: Some(id),
+            } if self.has_symbol(*id, text) => usize::try_from(*id).unwrap(),
+            SymbolToken {
+                text: Some(text), ..
+            } => self.symbol_table.insert_symbol(text),
+            // Only the system and the imported symbols have the same id in
+            // the written table, the rest are written as unknown text.
+            SymbolToken { id: Some(id), .. } if *id <= self.max_id_only_symbol() => {
+                usize::try_from(*id).unwrap()
+            }
+            _ => SystemSymbolIds::Zero as usize,
+        };
 
         let mut id_bytes = encode_uint(&BigUint::from(id));
         let id_bytes_len = id_bytes.len();
@@ -176,7 +399,7 @@ impl IonEncoder {
 
     pub(crate) fn encode_annotation(
         &mut self,
-        annotations: &[String],
+        annotations: &[Arc<str>],
         value: &IonValue,
     ) -> Vec<u8> {
         let mut annot_buffer: Vec<u8> = vec![];
@@ -219,10 +442,14 @@ impl IonEncoder {
         final_buffer
     }
 
-    pub(crate) fn encode_struct(&mut self, value: &HashMap<String, IonValue>) -> Vec<u8> {
+    /// Fields are encoded in the order given by the iterator.
+    pub(crate) fn encode_struct<'a>(
+        &mut self,
+        fields: impl Iterator<Item = (&'a Arc<str>, &'a IonValue)>,
+    ) -> Vec<u8> {
         let mut content_buffer: Vec<u8> = vec![];
 
-        for (key, value) in value {
+        for (key, value) in fields {
             let symbol = self.symbol_table.insert_symbol(key);
             let mut symbol_bytes = encode_varuint(&symbol.to_be_bytes());
             let mut value_bytes = self.encode_value(value);
@@ -252,20 +479,74 @@ impl IonEncoder {
         buffer
     }
 
+    fn has_symbol(&self, id: u64, text: &str) -> bool {
+        let symbol = usize::try_from(id)
+            .ok()
+            .and_then(|id| self.symbol_table.get_symbol_by_id(id));
+
+        matches!(symbol, Some(Symbol::Symbol(symbol)) if **symbol == *text)
+    }
+
+    // Symbols without text are written by id, so they need to be in the
+    // system table or in an import.
+    fn max_id_only_symbol(&self) -> u64 {
+        (SYSTEM_SYMBOL_TABLE.len() + self.imported_symbols_len() - 1) as u64
+    }
+
+    fn imported_symbols_len(&self) -> usize {
+        self.imports
+            .iter()
+            .map(|import| import.max_len.unwrap_or(0))
+            .sum()
+    }
+
+    fn encode_imports(&self) -> IonValue {
+        let imports = self
+            .imports
+            .iter()
+            .map(|import| {
+                IonValue::OrderedStruct(vec![
+                    (
+                        SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Name as usize].into(),
+                        IonValue::String(import.name.clone()),
+                    ),
+                    (
+                        SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Version as usize].into(),
+                        IonValue::Integer(import.version.unwrap_or(1).into()),
+                    ),
+                    (
+                        SYSTEM_SYMBOL_TABLE[SystemSymbolIds::MaxId as usize].into(),
+                        IonValue::Integer(import.max_len.unwrap_or(0) as i64),
+                    ),
+                ])
+            })
+            .collect();
+
+        IonValue::List(imports)
+    }
+
     pub(crate) fn encode_current_symbol_table(&mut self) -> Vec<u8> {
         let symbols = self.symbol_table.dump_all_local_symbols();
+        let symbols = symbols.into_iter().skip(self.imported_symbols_len());
 
-        let symbols = IonValue::List(symbols.into_iter().map(IonValue::String).collect());
+        let symbols = IonValue::List(symbols.map(IonValue::String).collect());
 
-        let mut annotation_struct = HashMap::new();
+        // The fields are written in a fixed order, with the imports first,
+        // so the same table always produces the same bytes.
+        let mut annotation_struct = vec![];
 
-        let symbols_symbol = SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Symbols as usize].to_string();
+        if !self.imports.is_empty() {
+            let imports_symbol = SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Imports as usize].into();
+            annotation_struct.push((imports_symbol, self.encode_imports()));
+        }
+
+        let symbols_symbol = SYSTEM_SYMBOL_TABLE[SystemSymbolIds::Symbols as usize].into();
         let local_table_annotation_symbol =
-            SYSTEM_SYMBOL_TABLE[SystemSymbolIds::IonSymbolTable as usize].to_string();
+            SYSTEM_SYMBOL_TABLE[SystemSymbolIds::IonSymbolTable as usize].into();
 
-        annotation_struct.insert(symbols_symbol, symbols);
+        annotation_struct.push((symbols_symbol, symbols));
 
-        let annotation_struct = IonValue::Struct(annotation_struct);
+        let annotation_struct = IonValue::OrderedStruct(annotation_struct);
 
         let annotation = IonValue::Annotation(
             vec![local_table_annotation_symbol],
@@ -275,3 +556,74 @@ impl IonEncoder {
         self.encode_value(&annotation)
     }
 }
+
+// Checks that the value can be encoded and read back as the same value.
+fn validate_value(value: &IonValue, max_symbol_id: u64) -> Result<(), IonParserError> {
+    let validate = |value| validate_value(value, max_symbol_id);
+
+    match value {
+        IonValue::Symbol(SymbolToken {
+            text: None,
+            id: None,
+        }) => Err(IonParserError::SymbolWithoutTextOrId),
+        IonValue::Symbol(SymbolToken {
+            text: None,
+            id: Some(id),
+        }) if *id > max_symbol_id => Err(IonParserError::SymbolIdNotImported(*id)),
+        IonValue::DateTime(value) => validate_year(value.naive_utc().year()),
+        IonValue::DateTimeUnknownOffset(value) => validate_year(value.year()),
+        IonValue::Timestamp(value) => validate_year(value.utc().year()),
+        IonValue::List(values) | IonValue::SExpr(values) => values.iter().try_for_each(validate),
+        IonValue::Struct(fields) => fields.values().try_for_each(validate),
+        IonValue::OrderedStruct(fields) => fields.iter().try_for_each(|(_, value)| validate(value)),
+        IonValue::Annotation(annotations, _) if annotations.is_empty() => {
+            Err(IonParserError::EmptyAnnotationList)
+        }
+        IonValue::Annotation(_, value) if matches!(**value, IonValue::Annotation(..)) => {
+            Err(IonParserError::NestedAnnotations)
+        }
+        IonValue::Annotation(_, value) => validate(value),
+        _ => Ok(()),
+    }
+}
+
+fn validate_year(year: i32) -> Result<(), IonParserError> {
+    if (1..=9999).contains(&year) {
+        Ok(())
+    } else {
+        Err(IonParserError::TimestampYearOutOfRange(year))
+    }
+}
+
+// Gathers the text of the symbols, struct keys and annotations of the value.
+fn collect_symbols<'a>(value: &'a IonValue, symbols: &mut BTreeSet<&'a str>) {
+    match value {
+        IonValue::Symbol(symbol) => {
+            if let Some(text) = symbol.text() {
+                symbols.insert(text);
+            }
+        }
+        IonValue::List(values) | IonValue::SExpr(values) => {
+            for value in values {
+                collect_symbols(value, symbols);
+            }
+        }
+        IonValue::Struct(values) => {
+            for (key, value) in values {
+                symbols.insert(key);
+                collect_symbols(value, symbols);
+            }
+        }
+        IonValue::OrderedStruct(values) => {
+            for (key, value) in values {
+                symbols.insert(key);
+                collect_symbols(value, symbols);
+            }
+        }
+        IonValue::Annotation(annotations, value) => {
+            symbols.extend(annotations.iter().map(|annotation| &**annotation));
+            collect_symbols(value, symbols);
+        }
+        _ => {}
+    }
+}
@@ -1,9 +1,12 @@
-use crate::ion_hash_encoder::encode_value;
-use crate::IonValue;
+use crate::ion_hash_encoder::{
+    add_markers, encode_sorted_struct_field_hashes, encode_value, hash_struct_field,
+};
+use crate::{IonParserError, IonValue};
 use digest::Digest;
 use sha2::Sha256;
 use std::cmp::{Ordering, PartialEq};
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Ion Hash implementation. Once the hasher is initialized you can add new values to it
 /// and it will perform the dot operation internally. Once you added everything you want
@@ -12,6 +15,10 @@ use std::marker::PhantomData;
 ///
 /// You can use the method digest if you want to hash only one IonValue.
 ///
+/// Negative zero decimals can't be represented by `IonValue::Decimal`, so a
+/// value parsed from `-0d0` gets the hash of `0d0`, which is not the one other
+/// Ion Hash implementations give for `-0d0`.
+///
 /// ```rust,no_run
 /// use sha2::Sha256;
 /// use ion_binary_rs::{IonHash, IonValue};
@@ -19,15 +26,15 @@ use std::marker::PhantomData;
 ///
 /// let mut ion_struct = HashMap::new();
 ///
-/// ion_struct.insert("Model".to_string(), IonValue::String("CLK 350".to_string()));
-/// ion_struct.insert("Type".to_string(), IonValue::String("Sedan".to_string()));
-/// ion_struct.insert("Color".to_string(), IonValue::String("White".to_string()));
+/// ion_struct.insert("Model".into(), IonValue::String("CLK 350".to_string()));
+/// ion_struct.insert("Type".into(), IonValue::String("Sedan".to_string()));
+/// ion_struct.insert("Color".into(), IonValue::String("White".to_string()));
 /// ion_struct.insert(
-///     "VIN".to_string(),
+///     "VIN".into(),
 ///     IonValue::String("1C4RJFAG0FC625797".to_string()),
 /// );
-/// ion_struct.insert("Make".to_string(), IonValue::String("Mercedes".to_string()));
-/// ion_struct.insert("Year".to_string(), IonValue::Integer(2019));
+/// ion_struct.insert("Make".into(), IonValue::String("Mercedes".to_string()));
+/// ion_struct.insert("Year".into(), IonValue::Integer(2019));
 ///
 /// let ion_value = IonValue::Struct(ion_struct);
 ///
@@ -36,9 +43,15 @@ use std::marker::PhantomData;
 /// println!("{:X?}", hash);
 /// ```
 ///
+/// When the hash is created from a struct with `from_ion_value`, it keeps
+/// the hash of each field so a change in a field can be applied with
+/// `update_struct_field` without hashing the whole struct again.
 #[derive(Debug)]
 pub struct IonHash<D: Digest = Sha256> {
     buffer: Vec<u8>,
+    // Sorted field hashes of the struct this hash comes from. Any other
+    // operation makes them stale, so they are dropped.
+    struct_fields: Option<Vec<Vec<u8>>>,
     hasher_type: PhantomData<D>,
 }
 
@@ -78,6 +91,8 @@ impl<D: Digest> IonHash<D> {
             return self;
         }
 
+        self.struct_fields = None;
+
         if self.buffer.is_empty() {
             self.buffer = value.buffer;
             return self;
@@ -98,11 +113,43 @@ impl<D: Digest> IonHash<D> {
         self
     }
 
+    /// Goes back to the empty hash, the same as a new `IonHash`, so the
+    /// instance can be reused to hash another value.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.struct_fields = None;
+    }
+
     /// Gets the current hash. Useful for when you need to
     /// extract the final result after several operations.
     pub fn get(&self) -> &[u8] {
         &self.buffer
     }
+
+    /// Replaces the hash of one field of the struct this hash was created
+    /// from (see `IonHash::struct_field_hash`) and recomputes the struct
+    /// hash, giving the same result as hashing the modified struct. It only
+    /// works for hashes created from a struct with `from_ion_value` and not
+    /// modified since. It returns false, leaving the hash untouched, when
+    /// that is not the case or the old field hash is not in the struct.
+    pub fn update_struct_field(&mut self, old_field_hash: &[u8], new_field_hash: &[u8]) -> bool {
+        let fields = match &mut self.struct_fields {
+            Some(fields) => fields,
+            None => return false,
+        };
+
+        let position = match fields.iter().position(|hash| hash == old_field_hash) {
+            Some(position) => position,
+            None => return false,
+        };
+
+        fields[position] = new_field_hash.to_vec();
+        fields.sort_unstable();
+
+        self.buffer = IonHash::hash_struct_field_hashes::<D>(fields);
+
+        true
+    }
 }
 
 impl IonHash {
@@ -110,6 +157,7 @@ impl IonHash {
     pub fn new() -> IonHash {
         IonHash {
             buffer: vec![],
+            struct_fields: None,
             hasher_type: PhantomData,
         }
     }
@@ -125,6 +173,7 @@ impl IonHash {
     pub fn from_hashes_bytes<D: Digest>(buf: &[u8]) -> IonHash<D> {
         IonHash {
             buffer: buf.to_vec(),
+            struct_fields: None,
             hasher_type: PhantomData,
         }
     }
@@ -132,6 +181,27 @@ impl IonHash {
     /// Creates a hasher with some starting Ion Value which will
     /// be first serialized and hashed
     pub fn from_ion_value<D: Digest>(value: &IonValue) -> IonHash<D> {
+        let fields: Option<Vec<(&Arc<str>, &IonValue)>> = match value {
+            IonValue::Struct(values) => Some(values.iter().collect()),
+            IonValue::OrderedStruct(values) => Some(values.iter().map(|(k, v)| (k, v)).collect()),
+            _ => None,
+        };
+
+        if let Some(fields) = fields {
+            let mut field_hashes: Vec<Vec<u8>> = fields
+                .into_iter()
+                .map(|(name, value)| hash_struct_field::<D>(name, value).to_vec())
+                .collect();
+
+            field_hashes.sort_unstable();
+
+            return IonHash {
+                buffer: IonHash::hash_struct_field_hashes::<D>(&field_hashes),
+                struct_fields: Some(field_hashes),
+                hasher_type: PhantomData,
+            };
+        }
+
         let mut hash = IonHash::with_hasher::<D>();
 
         hash.add_ion_value(value);
@@ -143,15 +213,76 @@ impl IonHash {
     pub fn with_hasher<D: Digest>() -> IonHash<D> {
         IonHash {
             buffer: vec![],
+            struct_fields: None,
             hasher_type: PhantomData,
         }
     }
 
+    /// Hashes one field of a struct the way it is hashed as part of the
+    /// struct, for `update_struct_field`.
+    pub fn struct_field_hash<D: Digest>(name: &str, value: &IonValue) -> Vec<u8> {
+        hash_struct_field::<D>(name, value).to_vec()
+    }
+
+    /// Returns the digest of each field of the struct, useful to find which
+    /// field causes a hash mismatch. Struct fields are sorted by name and
+    /// ordered struct fields keep their order. It returns `None` if the
+    /// value is not a struct.
+    pub fn struct_field_hashes<D: Digest>(value: &IonValue) -> Option<Vec<(Arc<str>, Vec<u8>)>> {
+        let mut fields: Vec<(&Arc<str>, &IonValue)> = match value {
+            IonValue::Struct(values) => values.iter().collect(),
+            IonValue::OrderedStruct(values) => {
+                values.iter().map(|(name, value)| (name, value)).collect()
+            }
+            _ => return None,
+        };
+
+        if let IonValue::Struct(_) = value {
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+        }
+
+        Some(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.clone(), IonHash::struct_field_hash::<D>(name, value)))
+                .collect(),
+        )
+    }
+
+    fn hash_struct_field_hashes<D: Digest>(field_hashes: &[Vec<u8>]) -> Vec<u8> {
+        let buffer = add_markers(encode_sorted_struct_field_hashes::<D, _>(field_hashes));
+
+        D::digest(&buffer).to_vec()
+    }
+
     /// Shorthand method for hashing an Ion Value in one step.
     pub fn digest<D: Digest>(value: &IonValue) -> Vec<u8> {
         IonHash::from_ion_value::<D>(value).get().to_vec()
     }
 
+    /// Hashes the Ion Value and checks that the result is the expected
+    /// hash. The comparison takes the same time wherever the first
+    /// different byte is, so it doesn't leak how much of the hash matched.
+    pub fn verify<D: Digest>(value: &IonValue, expected: &[u8]) -> bool {
+        constant_time_eq(&IonHash::digest::<D>(value), expected)
+    }
+
+    /// Checks the hash of a QLDB document revision, a struct with the
+    /// `hash`, `data` and `metadata` fields. The revision hash is the dot
+    /// of the hashes of `data` and `metadata`, computed with Sha256 as QLDB
+    /// does. It returns an error if any of the fields is missing or `hash`
+    /// is not a blob. Redacted revisions (without `data`) are not supported.
+    pub fn verify_qldb_revision(revision: &IonValue) -> Result<bool, IonParserError> {
+        let hash: Vec<u8> = revision.get_as("hash")?;
+        let data = revision.get_field("data")?;
+        let metadata = revision.get_field("metadata")?;
+
+        let mut revision_hash = IonHash::from_ion_value::<Sha256>(data);
+        revision_hash.dot(IonHash::from_ion_value::<Sha256>(metadata));
+
+        Ok(constant_time_eq(revision_hash.get(), &hash))
+    }
+
     /// Shorthand method for hashing an Ion Value in one step.
     /// It uses the default hasher: Sha256
     pub fn default_digest(value: &IonValue) -> Vec<u8> {
@@ -159,6 +290,20 @@ impl IonHash {
     }
 }
 
+// The time taken doesn't depend on where the first different byte is.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let difference = a
+        .iter()
+        .zip(b)
+        .fold(0u8, |difference, (a, b)| difference | (a ^ b));
+
+    difference == 0
+}
+
 impl Default for IonHash {
     fn default() -> IonHash<Sha256> {
         IonHash::with_hasher::<Sha256>()
@@ -180,3 +325,69 @@ impl<D: Digest> PartialOrd for IonHash<D> {
             .partial_cmp(value.get().iter().rev().map(|byte| *byte as i8))
     }
 }
+
+/// Combines the hashes of several documents into one digest for QLDB style
+/// block verification: the document hashes are sorted and then joined with
+/// the IonHash dot operation. Given that they are sorted, the
+/// result doesn't depend on the order in which the documents are added.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{DocumentHasher, IonValue};
+///
+/// let mut hasher = DocumentHasher::new();
+///
+/// hasher.add_document(&IonValue::String("Document 1".to_string()));
+/// hasher.add_document(&IonValue::String("Document 2".to_string()));
+///
+/// println!("{:X?}", hasher.digest());
+/// ```
+#[derive(Debug)]
+pub struct DocumentHasher<D: Digest = Sha256> {
+    hashes: Vec<IonHash<D>>,
+}
+
+impl<D: Digest> DocumentHasher<D> {
+    /// Hashes the document using IonHash and adds it to the set.
+    pub fn add_document(&mut self, value: &IonValue) {
+        self.hashes.push(IonHash::from_ion_value::<D>(value));
+    }
+
+    /// Adds an already computed document hash to the set.
+    pub fn add_document_hash(&mut self, hash: &[u8]) {
+        self.hashes.push(IonHash::from_hashes_bytes::<D>(hash));
+    }
+
+    /// Returns the combined digest of all the documents. It is empty when
+    /// no document was added.
+    pub fn digest(&self) -> Vec<u8> {
+        let mut hashes: Vec<&IonHash<D>> = self.hashes.iter().collect();
+
+        hashes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let mut result = IonHash::with_hasher::<D>();
+
+        for hash in hashes {
+            result.dot(IonHash::from_hashes_bytes::<D>(hash.get()));
+        }
+
+        result.get().to_vec()
+    }
+}
+
+impl DocumentHasher {
+    /// Creates an empty document hasher with the default hasher: Sha256
+    pub fn new() -> DocumentHasher {
+        DocumentHasher::with_hasher::<Sha256>()
+    }
+
+    /// Creates an empty document hasher using the provided hasher
+    pub fn with_hasher<D: Digest>() -> DocumentHasher<D> {
+        DocumentHasher { hashes: vec![] }
+    }
+}
+
+impl Default for DocumentHasher {
+    fn default() -> DocumentHasher<Sha256> {
+        DocumentHasher::new()
+    }
+}
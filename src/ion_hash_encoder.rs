@@ -1,12 +1,15 @@
-use crate::binary_encoder::{encode_datetime_representation, encode_int, encode_varint};
+use crate::binary_encoder::{
+    encode_datetime_representation, encode_datetime_unknown_offset_representation, encode_int,
+    encode_ion_timestamp_representation, encode_varint,
+};
 use crate::binary_parser_types::{SystemSymbolIds, SYSTEM_SYMBOL_TABLE};
-use crate::{IonValue, NullIonValue};
+use crate::{IonTimestamp, IonValue, NullIonValue};
 use bigdecimal::BigDecimal;
 use bigdecimal::Zero;
-use chrono::{DateTime, FixedOffset};
-use digest::Digest;
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use digest::{Digest, Output};
 use num_bigint::{BigInt, Sign};
-use std::collections::HashMap;
+use std::sync::Arc;
 
 pub fn encode_value<D: Digest>(value: &IonValue) -> Vec<u8> {
     let encoded_value = match value {
@@ -17,20 +20,28 @@ pub fn encode_value<D: Digest>(value: &IonValue) -> Vec<u8> {
         IonValue::Float(value) => encode_float_value(value),
         IonValue::Decimal(value) => encode_decimal_value(value),
         IonValue::DateTime(value) => encode_datetime_value(value),
+        IonValue::DateTimeUnknownOffset(value) => encode_datetime_unknown_offset_value(value),
+        IonValue::Timestamp(value) => encode_ion_timestamp_value(value),
         IonValue::String(value) => encode_string(value, 0x80),
-        IonValue::Symbol(value) => encode_symbol(value),
+        IonValue::Symbol(value) => match value.text() {
+            Some(text) => encode_symbol(text),
+            None => vec![0x71],
+        },
         IonValue::Clob(value) => encode_blob(value, 0x90),
         IonValue::Blob(value) => encode_blob(value, 0xA0),
         IonValue::List(value) => encode_list::<D>(value, 0xB0),
         IonValue::SExpr(value) => encode_list::<D>(value, 0xC0),
-        IonValue::Struct(value) => encode_struct::<D>(value),
+        IonValue::Struct(value) => encode_struct::<D>(value.iter(), value.len()),
+        IonValue::OrderedStruct(value) => {
+            encode_struct::<D>(value.iter().map(|(name, value)| (name, value)), value.len())
+        }
         IonValue::Annotation(annotations, value) => encode_annotation::<D>(annotations, value),
     };
 
     add_markers(encoded_value)
 }
 
-fn add_markers(mut encoded_value: Vec<u8>) -> Vec<u8> {
+pub fn add_markers(mut encoded_value: Vec<u8>) -> Vec<u8> {
     let mut buffer = vec![0x0B];
     buffer.append(&mut encoded_value);
     buffer.push(0x0E);
@@ -38,11 +49,11 @@ fn add_markers(mut encoded_value: Vec<u8>) -> Vec<u8> {
     buffer
 }
 
-fn encode_annotation<D: Digest>(annotations: &[String], value: &IonValue) -> Vec<u8> {
+fn encode_annotation<D: Digest>(annotations: &[Arc<str>], value: &IonValue) -> Vec<u8> {
     let mut buffer = vec![0xE0];
 
     for annotation in annotations {
-        buffer.append(&mut encode_value::<D>(&IonValue::Symbol(annotation.into())));
+        buffer.append(&mut add_markers(encode_symbol(annotation)));
     }
 
     buffer.append(&mut encode_value::<D>(value));
@@ -50,26 +61,42 @@ fn encode_annotation<D: Digest>(annotations: &[String], value: &IonValue) -> Vec
     buffer
 }
 
-fn encode_struct<D: Digest>(values: &HashMap<String, IonValue>) -> Vec<u8> {
-    let mut hashes: Vec<Vec<u8>> = vec![];
+// The field hashes are sorted, so the order of the fields doesn't matter.
+fn encode_struct<'a, D: Digest>(
+    values: impl Iterator<Item = (&'a Arc<str>, &'a IonValue)>,
+    len: usize,
+) -> Vec<u8> {
+    let mut hashes: Vec<Output<D>> = Vec::with_capacity(len);
 
     for (name, value) in values {
-        let mut buffer = add_markers(encode_symbol(name));
-        buffer.append(&mut encode_value::<D>(value));
-        let hash = D::digest(&buffer).to_vec();
-        hashes.push(hash);
+        hashes.push(hash_struct_field::<D>(name, value));
     }
 
-    hashes.sort();
+    // All digests have the same length, so equal elements are identical and
+    // the unstable sort gives the same result as a stable one.
+    hashes.sort_unstable();
+
+    encode_sorted_struct_field_hashes::<D, _>(&hashes)
+}
 
-    let hashes_buffer: Vec<u8> = hashes.into_iter().flatten().collect();
+pub fn hash_struct_field<D: Digest>(name: &str, value: &IonValue) -> Output<D> {
+    let mut hasher = D::new();
+    hasher.update(add_markers(encode_symbol(name)));
+    hasher.update(encode_value::<D>(value));
+    hasher.finalize()
+}
 
-    let mut buffer: Vec<u8> = escape_buffer(&hashes_buffer);
+// Builds the struct representation from its already sorted field hashes,
+// without the begin and end markers.
+pub fn encode_sorted_struct_field_hashes<D: Digest, H: AsRef<[u8]>>(hashes: &[H]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(1 + hashes.len() * D::output_size());
+    buffer.push(0xD0);
 
-    let mut header = vec![0xD0];
-    header.append(&mut buffer);
+    for hash in hashes {
+        escape_into(hash.as_ref(), &mut buffer);
+    }
 
-    header
+    buffer
 }
 
 fn encode_list<D: Digest>(values: &[IonValue], header: u8) -> Vec<u8> {
@@ -114,6 +141,26 @@ fn encode_datetime_value(value: &DateTime<FixedOffset>) -> Vec<u8> {
     buffer
 }
 
+fn encode_datetime_unknown_offset_value(value: &NaiveDateTime) -> Vec<u8> {
+    let mut buffer = vec![0x60];
+
+    buffer.append(&mut escape_buffer(
+        &encode_datetime_unknown_offset_representation(value),
+    ));
+
+    buffer
+}
+
+fn encode_ion_timestamp_value(value: &IonTimestamp) -> Vec<u8> {
+    let mut buffer = vec![0x60];
+
+    buffer.append(&mut escape_buffer(&encode_ion_timestamp_representation(
+        value,
+    )));
+
+    buffer
+}
+
 // Warning:
 //
 // BigDecimal doesn't distinguish between -0 and 0, but Ion does, so -0 get
@@ -242,8 +289,14 @@ fn encode_null_value(value: &NullIonValue) -> Vec<u8> {
 }
 
 fn escape_buffer(buffer: &[u8]) -> Vec<u8> {
-    let mut escaped_buffer = vec![];
+    let mut escaped_buffer = Vec::with_capacity(buffer.len());
 
+    escape_into(buffer, &mut escaped_buffer);
+
+    escaped_buffer
+}
+
+fn escape_into(buffer: &[u8], escaped_buffer: &mut Vec<u8>) {
     for byte in buffer {
         match byte {
             0x0B | 0x0C | 0x0E => {
@@ -253,8 +306,6 @@ fn escape_buffer(buffer: &[u8]) -> Vec<u8> {
             _ => escaped_buffer.push(*byte),
         }
     }
-
-    escaped_buffer
 }
 
 /*// Seems that 123.4f64.to_be_bytes() equals to [64, 94, 217, 153, 153, 153, 153, 154]
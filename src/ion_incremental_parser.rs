@@ -0,0 +1,129 @@
+use crate::ion_parser::{ConsumerResult, IonParser};
+use crate::symbol_table::{Symbol, SymbolContext, SymbolContextError};
+use crate::{IonParserConfig, IonParserError, ParsingError};
+use std::sync::Arc;
+
+/// Parser for sources where the data arrives in pieces, like non-blocking
+/// sockets. Bytes are added with `feed` and, when the buffered bytes don't
+/// contain a whole value yet, `consume_value` returns
+/// `IonParserError::Incomplete` instead of failing, so it can be called again
+/// once more bytes are fed.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IncrementalIonParser, IonParserError, IonValue};
+///
+/// let mut parser = IncrementalIonParser::new();
+///
+/// parser.feed(&b"\xe0\x01\0\xea\x83ab"[..]);
+///
+/// assert!(matches!(
+///     parser.consume_value(),
+///     Err(IonParserError::Incomplete { .. })
+/// ));
+///
+/// parser.feed(&b"c"[..]);
+///
+/// assert_eq!(
+///     parser.consume_value().unwrap().0,
+///     IonValue::String("abc".to_string())
+/// );
+/// ```
+///
+/// Every call parses the pending value from its beginning, so feeding a big
+/// value in tiny pieces is slow. The `needed` field of the error is only a
+/// hint: it is exact when the length of the value is known, otherwise it is 1.
+#[derive(Debug)]
+pub struct IncrementalIonParser {
+    buffer: Vec<u8>,
+    context: SymbolContext,
+    config: IonParserConfig,
+    ion_version: Option<(u8, u8)>,
+}
+
+impl Default for IncrementalIonParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalIonParser {
+    pub fn new() -> IncrementalIonParser {
+        IncrementalIonParser::with_config(IonParserConfig::default())
+    }
+
+    /// Creates a new parser using the provided configuration. Check
+    /// `IonParserConfig` for the available options.
+    pub fn with_config(config: IonParserConfig) -> IncrementalIonParser {
+        IncrementalIonParser {
+            buffer: vec![],
+            context: SymbolContext::new(),
+            config,
+            ion_version: None,
+        }
+    }
+
+    /// Allows to set up shared tables, same as `IonParser::with_shared_table`.
+    pub fn with_shared_table(
+        &mut self,
+        name: String,
+        version: u32,
+        symbols: &[String],
+    ) -> Result<(), SymbolContextError> {
+        let symbols: Vec<Symbol> = symbols
+            .iter()
+            .map(|s| Symbol::Symbol(Arc::from(s.as_str())))
+            .collect();
+
+        self.context.add_shared_table(name, version, &symbols)
+    }
+
+    /// Adds the bytes to the data pending to be parsed.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Amount of bytes fed but not consumed yet.
+    pub fn pending_bytes(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Consumes **one** IonValue, same as `IonParser::consume_value`. If the
+    /// value is not complete nothing is consumed and
+    /// `IonParserError::Incomplete` is returned. Symbol tables are only applied
+    /// once the value that follows them is complete.
+    ///
+    /// Every attempt parses against a clone of the symbol context. Cloning a
+    /// `SymbolContext` only shares its tables, they are copied only if the
+    /// value is preceded by a symbol table that appends to the current one.
+    pub fn consume_value(&mut self) -> ConsumerResult {
+        let mut parser =
+            IonParser::with_context(&self.buffer[..], self.config.clone(), self.context.clone());
+
+        if let Some(version) = self.ion_version {
+            parser.set_ion_version(version);
+        }
+
+        match parser.consume_value() {
+            Ok(value) => {
+                let consumed_bytes = parser.get_consumed_bytes();
+
+                self.ion_version = parser.get_ion_version();
+                self.context = parser.into_context();
+                self.buffer.drain(..consumed_bytes);
+
+                Ok(value)
+            }
+            Err(IonParserError::BinaryError(ParsingError::NoDataToRead))
+            | Err(IonParserError::BinaryError(ParsingError::NotEnoughtDataToRead(_)))
+            | Err(IonParserError::TruncatedSymbolTable) => {
+                let needed = match parser.get_current_value_end() {
+                    Some(end) if end > self.buffer.len() => end - self.buffer.len(),
+                    _ => 1,
+                };
+
+                Err(IonParserError::Incomplete { needed })
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
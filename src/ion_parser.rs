@@ -2,12 +2,14 @@ use crate::binary_parser::IonBinaryParser;
 use crate::binary_parser_types::*;
 use crate::ion_parser_types::*;
 use crate::symbol_table::*;
+use crate::{IonTimestamp, TimestampPrecision};
 use bigdecimal::BigDecimal;
 use chrono::{naive::NaiveDate, DateTime, FixedOffset, Utc};
 use log::trace;
-use num_bigint::{BigInt, BigUint};
+use num_bigint::{BigInt, BigUint, Sign};
 use num_traits::ops::checked::CheckedSub;
 use std::convert::{TryFrom, TryInto};
+use std::sync::Arc;
 use std::{collections::HashMap, io::Read};
 
 /// In order to use it call the new method and then the "consume_all" method.
@@ -31,20 +33,417 @@ use std::{collections::HashMap, io::Read};
 pub struct IonParser<T: Read> {
     parser: IonBinaryParser<T>,
     context: SymbolContext,
+    config: IonParserConfig,
+    container_ends: Vec<usize>,
+    current_value_end: Option<usize>,
+    annotation_callback: Option<AnnotationHook>,
+    symbol_table_callback: Option<SymbolTableHook>,
+    stats: ParseStats,
+}
+
+/// Statistics of what the parser has read so far, collected when
+/// `IonParserConfig::collect_stats` is enabled. Otherwise everything is 0.
+///
+/// - `value_counts`: values parsed of each type, including the nested ones
+///   and the ones of the symbol tables. NOP padding is not counted.
+/// - `max_depth`: deepest container nesting found, 0 when there are only
+///   scalars.
+/// - `total_bytes`: bytes read from the stream.
+/// - `symbol_table_updates`: local and shared symbol tables loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParseStats {
+    pub value_counts: HashMap<ValueType, usize>,
+    pub max_depth: usize,
+    pub total_bytes: usize,
+    pub symbol_table_updates: usize,
+}
+
+/// Function called for every top level annotation that is not a symbol
+/// table declaration. Check `IonParser::on_annotation`.
+pub type AnnotationCallback = Box<dyn FnMut(&[Arc<str>], &IonValue)>;
+
+struct AnnotationHook(AnnotationCallback);
+
+impl std::fmt::Debug for AnnotationHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AnnotationHook")
+    }
+}
+
+/// Function called with the bytes of every top level symbol table
+/// declaration. Check `IonParser::on_symbol_table`.
+pub type SymbolTableCallback = Box<dyn FnMut(&[u8])>;
+
+struct SymbolTableHook(SymbolTableCallback);
+
+impl std::fmt::Debug for SymbolTableHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SymbolTableHook")
+    }
+}
+
+/// Groups all the options that change how the IonParser behaves. Use it
+/// together with `IonParser::with_config`. `IonParser::new` uses the
+/// default configuration.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonParser, IonParserConfig};
+///
+/// let ion_test = b"\xe0\x01\0\xea\xb2\xb1\xb0";
+///
+/// let config = IonParserConfig {
+///     max_depth: Some(2),
+///     ..IonParserConfig::default()
+/// };
+///
+/// let mut parser = IonParser::with_config(&ion_test[..], config);
+///
+/// assert!(parser.consume_value().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IonParserConfig {
+    /// Maximum nesting of containers (lists, sexps and structs) allowed.
+    /// Going deeper returns `IonParserError::MaxDepthExceeded`. `None`
+    /// means that there is no limit.
+    pub max_depth: Option<usize>,
+    /// Return structs as `IonValue::OrderedStruct`, keeping the fields in the
+    /// same order as in the binary, so they can be encoded back in that order.
+    /// Disabled by default.
+    pub preserve_struct_order: bool,
+    /// Return timestamps as `IonValue::Timestamp`, keeping their precision
+    /// and offset, so they can be encoded and hashed back as they were.
+    /// Disabled by default.
+    pub preserve_timestamp_precision: bool,
+    /// Collect the statistics returned by `IonParser::stats`. Disabled by
+    /// default, as it adds some work for every value.
+    pub collect_stats: bool,
+    /// Maximum number of digits of a decimal coefficient. Longer ones return
+    /// `IonParserError::DecimalTooLarge`, which protects against huge
+    /// decimals that are very expensive to operate with. The binary parser
+    /// checks it with the size of the coefficient before reading it, so a
+    /// coefficient padded with zero bytes past the limit is rejected too.
+    /// `None` means that there is no limit. The default is
+    /// `DEFAULT_MAX_DECIMAL_DIGITS`.
+    pub max_decimal_digits: Option<usize>,
+    /// Maximum number of symbols of a local symbol table, counting the
+    /// imported ones but not the system symbols. Bigger tables return
+    /// `IonParserError::TooManySymbols`. `None` means that there is no limit.
+    pub max_symbols: Option<usize>,
+    /// Maximum length in bytes of a single value, as declared in its header.
+    /// Longer ones return `IonParserError::ValueTooLarge` before their
+    /// content is read, so a corrupted or malicious length can't make the
+    /// parser allocate or wait for that many bytes. The length of a
+    /// container includes all its elements. Only the binary parser checks
+    /// it. `None` means that there is no limit.
+    pub max_value_length: Option<usize>,
+}
+
+/// Default value of `IonParserConfig::max_decimal_digits`. It is far more than
+/// any real amount needs, while keeping the decimals cheap to handle.
+pub const DEFAULT_MAX_DECIMAL_DIGITS: usize = 10_000;
+
+impl Default for IonParserConfig {
+    fn default() -> Self {
+        IonParserConfig {
+            max_depth: None,
+            preserve_struct_order: false,
+            preserve_timestamp_precision: false,
+            collect_stats: false,
+            max_decimal_digits: Some(DEFAULT_MAX_DECIMAL_DIGITS),
+            max_symbols: None,
+            max_value_length: None,
+        }
+    }
 }
 
 pub type ConsumerResult = Result<(IonValue, usize), IonParserError>;
 
+enum CursorState {
+    NotStarted,
+    Remaining(usize),
+    // Stopped after an error that left the parser at the start of the next
+    // element, so the rest of the container can still be skipped.
+    Stopped(usize),
+    Done,
+}
+
+/// Iterator over the elements of a list (or sexp), returned by
+/// `IonParser::list_cursor`. The elements are parsed one at a time while the
+/// cursor borrows the parser.
+pub struct ListCursor<'a, T: Read> {
+    parser: &'a mut IonParser<T>,
+    state: CursorState,
+}
+
+impl<'a, T: Read> Iterator for ListCursor<'a, T> {
+    type Item = Result<IonValue, IonParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut remaining = match self.state {
+            CursorState::Done | CursorState::Stopped(_) => return None,
+            CursorState::Remaining(remaining) => remaining,
+            CursorState::NotStarted => match self.parser.start_list_elements() {
+                Ok(Some(length)) => length,
+                Ok(None) => {
+                    self.state = CursorState::Done;
+                    return None;
+                }
+                Err(error) => {
+                    self.state = CursorState::Done;
+                    return Some(Err(error));
+                }
+            },
+        };
+
+        let element = self.parser.consume_list_element(&mut remaining);
+
+        self.state.advance(element, remaining)
+    }
+}
+
+impl<'a, T: Read> Drop for ListCursor<'a, T> {
+    fn drop(&mut self) {
+        self.parser.skip_cursor_remaining(&self.state);
+    }
+}
+
+/// Iterator over the fields of a struct, returned by
+/// `IonParser::struct_cursor`. The fields are parsed one at a time while the
+/// cursor borrows the parser.
+pub struct StructCursor<'a, T: Read> {
+    parser: &'a mut IonParser<T>,
+    state: CursorState,
+}
+
+impl<'a, T: Read> Iterator for StructCursor<'a, T> {
+    type Item = Result<(Arc<str>, IonValue), IonParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut remaining = match self.state {
+            CursorState::Done | CursorState::Stopped(_) => return None,
+            CursorState::Remaining(remaining) => remaining,
+            CursorState::NotStarted => match self.parser.start_struct_fields() {
+                Ok(Some(length)) => length,
+                Ok(None) => {
+                    self.state = CursorState::Done;
+                    return None;
+                }
+                Err(error) => {
+                    self.state = CursorState::Done;
+                    return Some(Err(error));
+                }
+            },
+        };
+
+        let field = match self.parser.consume_struct_field(&mut remaining) {
+            Ok(Some((
+                SymbolToken {
+                    text: Some(key), ..
+                },
+                value,
+            ))) => Ok(Some((key, value))),
+            Ok(Some(_)) => {
+                // The whole field was consumed, so dropping the cursor can
+                // still skip the rest of the struct.
+                self.state = CursorState::Stopped(remaining);
+                return Some(Err(IonParserError::SymbolNotFoundInTable));
+            }
+            field => field.map(|_| None),
+        };
+
+        self.state.advance(field, remaining)
+    }
+}
+
+impl<'a, T: Read> Drop for StructCursor<'a, T> {
+    fn drop(&mut self) {
+        self.parser.skip_cursor_remaining(&self.state);
+    }
+}
+
+/// Iterator over the top-level values of the stream, returned by
+/// `IonParser::values`. The end of the stream ends the iteration, and the
+/// iterator stops after returning an error.
+pub struct Values<'a, T: Read> {
+    parser: &'a mut IonParser<T>,
+    done: bool,
+}
+
+impl<'a, T: Read> Iterator for Values<'a, T> {
+    type Item = Result<IonValue, IonParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.parser.consume_value() {
+            Ok((value, _)) => Some(Ok(value)),
+            Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) => {
+                self.done = true;
+                None
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl CursorState {
+    fn advance<V>(
+        &mut self,
+        item: Result<Option<V>, IonParserError>,
+        remaining: usize,
+    ) -> Option<Result<V, IonParserError>> {
+        match item {
+            Ok(Some(value)) => {
+                *self = CursorState::Remaining(remaining);
+                Some(Ok(value))
+            }
+            Ok(None) => {
+                *self = CursorState::Done;
+                None
+            }
+            Err(error) => {
+                *self = CursorState::Done;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Result of `consume_value_recovering`. The error comes together with a flag
+/// indicating if the parser was able to move to the start of the next value.
+pub type RecoveringConsumerResult = Result<(IonValue, usize), (IonParserError, bool)>;
+
 impl<T: Read> IonParser<T> {
     /// Creates a new parser. It accepts anything that implements the trait
     /// [Read Trait](https://doc.rust-lang.org/stable/std/io/trait.Read.html)
     pub fn new(reader: T) -> IonParser<T> {
+        IonParser::with_config(reader, IonParserConfig::default())
+    }
+
+    /// Creates a new parser that reuses `scratch` for the bytes that are only
+    /// needed while decoding a value (ints, decimals, strings, padding...), so
+    /// loops parsing many streams can avoid allocating them again and again.
+    /// Take the buffer back with `into_scratch`.
+    pub fn with_scratch(reader: T, scratch: Vec<u8>) -> IonParser<T> {
+        IonParser::from_binary_parser(
+            IonBinaryParser::with_scratch(reader, scratch),
+            IonParserConfig::default(),
+        )
+    }
+
+    /// Gives back the scratch buffer, to be used by the next parser.
+    pub fn into_scratch(self) -> Vec<u8> {
+        self.parser.into_scratch()
+    }
+
+    /// Creates a new parser using the provided configuration. Check
+    /// `IonParserConfig` for the available options.
+    pub fn with_config(reader: T, config: IonParserConfig) -> IonParser<T> {
+        IonParser::from_binary_parser(IonBinaryParser::new(reader), config)
+    }
+
+    fn from_binary_parser(parser: IonBinaryParser<T>, config: IonParserConfig) -> IonParser<T> {
         IonParser {
-            parser: IonBinaryParser::new(reader),
+            parser,
             context: SymbolContext::new(),
+            config,
+            container_ends: vec![],
+            current_value_end: None,
+            annotation_callback: None,
+            symbol_table_callback: None,
+            stats: ParseStats::default(),
+        }
+    }
+
+    /// Statistics of the values parsed so far. Check `ParseStats`.
+    pub fn stats(&self) -> &ParseStats {
+        &self.stats
+    }
+
+    /// Registers a function that is called every time the parser finds a top
+    /// level annotation that is not a `$ion_symbol_table` or a
+    /// `$ion_shared_symbol_table` declaration. It receives the annotation
+    /// names and the annotated value, before the value is returned by
+    /// `consume_value`. Registering a new function replaces the previous one.
+    pub fn on_annotation(&mut self, callback: AnnotationCallback) {
+        self.annotation_callback = Some(AnnotationHook(callback));
+    }
+
+    /// Registers a function that is called with the exact bytes of every top
+    /// level `$ion_symbol_table` and `$ion_shared_symbol_table` declaration,
+    /// once it has been loaded. Parsing the bytes after an Ion Version Marker
+    /// loads the same table again, so they can be cached and replayed to
+    /// seed a new parser. Registering a new function replaces the previous
+    /// one.
+    pub fn on_symbol_table(&mut self, callback: SymbolTableCallback) {
+        self.symbol_table_callback = Some(SymbolTableHook(callback));
+    }
+
+    // Used by the IncrementalIonParser, which needs to keep the symbols
+    // between parsers.
+    pub(crate) fn with_context(
+        reader: T,
+        config: IonParserConfig,
+        context: SymbolContext,
+    ) -> IonParser<T> {
+        IonParser {
+            context,
+            ..IonParser::with_config(reader, config)
         }
     }
 
+    /// Creates a parser that starts with the symbols of the given context,
+    /// for streams that continue a previous one, like a resumed session
+    /// without an Ion Version Marker. The stream is assumed to be Ion 1.0.
+    /// A local table appending to the current one
+    /// (`imports: $ion_symbol_table`) continues the id numbering after the
+    /// symbols of the context.
+    pub fn from_reader_with_context(reader: T, context: SymbolContext) -> IonParser<T> {
+        let mut parser = IonParser::with_context(reader, IonParserConfig::default(), context);
+        parser.assume_version(IonVersion::V1_0);
+        parser
+    }
+
+    /// Consumes the parser returning the symbols known at the current point
+    /// of the stream, so they can be used to parse a continuation of it with
+    /// `from_reader_with_context`.
+    pub fn into_context(self) -> SymbolContext {
+        self.context
+    }
+
+    pub(crate) fn get_consumed_bytes(&self) -> usize {
+        self.parser.get_consumed_bytes()
+    }
+
+    // Where the top level value being consumed ends, if its length was read.
+    pub(crate) fn get_current_value_end(&self) -> Option<usize> {
+        self.current_value_end
+    }
+
+    /// Sets the Ion version of a stream without an Ion Version Marker, like
+    /// the ones written by `IonEncoder` with `with_ivm(false)`. Without it,
+    /// a stream that doesn't start with a version marker fails with
+    /// `ParsingError::MissingVersionMarker`. A version marker found later in
+    /// the stream replaces it.
+    pub fn assume_version(&mut self, version: IonVersion) {
+        self.parser.set_current_ion_version(version.major_minor());
+    }
+
+    // Used by the IncrementalIonParser, which needs to keep the version
+    // between parsers.
+    pub(crate) fn get_ion_version(&self) -> Option<(u8, u8)> {
+        self.parser.get_current_ion_version()
+    }
+
+    pub(crate) fn set_ion_version(&mut self, version: (u8, u8)) {
+        self.parser.set_current_ion_version(version);
+    }
+
     /// Allows to set up shared tables in order to define symbols that are not in the
     /// binary blob. This is useful when decoding binaries that depend of huge tables
     /// that are expected to exist in the client and not to be sent in the ion binary.
@@ -56,7 +455,7 @@ impl<T: Read> IonParser<T> {
     ) -> Result<(), SymbolContextError> {
         let symbols: Vec<Symbol> = symbols
             .iter()
-            .map(|s| Symbol::Symbol(s.to_string()))
+            .map(|s| Symbol::Symbol(Arc::from(s.as_str())))
             .collect();
 
         self.context.add_shared_table(name, version, &symbols)
@@ -77,10 +476,32 @@ impl<T: Read> IonParser<T> {
         Ok(values)
     }
 
-    /// Consumes **one** IonValue and stops. This function will automatically process
+    /// Returns an iterator consuming the values one at a time, same as
+    /// calling `consume_value` until the end of the stream.
+    pub fn values(&mut self) -> Values<'_, T> {
+        Values {
+            parser: self,
+            done: false,
+        }
+    }
+
+    /// Consumes **one** IonValue and stops. It returns the value together
+    /// with the number of bytes consumed. This function will automatically process
     /// NOP Padding, Shared Tables and Local Tables, automatically continuing in case
     /// that any of them are found.
     pub fn consume_value(&mut self) -> ConsumerResult {
+        let is_recording = self.start_symbol_table_recording();
+
+        let value = self.consume_next_value();
+
+        if is_recording {
+            self.parser.stop_recording();
+        }
+
+        value
+    }
+
+    fn consume_next_value(&mut self) -> ConsumerResult {
         let value_header = self.parser.consume_value_header()?;
 
         let mut value = self.consume_value_body(&value_header)?;
@@ -91,12 +512,332 @@ impl<T: Read> IonParser<T> {
         Ok(value)
     }
 
+    /// Consumes the only value of a single document stream. System values
+    /// (symbol tables, NOP padding) before or after it are skipped, but if
+    /// any other data follows the value it returns `TrailingData`.
+    pub fn parse_single(&mut self) -> Result<IonValue, IonParserError> {
+        let (value, _) = self.consume_value()?;
+
+        match self.consume_value() {
+            Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) => Ok(value),
+            _ => Err(IonParserError::TrailingData),
+        }
+    }
+
+    /// Same as `consume_value`, but the annotations of the value are returned
+    /// apart, together with the value without them. The annotations are
+    /// empty if the value is not annotated.
+    pub fn consume_value_with_annotations(
+        &mut self,
+    ) -> Result<(IonValue, Vec<Arc<str>>), IonParserError> {
+        match self.consume_value()?.0 {
+            IonValue::Annotation(annotations, value) => Ok((*value, annotations)),
+            value => Ok((value, vec![])),
+        }
+    }
+
+    /// Consumes one blob or clob and feeds its content to the sink in chunks,
+    /// instead of allocating a buffer for the whole value. Useful for huge lobs.
+    /// NOP Padding before the value is skipped, but the value itself needs to be
+    /// a blob or a clob, otherwise `IonParserError::UnexpectedValueType` is
+    /// returned. It returns the length of the lob or `None` if it is a
+    /// null.blob or a null.clob.
+    pub fn consume_blob_streaming(
+        &mut self,
+        mut sink: impl FnMut(&[u8]),
+    ) -> Result<Option<usize>, IonParserError> {
+        const CHUNK_SIZE: usize = 4096;
+
+        let mut header = self.parser.consume_value_header()?;
+
+        while header.r#type == ValueType::Nop {
+            self.consume_nop(&header)?;
+            header = self.parser.consume_value_header()?;
+        }
+
+        match header.r#type {
+            ValueType::Blob | ValueType::Clob => {}
+            r#type => return Err(IonParserError::UnexpectedValueType(r#type)),
+        }
+
+        if self.is_value_null(&header) {
+            return Ok(None);
+        }
+
+        let (length, _, _) = self.consume_value_len(&header)?;
+        let mut buffer = vec![0; std::cmp::min(length, CHUNK_SIZE)];
+        let mut remaining = length;
+
+        while remaining > 0 {
+            let chunk_len = std::cmp::min(remaining, CHUNK_SIZE);
+            self.parser.read_bytes(&mut buffer[..chunk_len])?;
+            sink(&buffer[..chunk_len]);
+            remaining -= chunk_len;
+        }
+
+        Ok(Some(length))
+    }
+
+    /// Consumes one list (or sexp) lazily, returning an iterator over its
+    /// elements, so huge lists can be processed without building the whole
+    /// `IonValue::List`. NOP Padding and symbol tables before the list are
+    /// processed as usual, but if the next value is not a list or a sexp the
+    /// iterator returns `IonParserError::UnexpectedValueType`. A null list
+    /// has no elements. The iterator stops after returning an error.
+    ///
+    /// The parser is borrowed by the iterator. Dropping it before the end of
+    /// the list skips the rest of the list, so the parser can continue with
+    /// the next value. The list isn't consumed until the first call to `next`.
+    pub fn list_cursor(&mut self) -> ListCursor<'_, T> {
+        ListCursor {
+            parser: self,
+            state: CursorState::NotStarted,
+        }
+    }
+
+    /// Same as `list_cursor`.
+    pub fn consume_list_iter(
+        &mut self,
+    ) -> impl Iterator<Item = Result<IonValue, IonParserError>> + '_ {
+        self.list_cursor()
+    }
+
+    /// Same as `list_cursor` but for the fields of a struct, in the order
+    /// they are in the stream. It returns `IonParserError::SymbolNotFoundInTable`
+    /// for fields whose name has no text.
+    pub fn struct_cursor(&mut self) -> StructCursor<'_, T> {
+        StructCursor {
+            parser: self,
+            state: CursorState::NotStarted,
+        }
+    }
+
+    /// Consumes one list (or sexp) appending its elements to `out`, so the
+    /// allocation of the vector can be reused between documents. It follows
+    /// the same rules as `consume_list_iter`. In case of error, the elements
+    /// read before it stay in `out`.
+    pub fn consume_list_into(&mut self, out: &mut Vec<IonValue>) -> Result<(), IonParserError> {
+        for value in self.consume_list_iter() {
+            out.push(value?);
+        }
+
+        Ok(())
+    }
+
+    /// Consumes one struct keeping the order of the fields and the symbol
+    /// id of each field name. NOP Padding and symbol tables before the struct
+    /// are processed as usual, but if the next value is not a struct it
+    /// returns `IonParserError::UnexpectedValueType`. A null struct returns
+    /// no fields. The nested structs follow the parser configuration.
+    pub fn consume_struct_ordered(
+        &mut self,
+    ) -> Result<Vec<(SymbolToken, IonValue)>, IonParserError> {
+        let header = self.consume_header_skipping_system_values()?;
+
+        if header.r#type != ValueType::Struct {
+            return Err(IonParserError::UnexpectedValueType(header.r#type));
+        }
+
+        if self.is_value_null(&header) {
+            return Ok(vec![]);
+        }
+
+        let (length, _, _) = self.consume_value_len_for_struct(&header)?;
+
+        self.consume_struct_fields(length)
+    }
+
+    // Returns the length of the struct or `None` when it is a null struct.
+    fn start_struct_fields(&mut self) -> Result<Option<usize>, IonParserError> {
+        let header = self.consume_header_skipping_system_values()?;
+
+        if header.r#type != ValueType::Struct {
+            return Err(IonParserError::UnexpectedValueType(header.r#type));
+        }
+
+        if self.is_value_null(&header) {
+            return Ok(None);
+        }
+
+        let (length, _, _) = self.consume_value_len_for_struct(&header)?;
+
+        self.enter_container(length)?;
+
+        Ok(Some(length))
+    }
+
+    // Moves the parser to the end of the container of a dropped cursor.
+    // Errors are ignored, the next read will find them again.
+    fn skip_cursor_remaining(&mut self, state: &CursorState) {
+        if let CursorState::Remaining(remaining) | CursorState::Stopped(remaining) = state {
+            let _ = self.parser.skip_bytes(*remaining);
+            self.exit_container();
+        }
+    }
+
+    // Returns the length of the list or `None` when it is a null list.
+    fn start_list_elements(&mut self) -> Result<Option<usize>, IonParserError> {
+        let header = self.consume_header_skipping_system_values()?;
+
+        match header.r#type {
+            ValueType::List | ValueType::SExpr => {
+                if self.is_value_null(&header) {
+                    return Ok(None);
+                }
+
+                let (length, _, _) = self.consume_value_len(&header)?;
+
+                self.enter_container(length)?;
+
+                Ok(Some(length))
+            }
+            r#type => Err(IonParserError::UnexpectedValueType(r#type)),
+        }
+    }
+
+    // Processes NOP Padding and symbol tables until it finds the header of a
+    // user value. Annotated values are consumed and rejected, as the caller
+    // expects to consume the value body by itself.
+    fn consume_header_skipping_system_values(&mut self) -> Result<ValueHeader, IonParserError> {
+        loop {
+            let is_recording = self.start_symbol_table_recording();
+
+            let header = self.consume_system_value_or_header();
+
+            if is_recording {
+                self.parser.stop_recording();
+            }
+
+            if let Some(header) = header? {
+                return Ok(header);
+            }
+        }
+    }
+
+    // Returns the header of the next value if it is not a system value.
+    fn consume_system_value_or_header(&mut self) -> Result<Option<ValueHeader>, IonParserError> {
+        let header = self.parser.consume_value_header()?;
+
+        match header.r#type {
+            ValueType::Nop => {
+                self.consume_nop(&header)?;
+                Ok(None)
+            }
+            ValueType::Annotation => match self.consume_annotation(&header)? {
+                (Some(_), _) => Err(IonParserError::UnexpectedValueType(ValueType::Annotation)),
+                (None, _) => Ok(None),
+            },
+            _ => Ok(Some(header)),
+        }
+    }
+
+    // Consumes the next element of the list, updating the remaining length.
+    fn consume_list_element(
+        &mut self,
+        remaining: &mut usize,
+    ) -> Result<Option<IonValue>, IonParserError> {
+        while *remaining > 0 {
+            let value_header = self.parser.consume_value_header()?;
+
+            let (value, consumed_bytes) = if let ValueType::Nop = value_header.r#type {
+                (None, self.consume_nop(&value_header)?)
+            } else {
+                let (value, consumed_bytes) = self.consume_value_body(&value_header)?;
+                (Some(value), consumed_bytes)
+            };
+
+            let already_consumed_value_header = 1;
+            let consumed_bytes = consumed_bytes + already_consumed_value_header;
+
+            *remaining = remaining
+                .checked_sub(consumed_bytes)
+                .ok_or(IonParserError::ListLengthWasTooShort)?;
+
+            if value.is_some() {
+                return Ok(value);
+            }
+        }
+
+        self.exit_container();
+
+        Ok(None)
+    }
+
+    /// Same as `consume_value` but when the value is malformed it tries to
+    /// move the parser to the next value, so the following calls can continue
+    /// consuming the stream. The returned error includes a flag which is
+    /// `true` when the parser could be resynchronized.
+    ///
+    /// Binary Ion doesn't have delimiters between top level values, so this
+    /// relies on a couple of heuristics:
+    ///
+    /// - If the length of the top level value could be read, the rest of the
+    ///   value is skipped. Errors inside containers are recovered this way.
+    /// - If not (for example, the type descriptor is invalid), the bytes are
+    ///   discarded until the next Ion Version Marker is found. Everything
+    ///   between the bad value and that marker is lost, and the marker may be
+    ///   found by accident inside a blob or a string.
+    ///
+    /// The symbol table is not reset during the recovery, so a corrupted
+    /// symbol table declaration is not recoverable. Reading errors and the
+    /// end of the stream are returned with the flag set to `false`.
+    pub fn consume_value_recovering(&mut self) -> RecoveringConsumerResult {
+        self.current_value_end = None;
+
+        let error = match self.consume_value() {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+
+        self.container_ends.clear();
+
+        let recovered = match error {
+            IonParserError::BinaryError(ParsingError::NoDataToRead)
+            | IonParserError::BinaryError(ParsingError::ErrorReadingData(_)) => false,
+            _ => self.resynchronize(),
+        };
+
+        Err((error, recovered))
+    }
+
+    fn resynchronize(&mut self) -> bool {
+        let position = self.parser.get_consumed_bytes();
+
+        trace!("Resynchronizing the parser at byte {}", position);
+
+        let result = match self.current_value_end.take() {
+            Some(end) if end >= position => self.parser.skip_bytes(end - position),
+            _ => self.parser.skip_to_version_marker(),
+        };
+
+        result.is_ok()
+    }
+
     fn consume_value_body(&mut self, value_header: &ValueHeader) -> ConsumerResult {
+        if self.config.collect_stats && value_header.r#type != ValueType::Nop {
+            *self
+                .stats
+                .value_counts
+                .entry(value_header.r#type)
+                .or_insert(0) += 1;
+        }
+
+        let value = self.consume_value_type_body(value_header);
+
+        if self.config.collect_stats {
+            self.stats.total_bytes = self.parser.get_consumed_bytes();
+        }
+
+        value
+    }
+
+    fn consume_value_type_body(&mut self, value_header: &ValueHeader) -> ConsumerResult {
         match value_header.r#type {
             ValueType::Bool => Ok(self.consume_bool(&value_header)?),
             ValueType::Annotation => match self.consume_annotation(value_header)? {
                 (Some(annotation), consumed_bytes) => Ok((annotation, consumed_bytes)),
                 (None, consumed_bytes) => {
+                    self.reset_top_level_value_end();
                     let value = self.consume_value()?;
                     Ok((value.0, value.1 + consumed_bytes))
                 }
@@ -111,6 +852,8 @@ impl<T: Read> IonParser<T> {
             ValueType::Null => Ok((IonValue::Null(NullIonValue::Null), 0)),
             ValueType::Nop => {
                 let consumed_bytes = self.consume_nop(value_header)?;
+                self.restart_symbol_table_recording();
+                self.reset_top_level_value_end();
                 let value = self.consume_value()?;
                 Ok((value.0, value.1 + consumed_bytes))
             }
@@ -130,8 +873,7 @@ impl<T: Read> IonParser<T> {
         trace!("Nop Padding with length {}", length);
 
         if length > 0 {
-            let mut buffer = vec![0; length as usize];
-            self.parser.read_bytes(&mut buffer)?;
+            self.parser.skip_bytes(length as usize)?;
         }
 
         Ok(total)
@@ -158,11 +900,10 @@ impl<T: Read> IonParser<T> {
         }
 
         let (length, _, total) = self.consume_value_len(header)?;
-        let mut buffer = vec![0; length as usize];
-        self.parser.read_bytes(&mut buffer)?;
+        let buffer = self.parser.read_to_scratch(length as usize)?;
 
-        let text = match String::from_utf8(buffer) {
-            Ok(text) => text,
+        let text = match std::str::from_utf8(buffer) {
+            Ok(text) => text.to_string(),
             Err(_) => return Err(IonParserError::NonUtf8String),
         };
 
@@ -178,7 +919,7 @@ impl<T: Read> IonParser<T> {
 
         if let ValueLength::ShortLength(0) = header.length {
             if negative {
-                return Err(IonParserError::InvalidNegativeInt);
+                return Err(IonParserError::BinaryError(ParsingError::NegativeZeroInt));
             } else {
                 return Ok((IonValue::Integer(0), 0));
             }
@@ -192,14 +933,16 @@ impl<T: Read> IonParser<T> {
         // positive one un u64. We do this here knowingly as it is exactly what we
         // want in order to avoid a .clone().
         if negative && value == BigUint::from(i64::MIN as u64) {
-            return Ok((IonValue::Integer(i64::MIN), total));
+            let value = IonValue::Integer(i64::MIN);
+            verify_int_sign(&value, negative)?;
+            return Ok((value, total));
         }
 
         let value = match i64::try_from(&value) {
             Ok(mut value) => {
                 if negative {
                     if value == 0 {
-                        return Err(IonParserError::InvalidNegativeInt);
+                        return Err(IonParserError::BinaryError(ParsingError::NegativeZeroInt));
                     } else {
                         value = -value;
                     }
@@ -218,6 +961,8 @@ impl<T: Read> IonParser<T> {
             }
         };
 
+        verify_int_sign(&value, negative)?;
+
         Ok((value, total))
     }
 
@@ -229,20 +974,65 @@ impl<T: Read> IonParser<T> {
         }
 
         let (length, _, total) = self.consume_value_len_for_struct(header)?;
-        let mut consumed_bytes = 0;
-        let mut values: HashMap<String, IonValue> = HashMap::new();
 
-        while length - consumed_bytes > 0 {
+        let values = self
+            .consume_struct_fields(length)?
+            .into_iter()
+            .map(|(key, value)| match key.text {
+                Some(text) => Ok((text, value)),
+                None => Err(IonParserError::SymbolNotFoundInTable),
+            })
+            .collect::<Result<Vec<(Arc<str>, IonValue)>, IonParserError>>()?;
+
+        let value = if self.config.preserve_struct_order {
+            IonValue::OrderedStruct(values)
+        } else {
+            IonValue::Struct(values.into_iter().collect())
+        };
+
+        Ok((value, total))
+    }
+
+    // Fields whose symbol is defined without text (for example, by a shared
+    // table that is not available) are returned with only the symbol id.
+    fn consume_struct_fields(
+        &mut self,
+        length: usize,
+    ) -> Result<Vec<(SymbolToken, IonValue)>, IonParserError> {
+        let mut remaining = length;
+        let mut values: Vec<(SymbolToken, IonValue)> = Vec::new();
+
+        self.enter_container(length)?;
+
+        while let Some(field) = self.consume_struct_field(&mut remaining)? {
+            values.push(field);
+        }
+
+        trace!("End consuming struct");
+
+        Ok(values)
+    }
+
+    // Consumes the next field of the struct, updating the remaining length.
+    fn consume_struct_field(
+        &mut self,
+        remaining: &mut usize,
+    ) -> Result<Option<(SymbolToken, IonValue)>, IonParserError> {
+        while *remaining > 0 {
             let key = self.parser.consume_varuint()?;
-            consumed_bytes += key.1;
+            let mut consumed_bytes = key.1;
 
-            let key = match self.context.get_symbol_by_id(
-                key.0
-                    .try_into()
-                    .map_err(|_| IonParserError::SymbolIdTooBig)?,
-            ) {
-                Some(Symbol::Symbol(text)) => text.clone(),
-                _ => return Err(IonParserError::SymbolNotFoundInTable),
+            let symbol_id: usize = key
+                .0
+                .try_into()
+                .map_err(|_| IonParserError::SymbolIdTooBig)?;
+
+            let key = match self.context.get_symbol_by_id(symbol_id) {
+                Some(Symbol::Symbol(text)) => {
+                    SymbolToken::new(Some(text.clone()), Some(symbol_id as u64))
+                }
+                Some(Symbol::Dummy) => SymbolToken::from_id(symbol_id as u64),
+                None => return Err(IonParserError::SymbolNotFoundInTable),
             };
 
             trace!("Struct key field: {:?}", key);
@@ -251,29 +1041,30 @@ impl<T: Read> IonParser<T> {
 
             consumed_bytes += 1;
 
-            if let ValueType::Nop = value_header.r#type {
+            let value = if let ValueType::Nop = value_header.r#type {
                 let consumed = self.consume_nop(&value_header)?;
                 trace!("Found NOP Padding in Struct of {:} bytes", consumed + 1);
                 consumed_bytes += consumed;
-                continue;
-            }
-
-            let value = self.consume_value_body(&value_header)?;
-
-            consumed_bytes += value.1;
-
-            trace!("Struct field -> Key: {:?}, Value: {:?}", key, value.0);
+                None
+            } else {
+                let value = self.consume_value_body(&value_header)?;
+                consumed_bytes += value.1;
+                trace!("Struct field -> Key: {:?}, Value: {:?}", key, value.0);
+                Some(value.0)
+            };
 
-            values.insert(key, value.0);
-        }
+            *remaining = remaining
+                .checked_sub(consumed_bytes)
+                .ok_or(IonParserError::ListLengthWasTooShort)?;
 
-        if length.checked_sub(consumed_bytes).is_none() {
-            return Err(IonParserError::ListLengthWasTooShort);
+            if let Some(value) = value {
+                return Ok(Some((key, value)));
+            }
         }
 
-        trace!("End consuming struct");
+        self.exit_container();
 
-        Ok((IonValue::Struct(values), total))
+        Ok(None)
     }
 
     fn consume_list(&mut self, header: &ValueHeader) -> ConsumerResult {
@@ -287,6 +1078,8 @@ impl<T: Read> IonParser<T> {
         let mut consumed_bytes = 0;
         let mut values = vec![];
 
+        self.enter_container(length)?;
+
         while length - consumed_bytes > 0 {
             let value_header = self.parser.consume_value_header()?;
 
@@ -309,6 +1102,8 @@ impl<T: Read> IonParser<T> {
             }
         }
 
+        self.exit_container();
+
         trace!("End consuming list with {:}", values.len());
 
         Ok((IonValue::List(values), total))
@@ -330,6 +1125,22 @@ impl<T: Read> IonParser<T> {
         }
     }
 
+    // Ids that fit in 8 bytes are read without a `BigUint`, so reading a
+    // symbol doesn't allocate.
+    fn consume_symbol_id(&mut self, length: usize) -> Result<usize, IonParserError> {
+        let symbol_id = if length > 8 {
+            self.parser.consume_uint(length)?.try_into().ok()
+        } else {
+            let bytes = self.parser.read_to_scratch(length)?;
+            let symbol_id = bytes
+                .iter()
+                .fold(0u64, |id, byte| id << 8 | u64::from(*byte));
+            usize::try_from(symbol_id).ok()
+        };
+
+        symbol_id.ok_or(IonParserError::SymbolIdTooBig)
+    }
+
     fn consume_symbol(&mut self, header: &ValueHeader) -> ConsumerResult {
         trace!("Consuming Symbol");
 
@@ -340,23 +1151,20 @@ impl<T: Read> IonParser<T> {
         let (length, _, total_consumed_bytes) = self.consume_value_len(header)?;
 
         let symbol_id = if length == 0 {
-            BigUint::from(SystemSymbolIds::Zero as u8)
+            SystemSymbolIds::Zero as usize
         } else {
-            self.parser.consume_uint(length)?
+            self.consume_symbol_id(length)?
         };
 
-        let symbol = self.context.get_symbol_by_id(
-            symbol_id
-                .try_into()
-                .map_err(|_| IonParserError::SymbolIdTooBig)?,
-        );
-
-        let text = match symbol {
+        let text = match self.context.get_symbol_by_id(symbol_id) {
             Some(Symbol::Symbol(text)) => text.clone(),
             _ => return Err(IonParserError::SymbolNotFoundInTable),
         };
 
-        Ok((IonValue::Symbol(text), total_consumed_bytes))
+        Ok((
+            IonValue::Symbol(SymbolToken::new(Some(text), Some(symbol_id as u64))),
+            total_consumed_bytes,
+        ))
     }
 
     fn consume_timestamp(&mut self, header: &ValueHeader) -> ConsumerResult {
@@ -366,10 +1174,22 @@ impl<T: Read> IonParser<T> {
             return Ok((IonValue::Null(NullIonValue::DateTime), 0));
         }
 
-        let (length, mut consumed_bytes, _) = self.consume_value_len(header)?;
+        // The bytes of the length field don't count against the length.
+        let (length, _, total) = self.consume_value_len(header)?;
+        let mut consumed_bytes = 0;
 
-        let (offset, consumed) = self.parser.consume_varint()?;
+        let (offset, consumed, negative_offset) = self.parser.consume_varint_with_sign()?;
         consumed_bytes += consumed;
+
+        // Offset in minutes, from -23:59 to +23:59.
+        let offset: i32 = match offset.try_into() {
+            Ok(offset) if i32::abs(offset) < 24 * 60 => offset,
+            _ => return Err(IonParserError::InvalidTimestampOffset),
+        };
+
+        // -00:00 means that the local offset is unknown.
+        let unknown_offset = offset == 0 && negative_offset;
+
         let (year, consumed) = self.parser.consume_varuint()?;
         consumed_bytes += consumed;
 
@@ -413,7 +1233,6 @@ impl<T: Read> IonParser<T> {
         let fraction_coefficient: i64 = if (consumed_bytes) < length {
             let remaining_bytes = length - consumed_bytes;
             let value = self.parser.consume_int(remaining_bytes)?;
-            consumed_bytes += remaining_bytes;
             value
                 .try_into()
                 .map_err(|_| IonParserError::DateValueTooBig)?
@@ -421,31 +1240,11 @@ impl<T: Read> IonParser<T> {
             0
         };
 
-        let second_fraction: f64 =
-            ((10f64.powi(fraction_exponent)) * fraction_coefficient as f64) * 1e9;
-
-        if second_fraction < 0.0 {
-            return Err(IonParserError::DateNegativeSecondFraction);
-        }
-
-        let second_fraction =
-            u32::try_from(second_fraction as i64).map_err(|_| IonParserError::DateValueTooBig)?;
-
-        let upper_fraction_limit = 1_000_000_000;
-        if second_fraction >= upper_fraction_limit {
-            return Err(IonParserError::DateSecondFractionOverflow);
-        }
+        let second_fraction = fraction_nanoseconds(fraction_exponent, fraction_coefficient)?;
 
+        // Rejects months and days out of range, like 2001-02-29.
         let datetime = NaiveDate::from_ymd_opt(year, month, day)
-            .ok_or(IonParserError::InvalidDate(
-                year,
-                month,
-                day,
-                hour,
-                minute,
-                second,
-                second_fraction,
-            ))?
+            .ok_or(IonParserError::InvalidTimestampComponent(year, month, day))?
             .and_hms_nano_opt(hour, minute, second, second_fraction)
             .ok_or(IonParserError::InvalidDate(
                 year,
@@ -457,9 +1256,27 @@ impl<T: Read> IonParser<T> {
                 second_fraction,
             ))?;
 
-        let offset: i32 = offset
-            .try_into()
-            .map_err(|_| IonParserError::DateValueTooBig)?;
+        if self.config.preserve_timestamp_precision {
+            let precision = match component_counter {
+                0 => TimestampPrecision::Year,
+                1 => TimestampPrecision::Month,
+                2 => TimestampPrecision::Day,
+                4 => TimestampPrecision::Minute,
+                _ if fraction_exponent < 0 => {
+                    TimestampPrecision::Fraction(std::cmp::min(fraction_exponent.unsigned_abs(), 9))
+                }
+                _ => TimestampPrecision::Second,
+            };
+
+            let offset = if unknown_offset { None } else { Some(offset) };
+            let timestamp = IonTimestamp::new(datetime, offset, precision)?;
+
+            return Ok((IonValue::Timestamp(timestamp), total));
+        }
+
+        if unknown_offset {
+            return Ok((IonValue::DateTimeUnknownOffset(datetime), total));
+        }
 
         let offset = FixedOffset::east_opt(offset * 60).ok_or({
             IonParserError::InvalidDate(year, month, day, hour, minute, second, second_fraction)
@@ -469,7 +1286,7 @@ impl<T: Read> IonParser<T> {
 
         let datetime = datetime.with_timezone(&offset);
 
-        Ok((IonValue::DateTime(datetime), consumed_bytes))
+        Ok((IonValue::DateTime(datetime), total))
     }
 
     fn consume_float(&mut self, header: &ValueHeader) -> ConsumerResult {
@@ -523,16 +1340,32 @@ impl<T: Read> IonParser<T> {
             .checked_sub(consumed_bytes)
             .ok_or(IonParserError::DecimalExponentTooBig)?;
 
+        // The size alone can be enough to reject the coefficient, so a huge
+        // one is never read.
+        if let Some(max_digits) = self.config.max_decimal_digits {
+            if min_decimal_digits(coefficient_size) > max_digits {
+                return Err(IonParserError::DecimalTooLarge);
+            }
+        }
+
         let coefficient = if coefficient_size > 0 {
             self.parser.consume_int(coefficient_size)?
         } else {
             BigInt::from(0)
         };
 
+        if let Some(max_digits) = self.config.max_decimal_digits {
+            if exceeds_decimal_digits(&coefficient, max_digits) {
+                return Err(IonParserError::DecimalTooLarge);
+            }
+        }
+
         let exponent: i64 = exponent
             .try_into()
             .map_err(|_| IonParserError::DecimalExponentTooBig)?;
 
+        // BigDecimal has no negative zero, so the sign of a zero coefficient
+        // is lost and `-0d0` is read as `0d0`.
         Ok((
             IonValue::Decimal(BigDecimal::new(coefficient, -exponent)),
             total,
@@ -621,7 +1454,19 @@ impl<T: Read> IonParser<T> {
         let is_local_table_declaration =
             self.contains_system_symbol(&symbols, SystemSymbolIds::IonSymbolTable);
 
-        let value = self.consume_value()?;
+        let value = if is_shared_table_declaration || is_local_table_declaration {
+            self.consume_symbol_table_value()
+                .map_err(|error| match error {
+                    IonParserError::BinaryError(ParsingError::NoDataToRead)
+                    | IonParserError::BinaryError(ParsingError::NotEnoughtDataToRead(_)) => {
+                        IonParserError::TruncatedSymbolTable
+                    }
+                    error => error,
+                })?
+        } else {
+            self.consume_value()?
+        };
+
         if let IonValue::Annotation(_, _) = value.0 {
             return Err(IonParserError::NestedAnnotations);
         }
@@ -637,16 +1482,136 @@ impl<T: Read> IonParser<T> {
             }
             (true, false) => {
                 self.load_shared_table(value.0)?;
+                self.count_symbol_table_update();
+                self.emit_symbol_table_bytes();
                 Ok((None, total_consumed_bytes))
             }
             (false, true) => {
                 self.load_local_table(value.0)?;
+                self.count_symbol_table_update();
+                self.emit_symbol_table_bytes();
                 Ok((None, total_consumed_bytes))
             }
-            (false, false) => Ok((
-                Some(self.construct_raw_annotation(&symbols, value.0)?),
-                total_consumed_bytes,
-            )),
+            (false, false) => {
+                let annotation = self.construct_raw_annotation(&symbols, value.0)?;
+
+                if let (0, Some(callback), IonValue::Annotation(names, value)) = (
+                    self.container_ends.len(),
+                    &mut self.annotation_callback,
+                    &annotation,
+                ) {
+                    (callback.0)(names, value);
+                }
+
+                Ok((Some(annotation), total_consumed_bytes))
+            }
+        }
+    }
+
+    // Symbol tables are always loaded from a Struct, whatever the configured
+    // struct representation is.
+    fn consume_symbol_table_value(&mut self) -> ConsumerResult {
+        let preserve_struct_order = self.config.preserve_struct_order;
+
+        self.config.preserve_struct_order = false;
+        let value = self.consume_value();
+        self.config.preserve_struct_order = preserve_struct_order;
+
+        value
+    }
+
+    // Needs to be called just after consuming the container length, so the
+    // position where the container ends can be tracked.
+    fn enter_container(&mut self, length: usize) -> Result<(), IonParserError> {
+        self.container_ends
+            .push(self.parser.get_consumed_bytes() + length);
+
+        if self.config.collect_stats {
+            self.stats.max_depth = std::cmp::max(self.stats.max_depth, self.container_ends.len());
+        }
+
+        match self.config.max_depth {
+            Some(max_depth) if self.container_ends.len() > max_depth => {
+                Err(IonParserError::MaxDepthExceeded(max_depth))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // The bytes of top level values are only recorded while there is a
+    // symbol table callback. Returns if the recording was started by this
+    // call, as nested values are part of the same recording.
+    fn start_symbol_table_recording(&mut self) -> bool {
+        if self.symbol_table_callback.is_none()
+            || !self.container_ends.is_empty()
+            || self.parser.is_recording()
+        {
+            return false;
+        }
+
+        self.parser.start_recording();
+        true
+    }
+
+    // Drops the NOP padding from the recording.
+    fn restart_symbol_table_recording(&mut self) {
+        if self.container_ends.is_empty() && self.parser.is_recording() {
+            self.parser.start_recording();
+        }
+    }
+
+    fn emit_symbol_table_bytes(&mut self) {
+        if !self.container_ends.is_empty() {
+            return;
+        }
+
+        if let (Some(bytes), Some(callback)) = (
+            self.parser.stop_recording(),
+            &mut self.symbol_table_callback,
+        ) {
+            (callback.0)(&bytes);
+        }
+    }
+
+    fn count_symbol_table_update(&mut self) {
+        if self.config.collect_stats {
+            self.stats.symbol_table_updates += 1;
+        }
+    }
+
+    fn exit_container(&mut self) {
+        self.container_ends.pop();
+    }
+
+    // A value inside a container cannot end after the container itself.
+    fn check_container_boundary(
+        &self,
+        header: &ValueHeader,
+        length: usize,
+    ) -> Result<(), IonParserError> {
+        if header.length == ValueLength::NullValue {
+            return Ok(());
+        }
+
+        match self.container_ends.last() {
+            Some(end) if self.parser.get_consumed_bytes() + length > *end => Err(
+                IonParserError::BinaryError(ParsingError::ChildExceedsParent),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    // Used by the recovery mode in order to know where the current top level
+    // value ends.
+    fn set_top_level_value_end(&mut self, length: usize) {
+        if self.container_ends.is_empty() && self.current_value_end.is_none() {
+            self.current_value_end = Some(self.parser.get_consumed_bytes() + length);
+        }
+    }
+
+    fn reset_top_level_value_end(&mut self) {
+        if self.container_ends.is_empty() {
+            self.current_value_end = None;
         }
     }
 
@@ -671,6 +1636,13 @@ impl<T: Read> IonParser<T> {
             ValueLength::NullValue => null_length,
         };
 
+        if header.length != ValueLength::NullValue {
+            self.check_max_value_length(length)?;
+        }
+
+        self.check_container_boundary(header, length)?;
+        self.set_top_level_value_end(length);
+
         let total = consumed_bytes + length;
 
         Ok((length, consumed_bytes, total))
@@ -696,6 +1668,13 @@ impl<T: Read> IonParser<T> {
             ValueLength::NullValue => null_length,
         };
 
+        if header.length != ValueLength::NullValue {
+            self.check_max_value_length(length)?;
+        }
+
+        self.check_container_boundary(header, length)?;
+        self.set_top_level_value_end(length);
+
         let total = consumed_bytes + length;
 
         Ok((length, consumed_bytes, total))
@@ -704,7 +1683,7 @@ impl<T: Read> IonParser<T> {
     fn get_parsed_struct_hashmap<'a>(
         &self,
         table: &'a IonValue,
-    ) -> Result<&'a HashMap<String, IonValue>, IonParserError> {
+    ) -> Result<&'a HashMap<Arc<str>, IonValue>, IonParserError> {
         if let IonValue::Struct(table) = table {
             Ok(table)
         } else {
@@ -712,7 +1691,7 @@ impl<T: Read> IonParser<T> {
         }
     }
 
-    fn get_symbols_string(&self, table: &HashMap<String, IonValue>) -> Vec<Symbol> {
+    fn get_symbols_string(&self, table: &HashMap<Arc<str>, IonValue>) -> Vec<Symbol> {
         let symbols = table.get(self.get_symbol_name_by_type(SystemSymbolIds::Symbols));
 
         if let Some(IonValue::List(symbols)) = symbols {
@@ -720,7 +1699,7 @@ impl<T: Read> IonParser<T> {
 
             for symbol in symbols {
                 if let IonValue::String(text) = symbol {
-                    symbols_string.push(Symbol::Symbol(text.clone()));
+                    symbols_string.push(Symbol::Symbol(Arc::from(text.as_str())));
                 } else {
                     symbols_string.push(Symbol::Dummy);
                 }
@@ -744,8 +1723,10 @@ impl<T: Read> IonParser<T> {
         let imports = match imports {
             None => Vec::new(),
             Some(IonValue::Symbol(symbol))
-                if symbol == self.get_symbol_name_by_type(SystemSymbolIds::IonSymbolTable) =>
+                if symbol.text()
+                    == Some(self.get_symbol_name_by_type(SystemSymbolIds::IonSymbolTable)) =>
             {
+                self.check_max_symbols(self.context.local_symbols_len() + symbols.len())?;
                 self.context.set_new_table_from_current(symbols);
                 return Ok(());
             }
@@ -753,11 +1734,36 @@ impl<T: Read> IonParser<T> {
             _ => return Err(IonParserError::LocalSymbolTableWithoutValidImport),
         };
 
+        // Imports of unknown tables are filled with max_id placeholders, so
+        // they are checked before creating the table.
+        let imported_symbols = imports.iter().fold(0usize, |count, import| {
+            count.saturating_add(import.max_len.unwrap_or(0))
+        });
+        self.check_max_symbols(imported_symbols.saturating_add(symbols.len()))?;
+
         self.context
             .set_new_table(&imports, &symbols)
             .map_err(IonParserError::ErrorAddingCreatingLocal)?;
 
-        Ok(())
+        self.check_max_symbols(self.context.local_symbols_len())
+    }
+
+    fn check_max_value_length(&self, length: usize) -> Result<(), IonParserError> {
+        match self.config.max_value_length {
+            Some(max_value_length) if length > max_value_length => {
+                Err(IonParserError::ValueTooLarge(max_value_length))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_max_symbols(&self, count: usize) -> Result<(), IonParserError> {
+        match self.config.max_symbols {
+            Some(max_symbols) if count > max_symbols => {
+                Err(IonParserError::TooManySymbols(max_symbols))
+            }
+            _ => Ok(()),
+        }
     }
 
     fn decode_imports(&self, values: &[IonValue]) -> Result<Vec<Import>, IonParserError> {
@@ -859,10 +1865,95 @@ impl<T: Read> IonParser<T> {
         symbols.iter().any(|&s| s == symbol as usize)
     }
 
-    fn get_symbol_name(&self, symbol_id: usize) -> Result<String, IonParserError> {
+    fn get_symbol_name(&self, symbol_id: usize) -> Result<Arc<str>, IonParserError> {
         match self.context.get_symbol_by_id(symbol_id) {
             Some(Symbol::Symbol(name)) => Ok(name.clone()),
             Some(Symbol::Dummy) | None => Err(IonParserError::SymbolIdNotDefined),
         }
     }
 }
+
+// The magnitude is read as an unsigned int and the sign comes from the type
+// code, so a mismatch can only be a bug in the conversion above.
+pub(crate) fn verify_int_sign(value: &IonValue, negative: bool) -> Result<(), ParsingError> {
+    let is_negative = match value {
+        IonValue::Integer(value) => *value < 0,
+        IonValue::BigInteger(value) => value.sign() == Sign::Minus,
+        _ => return Ok(()),
+    };
+
+    if is_negative == negative {
+        Ok(())
+    } else {
+        Err(ParsingError::IntSignMismatch)
+    }
+}
+
+// Whether the value has more than `max_digits` decimal digits. The bit length
+// bounds the number of digits, so the value is only converted to a string
+// when the limit is between the bounds.
+pub(crate) fn exceeds_decimal_digits(value: &BigInt, max_digits: usize) -> bool {
+    let bits = value.bits();
+
+    if bits == 0 {
+        return max_digits < 1;
+    }
+
+    let min_digits = ((bits - 1) as f64 * std::f64::consts::LOG10_2) as usize + 1;
+    let max_bound = (bits as f64 * std::f64::consts::LOG10_2).ceil() as usize;
+
+    if min_digits > max_digits {
+        return true;
+    }
+
+    if max_bound <= max_digits {
+        return false;
+    }
+
+    value.magnitude().to_str_radix(10).len() > max_digits
+}
+
+// Minimum number of digits of an Int field of `len` bytes. Without leading
+// zero bytes its magnitude is at least 2^(8 * (len - 1) - 1), as the first
+// byte can only hold the sign. Coefficients padded with zero bytes can have
+// fewer digits, but they are rejected too.
+fn min_decimal_digits(len: usize) -> usize {
+    if len <= 1 {
+        return 1;
+    }
+
+    let bits = (len - 1) as f64 * 8.0 - 1.0;
+
+    (bits * std::f64::consts::LOG10_2) as usize + 1
+}
+
+// The fraction of the second in nanoseconds. The digits after them are
+// truncated.
+fn fraction_nanoseconds(exponent: i32, coefficient: i64) -> Result<u32, IonParserError> {
+    if coefficient < 0 {
+        return Err(IonParserError::DateNegativeSecondFraction);
+    }
+
+    if coefficient == 0 {
+        return Ok(0);
+    }
+
+    if exponent >= 0 {
+        return Err(IonParserError::DateSecondFractionOverflow);
+    }
+
+    let coefficient = coefficient as u128;
+    let digits = exponent.unsigned_abs();
+
+    // The coefficient has at most 19 digits, so it is 0 from there.
+    let nanoseconds = match digits {
+        0..=9 => coefficient * 10u128.pow(9 - digits),
+        10..=28 => coefficient / 10u128.pow(digits - 9),
+        _ => 0,
+    };
+
+    u32::try_from(nanoseconds)
+        .ok()
+        .filter(|nanoseconds| *nanoseconds < 1_000_000_000)
+        .ok_or(IonParserError::DateSecondFractionOverflow)
+}
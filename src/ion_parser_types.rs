@@ -1,24 +1,30 @@
 use crate::binary_parser_types::*;
-use crate::symbol_table::SymbolContextError;
+use crate::ion_timestamp::IonTimestamp;
+use crate::symbol_table::{SymbolContextError, SymbolToken};
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use num_bigint::BigInt;
 use std::collections::HashMap;
 use std::error::Error;
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Indicated a problem in the binary blob internal structure. When all data is read
 /// the library will return IonParserError::BinaryError(ParsingError::NoDataToRead).
 #[derive(PartialEq, Debug, Error)]
 pub enum IonParserError {
-    #[error("Ion type not implemented")]
-    Unimplemented,
+    #[error("Ion type {0:?} not implemented")]
+    Unimplemented(ValueType),
+    #[error("The value is not complete yet, at least {needed} more bytes are needed")]
+    Incomplete { needed: usize },
     #[error("Null annotation found")]
     NullAnnotationFound,
     #[error("Nested annotation found")]
     NestedAnnotations,
     #[error("Annotation len field is wrong")]
     BadAnnotationLength,
+    #[error("The data ended in the middle of a symbol table")]
+    TruncatedSymbolTable,
     #[error("Ion symbol table declared as local and shared at the same time")]
     SharedTableAndLocalTableDeclarationIntTheSameAnnotation,
     #[error("Not known symbol id")]
@@ -53,6 +59,18 @@ pub enum IonParserError {
     DateNegativeSecondFraction,
     #[error("The date has hours defined but not minutes, which is illegal")]
     DateWithHourButNoMinutes,
+    #[error("The timestamp offset is not between -23:59 and +23:59")]
+    InvalidTimestampOffset,
+    #[error("The timestamp has an unknown offset, so it can't be converted to a time with offset")]
+    TimestampWithUnknownOffset,
+    #[error("The timestamp has components more precise than its precision")]
+    InvalidTimestampPrecision,
+    #[error("The timestamp is before 1970, so it can't be converted to a SystemTime")]
+    TimestampBeforeUnixEpoch,
+    #[error(
+        "The timestamp is less precise than seconds, so it can't be converted to a SystemTime"
+    )]
+    TimestampWithoutSeconds,
     #[error("The length of the value is bigger than usize, which is far too")]
     ValueLenTooBig,
     #[error("Floats can only be 4 or 8 bytes")]
@@ -61,14 +79,16 @@ pub enum IonParserError {
     BinaryError(ParsingError),
     #[error("Exponent for the decimal value is too big (greater than i64)")]
     DecimalExponentTooBig,
+    #[error("The decimal coefficient has more digits than the configured max")]
+    DecimalTooLarge,
     #[error("Bool cannot have len")]
     InvalidBoolLength(ValueLength),
     #[error("The date is not valid")]
     InvalidDate(i32, u32, u32, u32, u32, u32, u32),
+    #[error("The month or the day of the timestamp doesn't exist: {0}-{1}-{2}")]
+    InvalidTimestampComponent(i32, u32, u32),
     #[error("Ion type 15 doesn't exist in Ion 1.0")]
     InvalidReservedTypeDescriptor,
-    #[error("Negative ints need a value")]
-    InvalidNegativeInt,
     #[error("Ordered structs cannot be empty")]
     EmptyOrderedStruct,
     #[error("Error transforming the IonValue to a rust type")]
@@ -79,6 +99,42 @@ pub enum IonParserError {
     DecimalNotANumericValue(f64),
     #[error("The origin type is not supported in the destination type")]
     TypeNotSupported(IonValue),
+    #[error("The containers are nested deeper than the configured max depth")]
+    MaxDepthExceeded(usize),
+    #[error("The local symbol table has more symbols than the configured max")]
+    TooManySymbols(usize),
+    #[error("The value is longer than the configured max length")]
+    ValueTooLarge(usize),
+    #[error("The value type is not the expected one")]
+    UnexpectedValueType(ValueType),
+    #[error("Found a null value where a value was expected")]
+    UnexpectedNull(NullIonValue),
+    #[error("The struct doesn't have the requested field")]
+    FieldNotFound(String),
+    #[error("The list doesn't have the requested index")]
+    IndexOutOfBounds(usize),
+    #[error("The operation needs a path with at least one segment")]
+    EmptyPath,
+    #[error("There is more data after the expected value")]
+    TrailingData,
+    #[error("The encoded data is {len} bytes, longer than the {target} bytes to pad to")]
+    PadTargetTooSmall { len: usize, target: usize },
+    #[error("A NaN float can't be ordered")]
+    NanNotOrderable,
+    #[error("Invalid Ion text at byte {position}: {reason}")]
+    InvalidText { position: usize, reason: String },
+    #[error("A symbol needs a text or an id to be encoded")]
+    SymbolWithoutTextOrId,
+    #[error("The symbol ${0} has no text and isn't a system or imported symbol")]
+    SymbolIdNotImported(u64),
+    #[error("An annotation needs at least one annotation to be encoded")]
+    EmptyAnnotationList,
+    #[error("Ion timestamps years are between 1 and 9999, got {0}")]
+    TimestampYearOutOfRange(i32),
+    #[error("Error deserializing the value: {0}")]
+    Deserialization(String),
+    #[error("Error serializing the value: {0}")]
+    Serialization(String),
 }
 
 impl From<ParsingError> for IonParserError {
@@ -125,7 +181,27 @@ pub enum SerdeJsonParseError {
 ///
 /// Floats are implemented only using f64. Previously there was Float32 and
 /// Float64, but there are some problems with IonHash and QLDB when using Float32.
-#[derive(PartialEq, Debug, Clone)]
+///
+/// Decimals are implemented with BigDecimal, which has no negative zero.
+/// Negative zero decimals, like `-0d0` or `-0.0`, are not supported: they are
+/// read as positive zero, so encoding or hashing them gives the bytes and the
+/// Ion Hash of `0d0` instead of the ones other Ion implementations give.
+///
+/// `Debug` truncates big blobs, clobs and strings, check its implementation.
+///
+/// DateTimeUnknownOffset is a timestamp with the unknown offset `-00:00`, which
+/// chrono can't represent. The time is the UTC one.
+///
+/// Timestamp keeps the precision and the offset of the timestamp, check
+/// `IonTimestamp`. The parser only returns it when
+/// `IonParserConfig::preserve_timestamp_precision` is enabled. Like with
+/// OrderedStruct, a Timestamp is never equal to a DateTime.
+///
+/// OrderedStruct keeps the fields in the order they were found or added, which
+/// allows to encode them back in the same order. The parser only returns it
+/// when `IonParserConfig::preserve_struct_order` is enabled. Note that an
+/// OrderedStruct is never equal to a Struct, even with the same fields.
+#[derive(PartialEq, Clone)]
 pub enum IonValue {
     Null(NullIonValue),
     Bool(bool),
@@ -134,23 +210,87 @@ pub enum IonValue {
     Float(f64),
     Decimal(BigDecimal),
     DateTime(DateTime<FixedOffset>),
+    DateTimeUnknownOffset(NaiveDateTime),
+    Timestamp(IonTimestamp),
     String(String),
-    Symbol(String),
+    Symbol(SymbolToken),
     Clob(Vec<u8>),
     Blob(Vec<u8>),
     List(Vec<IonValue>),
     SExpr(Vec<IonValue>),
-    Struct(HashMap<String, IonValue>),
-    Annotation(Vec<String>, Box<IonValue>),
+    Struct(HashMap<Arc<str>, IonValue>),
+    OrderedStruct(Vec<(Arc<str>, IonValue)>),
+    Annotation(Vec<Arc<str>>, Box<IonValue>),
 }
 
 impl Eq for IonValue {}
 
+/// A step of a path inside an IonValue, used by `IonValue::get_path` and
+/// `IonPatch`. Fields are looked up in structs and indexes in lists and
+/// sexps.
+#[derive(PartialEq, Debug, Clone, Eq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl From<&str> for PathSegment {
+    fn from(field: &str) -> PathSegment {
+        PathSegment::Field(field.to_string())
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> PathSegment {
+        PathSegment::Index(index)
+    }
+}
+
+/// Options of `IonValue::flatten_with`. By default the path segments are
+/// joined with `.` and list indexes are written as another segment, like
+/// `items.0.name`.
+#[derive(PartialEq, Debug, Clone, Eq)]
+pub struct FlattenOptions {
+    pub separator: String,
+    pub index_notation: IndexNotation,
+}
+
+impl Default for FlattenOptions {
+    fn default() -> Self {
+        FlattenOptions {
+            separator: ".".to_string(),
+            index_notation: IndexNotation::Segment,
+        }
+    }
+}
+
+/// How `IonValue::flatten_with` writes list indexes: `items.0` (`Segment`)
+/// or `items[0]` (`Brackets`).
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+pub enum IndexNotation {
+    Segment,
+    Brackets,
+}
+
+/// The Ion versions that the parser can read.
+#[derive(PartialEq, Debug, Clone, Copy, Eq)]
+pub enum IonVersion {
+    V1_0,
+}
+
+impl IonVersion {
+    pub(crate) fn major_minor(self) -> (u8, u8) {
+        match self {
+            IonVersion::V1_0 => (1, 0),
+        }
+    }
+}
+
 /// Instead of wrapping each IonValue in an Option in order to represent the
 /// null value, we opted to join all Null values in the IonValue::Null(_) which
 /// contains this struct. Here you can check what kind of null you got. We do this
 /// because we believe is more ergonomic and simplifies the API handling.
-#[derive(PartialEq, Debug, Clone, Eq)]
+#[derive(PartialEq, Debug, Clone, Eq, Hash, PartialOrd, Ord)]
 pub enum NullIonValue {
     Null,
     Bool,
@@ -0,0 +1,173 @@
+use crate::{IonExtractionError, IonParserError, IonValue, PathSegment};
+use std::collections::HashMap;
+
+/// Collects changes to apply to an IonValue, for example to patch a parsed
+/// document before encoding it again.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonPatch, IonValue, PathSegment};
+/// use std::collections::HashMap;
+///
+/// let mut fields = HashMap::new();
+/// fields.insert("VIN".into(), IonValue::String("1C4RJFAG0FC625797".into()));
+///
+/// let mut document = IonValue::Struct(fields);
+///
+/// IonPatch::new()
+///     .set(&["owner".into(), "name".into()], IonValue::String("Jane".into()))
+///     .remove(&["VIN".into()])
+///     .apply(&mut document)
+///     .unwrap();
+///
+/// let name: Vec<PathSegment> = vec!["owner".into(), "name".into()];
+///
+/// assert_eq!(document.get_path(&name), Some(&IonValue::String("Jane".into())));
+/// ```
+///
+/// - `set` replaces the value at the path. Missing struct fields along the
+///   way are created as empty structs (ordered ones when the parent is an
+///   ordered struct). Indexes must already exist.
+/// - `remove` deletes the field or the list element at the end of the path.
+///   Every field with that name is removed from ordered structs.
+///
+/// The changes are applied in the order they were added. If any of them
+/// fails, the value is left untouched.
+#[derive(Debug, Clone, Default)]
+pub struct IonPatch {
+    operations: Vec<PatchOperation>,
+}
+
+#[derive(Debug, Clone)]
+enum PatchOperation {
+    Set(Vec<PathSegment>, IonValue),
+    Remove(Vec<PathSegment>),
+}
+
+impl IonPatch {
+    pub fn new() -> IonPatch {
+        IonPatch { operations: vec![] }
+    }
+
+    /// An empty path replaces the whole value.
+    pub fn set(mut self, path: &[PathSegment], value: IonValue) -> IonPatch {
+        self.operations
+            .push(PatchOperation::Set(path.to_vec(), value));
+        self
+    }
+
+    /// An empty path makes `apply` fail with `EmptyPath`.
+    pub fn remove(mut self, path: &[PathSegment]) -> IonPatch {
+        self.operations.push(PatchOperation::Remove(path.to_vec()));
+        self
+    }
+
+    pub fn apply(&self, value: &mut IonValue) -> Result<(), IonParserError> {
+        let mut result = value.clone();
+
+        for operation in &self.operations {
+            match operation {
+                PatchOperation::Set(path, new_value) => set_path(&mut result, path, new_value)?,
+                PatchOperation::Remove(path) => remove_path(&mut result, path)?,
+            }
+        }
+
+        *value = result;
+
+        Ok(())
+    }
+}
+
+fn set_path(
+    target: &mut IonValue,
+    path: &[PathSegment],
+    value: &IonValue,
+) -> Result<(), IonParserError> {
+    let target = path
+        .iter()
+        .try_fold(target, |current, segment| child_mut(current, segment, true))?;
+
+    *target = value.clone();
+
+    Ok(())
+}
+
+fn remove_path(target: &mut IonValue, path: &[PathSegment]) -> Result<(), IonParserError> {
+    let (last, parents) = path.split_last().ok_or(IonParserError::EmptyPath)?;
+
+    let parent = parents.iter().try_fold(target, |current, segment| {
+        child_mut(current, segment, false)
+    })?;
+
+    match last {
+        PathSegment::Field(key) => match parent {
+            IonValue::Struct(values) => values
+                .remove(key.as_str())
+                .map(|_| ())
+                .ok_or_else(|| IonParserError::FieldNotFound(key.clone())),
+            IonValue::OrderedStruct(values) => {
+                let len = values.len();
+                values.retain(|(name, _)| **name != **key);
+
+                if values.len() == len {
+                    Err(IonParserError::FieldNotFound(key.clone()))
+                } else {
+                    Ok(())
+                }
+            }
+            parent => Err(type_not_supported(parent)),
+        },
+        PathSegment::Index(index) => match parent {
+            IonValue::List(values) | IonValue::SExpr(values) if *index < values.len() => {
+                values.remove(*index);
+                Ok(())
+            }
+            IonValue::List(_) | IonValue::SExpr(_) => Err(IonParserError::IndexOutOfBounds(*index)),
+            parent => Err(type_not_supported(parent)),
+        },
+    }
+}
+
+fn child_mut<'a>(
+    current: &'a mut IonValue,
+    segment: &PathSegment,
+    create: bool,
+) -> Result<&'a mut IonValue, IonParserError> {
+    match segment {
+        PathSegment::Field(key) => match current {
+            IonValue::Struct(values) => {
+                if create {
+                    Ok(values
+                        .entry(key.as_str().into())
+                        .or_insert_with(|| IonValue::Struct(HashMap::new())))
+                } else {
+                    values
+                        .get_mut(key.as_str())
+                        .ok_or_else(|| IonParserError::FieldNotFound(key.clone()))
+                }
+            }
+            IonValue::OrderedStruct(values) => {
+                let position = match values.iter().position(|(name, _)| **name == **key) {
+                    Some(position) => position,
+                    None if create => {
+                        values.push((key.as_str().into(), IonValue::OrderedStruct(vec![])));
+                        values.len() - 1
+                    }
+                    None => return Err(IonParserError::FieldNotFound(key.clone())),
+                };
+
+                Ok(&mut values[position].1)
+            }
+            current => Err(type_not_supported(current)),
+        },
+        PathSegment::Index(index) => match current {
+            IonValue::List(values) | IonValue::SExpr(values) => values
+                .get_mut(*index)
+                .ok_or(IonParserError::IndexOutOfBounds(*index)),
+            current => Err(type_not_supported(current)),
+        },
+    }
+}
+
+fn type_not_supported(value: &IonValue) -> IonParserError {
+    IonParserError::ValueExtractionFailure(IonExtractionError::TypeNotSupported(value.clone()))
+}
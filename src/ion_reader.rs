@@ -0,0 +1,90 @@
+use crate::{IonParser, IonParserError, IonTextParser, IonValue, ParsingError};
+use std::io::{Cursor, Read};
+
+const ION_1_0_VERSION_MARKER: [u8; 4] = [0xE0, 0x01, 0x00, 0xEA];
+
+/// A reader of Ion values, independent of the format of the data. Returned
+/// by `read`, which chooses the implementation for the given data.
+pub trait IonReader {
+    /// Consumes one value, returning `BinaryError(NoDataToRead)` at the end
+    /// of the stream, same as `IonParser::consume_value`.
+    fn next_value(&mut self) -> Result<IonValue, IonParserError>;
+
+    /// Consumes all the remaining values.
+    fn read_all(&mut self) -> Result<Vec<IonValue>, IonParserError> {
+        let mut values = vec![];
+
+        loop {
+            match self.next_value() {
+                Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) => break,
+                Ok(value) => values.push(value),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+impl<T: Read> IonReader for IonParser<T> {
+    fn next_value(&mut self) -> Result<IonValue, IonParserError> {
+        Ok(self.consume_value()?.0)
+    }
+}
+
+impl IonReader for IonTextParser {
+    fn next_value(&mut self) -> Result<IonValue, IonParserError> {
+        self.consume_value()
+    }
+}
+
+/// Creates a reader for the data, looking at the first bytes in order to
+/// know the format: data starting with the binary Ion Version Marker is
+/// read with `IonParser`, anything else is considered Ion text and read with
+/// `IonTextParser`. An empty stream is read as binary, returning no values.
+/// The bytes used to detect the format are given back to the reader, so
+/// nothing is lost. Ion text is read completely before returning, as
+/// `IonTextParser` works with the whole text.
+///
+/// ```rust,no_run
+/// let ion_test = b"\xe0\x01\0\xea\x21\x01";
+///
+/// let mut reader = ion_binary_rs::read(&ion_test[..]).unwrap();
+///
+/// assert_eq!(reader.read_all().unwrap(), vec![ion_binary_rs::IonValue::Integer(1)]);
+/// ```
+pub fn read<'a, R: Read + 'a>(mut reader: R) -> Result<Box<dyn IonReader + 'a>, IonParserError> {
+    let mut header = [0u8; 4];
+    let mut header_len = 0;
+
+    while header_len < header.len() {
+        match reader.read(&mut header[header_len..]) {
+            Ok(0) => break,
+            Ok(len) => header_len += len,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(IonParserError::BinaryError(ParsingError::ErrorReadingData(
+                    e.to_string(),
+                )))
+            }
+        }
+    }
+
+    // A stream shorter than the marker is binary if it is the start of
+    // the marker, so the parser reports it as incomplete.
+    let is_binary = ION_1_0_VERSION_MARKER.starts_with(&header[..header_len]);
+
+    let mut reader = Cursor::new(header).take(header_len as u64).chain(reader);
+
+    if is_binary {
+        return Ok(Box::new(IonParser::new(reader)));
+    }
+
+    let mut text = String::new();
+
+    reader
+        .read_to_string(&mut text)
+        .map_err(|e| IonParserError::BinaryError(ParsingError::ErrorReadingData(e.to_string())))?;
+
+    Ok(Box::new(IonTextParser::new(text)))
+}
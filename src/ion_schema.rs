@@ -0,0 +1,251 @@
+use crate::IonValue;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// The Ion types reported by `infer_schema`. Typed nulls (`null.string`,
+/// `null.struct`, ...) are all reported as `Null`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SchemaType {
+    Null,
+    Bool,
+    Integer,
+    Float,
+    Decimal,
+    Timestamp,
+    String,
+    Symbol,
+    Clob,
+    Blob,
+    List,
+    SExpr,
+    Struct,
+}
+
+/// Summary of a set of values, as returned by `infer_schema`.
+///
+/// - `types` has every type seen.
+/// - `fields` has the fields of the values that are structs.
+/// - `elements` summarizes the elements of the values that are lists or
+///   sexps. It is `None` if there are none of them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SchemaNode {
+    pub types: BTreeSet<SchemaType>,
+    pub fields: BTreeMap<String, SchemaField>,
+    pub elements: Option<Box<SchemaNode>>,
+}
+
+/// A struct field of a `SchemaNode`. It is required when it is present in
+/// all the structs. A field that is present but null is still required, it
+/// just has the `Null` type in its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaField {
+    pub required: bool,
+    pub schema: SchemaNode,
+}
+
+/// Infers the common structure of a set of values, for example to document
+/// the documents of a table from some samples. Annotations are ignored and
+/// the annotated value is used instead.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{infer_schema, IonValue, SchemaType};
+/// use std::collections::HashMap;
+///
+/// let mut car = HashMap::new();
+/// car.insert("Year".into(), IonValue::Integer(2019));
+///
+/// let schema = infer_schema(&[IonValue::Struct(car), IonValue::Struct(HashMap::new())]);
+///
+/// let year = &schema.fields["Year"];
+///
+/// assert!(!year.required);
+/// assert!(year.schema.types.contains(&SchemaType::Integer));
+/// ```
+pub fn infer_schema(values: &[IonValue]) -> SchemaNode {
+    infer(&values.iter().collect::<Vec<&IonValue>>())
+}
+
+fn infer(values: &[&IonValue]) -> SchemaNode {
+    let mut node = SchemaNode::default();
+    let mut struct_count = 0;
+    let mut fields: BTreeMap<&Arc<str>, (usize, Vec<&IonValue>)> = BTreeMap::new();
+    let mut elements: Option<Vec<&IonValue>> = None;
+
+    for value in values {
+        let value = without_annotations(value);
+
+        node.types.insert(schema_type(value));
+
+        let struct_fields: Vec<(&Arc<str>, &IonValue)> = match value {
+            IonValue::Struct(values) => values.iter().collect(),
+            IonValue::OrderedStruct(values) => values.iter().map(|(k, v)| (k, v)).collect(),
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                elements.get_or_insert_with(Vec::new).extend(values);
+                continue;
+            }
+            _ => continue,
+        };
+
+        struct_count += 1;
+
+        // Ordered structs can repeat a field, but it only counts once.
+        let mut seen = BTreeSet::new();
+
+        for (name, value) in struct_fields {
+            let field = fields.entry(name).or_insert_with(|| (0, vec![]));
+
+            if seen.insert(name) {
+                field.0 += 1;
+            }
+
+            field.1.push(value);
+        }
+    }
+
+    node.fields = fields
+        .into_iter()
+        .map(|(name, (count, values))| {
+            let field = SchemaField {
+                required: count == struct_count,
+                schema: infer(&values),
+            };
+
+            (name.to_string(), field)
+        })
+        .collect();
+
+    node.elements = elements.map(|elements| Box::new(infer(&elements)));
+
+    node
+}
+
+fn without_annotations(value: &IonValue) -> &IonValue {
+    match value {
+        IonValue::Annotation(_, value) => without_annotations(value),
+        value => value,
+    }
+}
+
+fn schema_type(value: &IonValue) -> SchemaType {
+    match value {
+        IonValue::Null(_) => SchemaType::Null,
+        IonValue::Bool(_) => SchemaType::Bool,
+        IonValue::Integer(_) | IonValue::BigInteger(_) => SchemaType::Integer,
+        IonValue::Float(_) => SchemaType::Float,
+        IonValue::Decimal(_) => SchemaType::Decimal,
+        IonValue::DateTime(_) | IonValue::DateTimeUnknownOffset(_) | IonValue::Timestamp(_) => {
+            SchemaType::Timestamp
+        }
+        IonValue::String(_) => SchemaType::String,
+        IonValue::Symbol(_) => SchemaType::Symbol,
+        IonValue::Clob(_) => SchemaType::Clob,
+        IonValue::Blob(_) => SchemaType::Blob,
+        IonValue::List(_) => SchemaType::List,
+        IonValue::SExpr(_) => SchemaType::SExpr,
+        IonValue::Struct(_) | IonValue::OrderedStruct(_) => SchemaType::Struct,
+        IonValue::Annotation(_, value) => schema_type(value),
+    }
+}
+
+/// A simple description of the fields that a struct must have, in order to
+/// check input documents with `IonSchema::validate`.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonSchema, IonValue, SchemaType, ValidationError};
+/// use std::collections::HashMap;
+///
+/// let schema = IonSchema::new()
+///     .required("VIN", SchemaType::String)
+///     .optional("Year", SchemaType::Integer);
+///
+/// let mut car = HashMap::new();
+/// car.insert("Year".into(), IonValue::String("2019".to_string()));
+///
+/// let errors = schema.validate(&IonValue::Struct(car)).unwrap_err();
+///
+/// assert!(errors.contains(&ValidationError::MissingField("VIN".to_string())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IonSchema {
+    fields: BTreeMap<String, (SchemaType, bool)>,
+}
+
+/// A rule of an `IonSchema` that a value doesn't follow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    NotAStruct(SchemaType),
+    MissingField(String),
+    WrongType {
+        field: String,
+        expected: SchemaType,
+        found: SchemaType,
+    },
+}
+
+impl IonSchema {
+    pub fn new() -> IonSchema {
+        IonSchema::default()
+    }
+
+    /// Adds a field that must be present with the given type.
+    pub fn required<K: Into<String>>(mut self, field: K, field_type: SchemaType) -> IonSchema {
+        self.fields.insert(field.into(), (field_type, true));
+        self
+    }
+
+    /// Adds a field that must have the given type when present. A null value
+    /// counts as not present.
+    pub fn optional<K: Into<String>>(mut self, field: K, field_type: SchemaType) -> IonSchema {
+        self.fields.insert(field.into(), (field_type, false));
+        self
+    }
+
+    /// Checks the value against the schema, returning all the rules it
+    /// breaks instead of only the first one. Annotations are ignored and
+    /// fields not in the schema are allowed. When a field is repeated in an
+    /// ordered struct every occurrence is checked.
+    pub fn validate(&self, value: &IonValue) -> Result<(), Vec<ValidationError>> {
+        let struct_fields: Vec<(&Arc<str>, &IonValue)> = match without_annotations(value) {
+            IonValue::Struct(values) => values.iter().collect(),
+            IonValue::OrderedStruct(values) => values.iter().map(|(k, v)| (k, v)).collect(),
+            value => return Err(vec![ValidationError::NotAStruct(schema_type(value))]),
+        };
+
+        let mut errors = vec![];
+
+        for (field, (expected, required)) in &self.fields {
+            let values: Vec<&IonValue> = struct_fields
+                .iter()
+                .filter(|(name, _)| ***name == **field)
+                .map(|(_, value)| *value)
+                .collect();
+
+            if values.is_empty() {
+                if *required {
+                    errors.push(ValidationError::MissingField(field.clone()));
+                }
+                continue;
+            }
+
+            for value in values {
+                let found = schema_type(value);
+
+                if found == *expected || (found == SchemaType::Null && !required) {
+                    continue;
+                }
+
+                errors.push(ValidationError::WrongType {
+                    field: field.clone(),
+                    expected: *expected,
+                    found,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,476 @@
+use crate::ion_deserializer::{Annotated, ANNOTATED_STRUCT};
+use crate::{IonEncoder, IonParserError, IonValue, NullIonValue, SymbolToken};
+use num_bigint::BigInt;
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::Serializer;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Serializes the value as an Ion binary document, using `IonEncoder`.
+///
+/// Maps are serialized as structs, Rust structs as ordered structs with the
+/// fields in declaration order (so the output is always the same), sequences
+/// and tuples as lists, `None` and `()` as nulls and bytes as blobs. Note that `Vec<u8>`
+/// serializes as a sequence, so it needs something like serde's `with`
+/// attribute to be a blob. Unit enum variants are symbols and the other
+/// variants are structs with one field, the variant name. Map keys need to
+/// be strings.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonParser, IonValue};
+///
+/// let bytes = ion_binary_rs::to_vec(&vec![1, 2]).unwrap();
+///
+/// assert_eq!(
+///     IonParser::new(&bytes[..]).consume_all().unwrap(),
+///     vec![IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)])]
+/// );
+/// ```
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, IonParserError> {
+    let mut encoder = IonEncoder::new();
+    encoder.add(to_value(value)?);
+    encoder.try_encode()
+}
+
+/// Same as `to_vec`, but writing the document to the writer.
+pub fn to_writer<W: Write, T: Serialize + ?Sized>(
+    writer: &mut W,
+    value: &T,
+) -> Result<(), IonParserError> {
+    writer
+        .write_all(&to_vec(value)?)
+        .map_err(serialization_error)
+}
+
+/// Serializes the value as an `IonValue`, following the same rules as
+/// `to_vec`.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<IonValue, IonParserError> {
+    value.serialize(IonSerializer)
+}
+
+impl<T: Serialize> Serialize for Annotated<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut annotated = serializer.serialize_struct(ANNOTATED_STRUCT, 2)?;
+        annotated.serialize_field("annotations", &self.annotations)?;
+        annotated.serialize_field("value", &self.value)?;
+        annotated.end()
+    }
+}
+
+impl ser::Error for IonParserError {
+    fn custom<M: fmt::Display>(msg: M) -> Self {
+        IonParserError::Serialization(msg.to_string())
+    }
+}
+
+fn serialization_error<M: fmt::Display>(msg: M) -> IonParserError {
+    ser::Error::custom(msg)
+}
+
+struct IonSerializer;
+
+impl Serializer for IonSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = ListSerializer;
+    type SerializeMap = StructSerializer;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = StructSerializer;
+
+    fn serialize_bool(self, value: bool) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::Bool(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<IonValue, IonParserError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<IonValue, IonParserError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<IonValue, IonParserError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::Integer(value))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<IonValue, IonParserError> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(IonValue::Integer(value)),
+            Err(_) => Ok(IonValue::BigInteger(BigInt::from(value))),
+        }
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<IonValue, IonParserError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<IonValue, IonParserError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<IonValue, IonParserError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<IonValue, IonParserError> {
+        self.serialize_i128(value.into())
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<IonValue, IonParserError> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(IonValue::Integer(value)),
+            Err(_) => Ok(IonValue::BigInteger(BigInt::from(value))),
+        }
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<IonValue, IonParserError> {
+        self.serialize_f64(value.into())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::Float(value))
+    }
+
+    fn serialize_char(self, value: char) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::String(value.to_string()))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::String(value.to_string()))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::Blob(value.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::Null(NullIonValue::Null))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<IonValue, IonParserError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::Null(NullIonValue::Null))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<IonValue, IonParserError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<IonValue, IonParserError> {
+        Ok(IonValue::Symbol(SymbolToken::from_text(variant)))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<IonValue, IonParserError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<IonValue, IonParserError> {
+        Ok(wrap_in_variant(Some(variant), value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ListSerializer, IonParserError> {
+        Ok(ListSerializer {
+            values: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer, IonParserError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<ListSerializer, IonParserError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<ListSerializer, IonParserError> {
+        Ok(ListSerializer {
+            values: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<StructSerializer, IonParserError> {
+        Ok(StructSerializer::new(None, false, false))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<StructSerializer, IonParserError> {
+        Ok(StructSerializer::new(None, name == ANNOTATED_STRUCT, true))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<StructSerializer, IonParserError> {
+        Ok(StructSerializer::new(Some(variant), false, true))
+    }
+}
+
+fn wrap_in_variant(variant: Option<&str>, value: IonValue) -> IonValue {
+    match variant {
+        Some(variant) => {
+            let mut fields = HashMap::new();
+            fields.insert(variant.into(), value);
+            IonValue::Struct(fields)
+        }
+        None => value,
+    }
+}
+
+struct ListSerializer {
+    values: Vec<IonValue>,
+    variant: Option<&'static str>,
+}
+
+impl ListSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), IonParserError> {
+        self.values.push(value.serialize(IonSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<IonValue, IonParserError> {
+        Ok(wrap_in_variant(self.variant, IonValue::List(self.values)))
+    }
+}
+
+impl SerializeSeq for ListSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), IonParserError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<IonValue, IonParserError> {
+        self.finish()
+    }
+}
+
+impl SerializeTuple for ListSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), IonParserError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<IonValue, IonParserError> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleStruct for ListSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), IonParserError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<IonValue, IonParserError> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleVariant for ListSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), IonParserError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<IonValue, IonParserError> {
+        self.finish()
+    }
+}
+
+// Maps, structs and `Annotated`, which is a struct with the annotations and
+// the value that becomes an annotated value. The fields of Rust structs are
+// `ordered`, maps become a `Struct`.
+struct StructSerializer {
+    fields: Vec<(Arc<str>, IonValue)>,
+    next_key: Option<String>,
+    variant: Option<&'static str>,
+    annotated: bool,
+    ordered: bool,
+}
+
+impl StructSerializer {
+    fn new(variant: Option<&'static str>, annotated: bool, ordered: bool) -> StructSerializer {
+        StructSerializer {
+            fields: vec![],
+            next_key: None,
+            variant,
+            annotated,
+            ordered,
+        }
+    }
+
+    fn insert<T: Serialize + ?Sized>(
+        &mut self,
+        key: String,
+        value: &T,
+    ) -> Result<(), IonParserError> {
+        self.fields
+            .push((key.into(), value.serialize(IonSerializer)?));
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Option<IonValue> {
+        let index = self.fields.iter().position(|(name, _)| &**name == key)?;
+        Some(self.fields.remove(index).1)
+    }
+
+    fn finish(mut self) -> Result<IonValue, IonParserError> {
+        if !self.annotated {
+            let value = if self.ordered {
+                IonValue::OrderedStruct(self.fields)
+            } else {
+                IonValue::Struct(self.fields.into_iter().collect())
+            };
+
+            return Ok(wrap_in_variant(self.variant, value));
+        }
+
+        let value = self
+            .remove("value")
+            .ok_or_else(|| serialization_error("the annotated value is missing"))?;
+
+        let annotations = match self.remove("annotations") {
+            Some(IonValue::List(annotations)) => annotations
+                .into_iter()
+                .map(|annotation| match annotation {
+                    IonValue::String(annotation) => Ok(annotation.into()),
+                    _ => Err(serialization_error("annotations need to be strings")),
+                })
+                .collect::<Result<Vec<Arc<str>>, IonParserError>>()?,
+            _ => vec![],
+        };
+
+        if annotations.is_empty() {
+            return Ok(value);
+        }
+
+        Ok(IonValue::Annotation(annotations, Box::new(value)))
+    }
+}
+
+impl SerializeMap for StructSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), IonParserError> {
+        let key = match key.serialize(IonSerializer)? {
+            IonValue::String(key) => key,
+            IonValue::Symbol(SymbolToken {
+                text: Some(key), ..
+            }) => key.to_string(),
+            _ => return Err(serialization_error("struct field names need to be strings")),
+        };
+
+        self.next_key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), IonParserError> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| serialization_error("a map value was serialized without its key"))?;
+
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<IonValue, IonParserError> {
+        self.finish()
+    }
+}
+
+impl SerializeStruct for StructSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), IonParserError> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<IonValue, IonParserError> {
+        self.finish()
+    }
+}
+
+impl SerializeStructVariant for StructSerializer {
+    type Ok = IonValue;
+    type Error = IonParserError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), IonParserError> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<IonValue, IonParserError> {
+        self.finish()
+    }
+}
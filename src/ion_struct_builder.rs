@@ -0,0 +1,67 @@
+use crate::IonValue;
+use std::sync::Arc;
+
+/// Builds a struct field by field, optionally normalizing the keys, which
+/// is handy when the data comes from external sources.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonValue, StructBuilder};
+///
+/// let value = StructBuilder::new()
+///     .with_key_normalizer(|key| key.trim().to_string())
+///     .insert(" Make ", IonValue::String("Mercedes".into()))
+///     .build();
+///
+/// assert_eq!(value.get_as::<String>("Make").unwrap(), "Mercedes");
+/// ```
+///
+/// The normalizer is applied to every key when building, no matter if the
+/// fields were inserted before setting it. For `build`, when several keys
+/// end up being the same, the last field wins. `build_ordered` keeps all of
+/// them.
+#[derive(Debug, Clone, Default)]
+pub struct StructBuilder {
+    fields: Vec<(String, IonValue)>,
+    key_normalizer: Option<fn(&str) -> String>,
+}
+
+impl StructBuilder {
+    pub fn new() -> StructBuilder {
+        StructBuilder {
+            fields: vec![],
+            key_normalizer: None,
+        }
+    }
+
+    pub fn with_key_normalizer(mut self, normalizer: fn(&str) -> String) -> StructBuilder {
+        self.key_normalizer = Some(normalizer);
+        self
+    }
+
+    pub fn insert<K: Into<String>>(mut self, key: K, value: IonValue) -> StructBuilder {
+        self.fields.push((key.into(), value));
+        self
+    }
+
+    /// Returns an `IonValue::Struct`.
+    pub fn build(self) -> IonValue {
+        IonValue::Struct(self.normalized_fields().collect())
+    }
+
+    /// Returns an `IonValue::OrderedStruct` with the fields in the order
+    /// they were inserted.
+    pub fn build_ordered(self) -> IonValue {
+        IonValue::OrderedStruct(self.normalized_fields().collect())
+    }
+
+    fn normalized_fields(self) -> impl Iterator<Item = (Arc<str>, IonValue)> {
+        let normalizer = self.key_normalizer;
+
+        self.fields
+            .into_iter()
+            .map(move |(key, value)| match normalizer {
+                Some(normalizer) => (normalizer(&key).into(), value),
+                None => (key.into(), value),
+            })
+    }
+}
@@ -0,0 +1,1048 @@
+use crate::binary_parser_types::SYSTEM_SYMBOL_TABLE;
+use crate::ion_parser::exceeds_decimal_digits;
+use crate::{
+    IonParserConfig, IonParserError, IonTimestamp, IonValue, NullIonValue, ParsingError,
+    SymbolToken, TimestampPrecision,
+};
+use bigdecimal::BigDecimal;
+use chrono::{Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const OPERATOR_CHARS: &[u8] = b"!#%&*+-./;<=>?@^`|~";
+
+/// Parser for the Ion text format, returning the same `IonValue`s as
+/// `IonParser` does for Ion binary. It supports the whole Ion 1.0 text
+/// syntax: comments, annotations, quoted and operator symbols, symbol ids,
+/// long strings, timestamps, hex and binary integers, decimals with `d`
+/// exponents and lobs in `{{ }}`.
+///
+/// Local symbol tables (`$ion_symbol_table::{ ... }`) are loaded in order to
+/// resolve symbol ids like `$10`. Shared symbol tables are not supported, so
+/// the symbols of their imports have unknown text and the imports need a
+/// `max_id`. The configuration options about the returned values and the
+/// limits work as in `IonParser`.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonTextParser, IonValue};
+///
+/// let mut parser = IonTextParser::new("meters::3 [1, 2.5d0]");
+///
+/// assert_eq!(
+///     parser.consume_value().unwrap(),
+///     IonValue::Annotation(vec!["meters".into()], Box::new(IonValue::Integer(3)))
+/// );
+/// ```
+#[derive(Debug)]
+pub struct IonTextParser {
+    text: String,
+    position: usize,
+    config: IonParserConfig,
+    symbols: Vec<Option<Arc<str>>>,
+    depth: usize,
+}
+
+impl IonTextParser {
+    pub fn new<S: Into<String>>(text: S) -> IonTextParser {
+        IonTextParser::with_config(text, IonParserConfig::default())
+    }
+
+    pub fn with_config<S: Into<String>>(text: S, config: IonParserConfig) -> IonTextParser {
+        IonTextParser {
+            text: text.into(),
+            position: 0,
+            config,
+            symbols: system_symbols(),
+            depth: 0,
+        }
+    }
+
+    /// Consumes **one** value, skipping comments, Ion Version Markers and
+    /// local symbol tables. At the end of the text it returns
+    /// `IonParserError::BinaryError(ParsingError::NoDataToRead)`, same as
+    /// `IonParser::consume_value`.
+    pub fn consume_value(&mut self) -> Result<IonValue, IonParserError> {
+        loop {
+            self.skip_whitespace()?;
+
+            if self.peek().is_none() {
+                return Err(IonParserError::BinaryError(ParsingError::NoDataToRead));
+            }
+
+            let start = self.position;
+
+            match self.parse_value(false)? {
+                // Only the unquoted symbol is a version marker, `'$ion_1_0'`
+                // and `$2` are symbol values.
+                IonValue::Symbol(_) if &self.text[start..self.position] == "$ion_1_0" => {
+                    self.symbols = system_symbols()
+                }
+                IonValue::Annotation(annotations, value)
+                    if &*annotations[0] == "$ion_symbol_table" && is_struct(&value) =>
+                {
+                    self.load_symbol_table(*value)?
+                }
+                value => return Ok(value),
+            }
+        }
+    }
+
+    /// Consumes all the values of the text and returns an array with them.
+    pub fn consume_all(&mut self) -> Result<Vec<IonValue>, IonParserError> {
+        let mut values = vec![];
+
+        loop {
+            match self.consume_value() {
+                Err(IonParserError::BinaryError(ParsingError::NoDataToRead)) => break,
+                Ok(value) => values.push(value),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn load_symbol_table(&mut self, table: IonValue) -> Result<(), IonParserError> {
+        let fields = struct_fields(table);
+
+        match field(&fields, "imports") {
+            Some(IonValue::Symbol(symbol)) if symbol.text() == Some("$ion_symbol_table") => {}
+            Some(IonValue::List(imports)) => {
+                self.symbols = system_symbols();
+
+                for import in imports {
+                    self.load_import(import)?;
+                }
+            }
+            _ => self.symbols = system_symbols(),
+        }
+
+        if let Some(IonValue::List(symbols)) = field(&fields, "symbols") {
+            self.check_max_symbols(symbols.len())?;
+
+            for symbol in symbols {
+                match symbol {
+                    IonValue::String(text) => self.symbols.push(Some(Arc::from(text.as_str()))),
+                    _ => self.symbols.push(None),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // The shared table is not known, so the import adds `max_id` symbols
+    // with unknown text.
+    fn load_import(&mut self, import: &IonValue) -> Result<(), IonParserError> {
+        let fields = struct_fields(import.clone());
+
+        match field(&fields, "name") {
+            Some(IonValue::String(name)) if name != "$ion" => {}
+            _ => return Ok(()),
+        }
+
+        let max_id = match field(&fields, "max_id") {
+            Some(IonValue::Integer(max_id)) => usize::try_from(*max_id)
+                .map_err(|_| self.error("the max_id of an import can't be negative"))?,
+            _ => return Err(self.error("an import of an unknown shared table needs a max_id")),
+        };
+
+        self.check_max_symbols(max_id)?;
+        self.symbols.resize(self.symbols.len() + max_id, None);
+
+        Ok(())
+    }
+
+    // Checks the local symbols count after adding `new_symbols` to it.
+    fn check_max_symbols(&self, new_symbols: usize) -> Result<(), IonParserError> {
+        let count = (self.symbols.len() - SYSTEM_SYMBOL_TABLE.len()).saturating_add(new_symbols);
+
+        match self.config.max_symbols {
+            Some(max_symbols) if count > max_symbols => {
+                Err(IonParserError::TooManySymbols(max_symbols))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn parse_value(&mut self, in_sexp: bool) -> Result<IonValue, IonParserError> {
+        let mut annotations = vec![];
+
+        let value = loop {
+            self.skip_whitespace()?;
+
+            let value = self.parse_unannotated_value(in_sexp)?;
+
+            if let IonValue::Symbol(symbol) = &value {
+                let position = self.position;
+                self.skip_whitespace()?;
+
+                if self.starts_with("::") {
+                    self.position += 2;
+                    annotations.push(self.symbol_text(symbol)?);
+                    continue;
+                }
+
+                self.position = position;
+            }
+
+            break value;
+        };
+
+        if annotations.is_empty() {
+            Ok(value)
+        } else {
+            Ok(IonValue::Annotation(annotations, Box::new(value)))
+        }
+    }
+
+    fn parse_unannotated_value(&mut self, in_sexp: bool) -> Result<IonValue, IonParserError> {
+        let next = self
+            .peek()
+            .ok_or_else(|| self.error("a value was expected"))?;
+
+        match next {
+            b'[' => self.parse_list(),
+            b'(' => self.parse_sexp(),
+            b'{' if self.starts_with("{{") => self.parse_lob(),
+            b'{' => self.parse_struct(),
+            b'"' => Ok(IonValue::String(self.parse_short_string(false)?)),
+            b'\'' if self.starts_with("'''") => {
+                Ok(IonValue::String(self.parse_long_strings(false)?))
+            }
+            b'\'' => Ok(IonValue::Symbol(SymbolToken::from_text(
+                self.parse_quoted_symbol()?,
+            ))),
+            b'+' if self.starts_with_keyword("+inf") => {
+                self.position += 4;
+                Ok(IonValue::Float(f64::INFINITY))
+            }
+            b'-' if self.starts_with_keyword("-inf") => {
+                self.position += 4;
+                Ok(IonValue::Float(f64::NEG_INFINITY))
+            }
+            b'0'..=b'9' => self.parse_number(),
+            b'-' if matches!(self.peek_at(1), Some(b'0'..=b'9')) => self.parse_number(),
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'$' => self.parse_identifier(),
+            _ if in_sexp && OPERATOR_CHARS.contains(&next) => {
+                let operator = self.take_while(|c| OPERATOR_CHARS.contains(&c));
+                Ok(IonValue::Symbol(SymbolToken::from_text(operator)))
+            }
+            _ => Err(self.error("a value was expected")),
+        }
+    }
+
+    fn parse_identifier(&mut self) -> Result<IonValue, IonParserError> {
+        let start = self.position;
+        let identifier = self.take_while(is_identifier_char);
+
+        let value = match identifier.as_str() {
+            "null" if self.peek() == Some(b'.') => {
+                self.position += 1;
+                let null_type = self.take_while(is_identifier_char);
+                IonValue::Null(self.null_type(&null_type, start)?)
+            }
+            "null" => IonValue::Null(NullIonValue::Null),
+            "true" => IonValue::Bool(true),
+            "false" => IonValue::Bool(false),
+            "nan" => IonValue::Float(f64::NAN),
+            _ if is_symbol_id(&identifier) => {
+                let id = identifier[1..]
+                    .parse::<usize>()
+                    .map_err(|_| IonParserError::SymbolIdNotDefined)?;
+
+                match self.symbols.get(id) {
+                    Some(text) => IonValue::Symbol(SymbolToken::new(text.clone(), Some(id as u64))),
+                    None => return Err(IonParserError::SymbolIdNotDefined),
+                }
+            }
+            _ => IonValue::Symbol(SymbolToken::from_text(identifier)),
+        };
+
+        Ok(value)
+    }
+
+    fn null_type(&self, name: &str, start: usize) -> Result<NullIonValue, IonParserError> {
+        let null_type = match name {
+            "null" => NullIonValue::Null,
+            "bool" => NullIonValue::Bool,
+            "int" => NullIonValue::Integer,
+            "float" => NullIonValue::Float,
+            "decimal" => NullIonValue::Decimal,
+            "timestamp" => NullIonValue::DateTime,
+            "string" => NullIonValue::String,
+            "symbol" => NullIonValue::Symbol,
+            "clob" => NullIonValue::Clob,
+            "blob" => NullIonValue::Blob,
+            "list" => NullIonValue::List,
+            "sexp" => NullIonValue::SExpr,
+            "struct" => NullIonValue::Struct,
+            _ => return Err(self.error_at(start, "unknown null type")),
+        };
+
+        Ok(null_type)
+    }
+
+    fn parse_list(&mut self) -> Result<IonValue, IonParserError> {
+        self.enter_container()?;
+        let mut values = vec![];
+
+        loop {
+            self.skip_whitespace()?;
+
+            if self.consume_byte(b']') {
+                break;
+            }
+
+            values.push(self.parse_value(false)?);
+            self.skip_whitespace()?;
+
+            if !self.consume_byte(b',') && self.peek() != Some(b']') {
+                return Err(self.error("expected ',' or ']' after a list element"));
+            }
+        }
+
+        self.depth -= 1;
+        Ok(IonValue::List(values))
+    }
+
+    fn parse_sexp(&mut self) -> Result<IonValue, IonParserError> {
+        self.enter_container()?;
+        let mut values = vec![];
+
+        loop {
+            self.skip_whitespace()?;
+
+            if self.consume_byte(b')') {
+                break;
+            }
+
+            values.push(self.parse_value(true)?);
+        }
+
+        self.depth -= 1;
+        Ok(IonValue::SExpr(values))
+    }
+
+    fn parse_struct(&mut self) -> Result<IonValue, IonParserError> {
+        self.enter_container()?;
+        let mut fields = vec![];
+
+        loop {
+            self.skip_whitespace()?;
+
+            if self.consume_byte(b'}') {
+                break;
+            }
+
+            let name = self.parse_field_name()?;
+            self.skip_whitespace()?;
+
+            if self.starts_with("::") || !self.consume_byte(b':') {
+                return Err(self.error("expected ':' after a field name"));
+            }
+
+            fields.push((name, self.parse_value(false)?));
+            self.skip_whitespace()?;
+
+            if !self.consume_byte(b',') && self.peek() != Some(b'}') {
+                return Err(self.error("expected ',' or '}' after a struct field"));
+            }
+        }
+
+        self.depth -= 1;
+
+        if self.config.preserve_struct_order {
+            Ok(IonValue::OrderedStruct(fields))
+        } else {
+            Ok(IonValue::Struct(
+                fields.into_iter().collect::<HashMap<_, _>>(),
+            ))
+        }
+    }
+
+    fn parse_field_name(&mut self) -> Result<Arc<str>, IonParserError> {
+        match self.peek() {
+            Some(b'"') => Ok(self.parse_short_string(false)?.into()),
+            Some(b'\'') if self.starts_with("'''") => Ok(self.parse_long_strings(false)?.into()),
+            Some(b'\'') => Ok(self.parse_quoted_symbol()?.into()),
+            Some(b'a'..=b'z') | Some(b'A'..=b'Z') | Some(b'_') | Some(b'$') => {
+                match self.parse_identifier()? {
+                    IonValue::Symbol(symbol) => self.symbol_text(&symbol),
+                    _ => Err(self.error("field names can't be keywords")),
+                }
+            }
+            _ => Err(self.error("a field name was expected")),
+        }
+    }
+
+    fn parse_lob(&mut self) -> Result<IonValue, IonParserError> {
+        self.position += 2;
+        self.skip_lob_whitespace();
+
+        let value = match self.peek() {
+            Some(b'"') => IonValue::Clob(into_clob(self.parse_short_string(true)?)),
+            Some(b'\'') if self.starts_with("'''") => {
+                IonValue::Clob(into_clob(self.parse_long_strings(true)?))
+            }
+            _ => {
+                let start = self.position;
+                let base64 = self.take_while(|c| c != b'}');
+
+                IonValue::Blob(
+                    decode_base64(&base64).ok_or_else(|| self.error_at(start, "invalid base64"))?,
+                )
+            }
+        };
+
+        self.skip_lob_whitespace();
+
+        if !self.starts_with("}}") {
+            return Err(self.error("expected '}}' at the end of the lob"));
+        }
+
+        self.position += 2;
+        Ok(value)
+    }
+
+    fn parse_quoted_symbol(&mut self) -> Result<String, IonParserError> {
+        self.parse_quoted(b'\'', false)
+    }
+
+    fn parse_short_string(&mut self, clob: bool) -> Result<String, IonParserError> {
+        self.parse_quoted(b'"', clob)
+    }
+
+    fn parse_quoted(&mut self, quote: u8, clob: bool) -> Result<String, IonParserError> {
+        self.position += 1;
+        let mut text = String::new();
+
+        loop {
+            match self.next_char(clob)? {
+                '\\' => {
+                    if let Some(c) = self.parse_escape(clob)? {
+                        text.push(c);
+                    }
+                }
+                c if c == char::from(quote) => return Ok(text),
+                '\n' | '\r' => return Err(self.error("new line in a quoted text")),
+                c => text.push(c),
+            }
+        }
+    }
+
+    // Adjacent long strings are a single value.
+    fn parse_long_strings(&mut self, clob: bool) -> Result<String, IonParserError> {
+        let mut text = String::new();
+
+        while self.starts_with("'''") {
+            self.position += 3;
+
+            while !self.starts_with("'''") {
+                match self.next_char(clob)? {
+                    '\\' => {
+                        if let Some(c) = self.parse_escape(clob)? {
+                            text.push(c);
+                        }
+                    }
+                    c => text.push(c),
+                }
+            }
+
+            self.position += 3;
+
+            let position = self.position;
+
+            if clob {
+                self.skip_lob_whitespace();
+            } else {
+                self.skip_whitespace()?;
+            }
+
+            if !self.starts_with("'''") {
+                self.position = position;
+            }
+        }
+
+        Ok(text)
+    }
+
+    // Returns `None` for escaped new lines, which are skipped.
+    fn parse_escape(&mut self, clob: bool) -> Result<Option<char>, IonParserError> {
+        let start = self.position - 1;
+
+        let c = match self.next_char(clob)? {
+            'a' => '\u{07}',
+            'b' => '\u{08}',
+            't' => '\t',
+            'n' => '\n',
+            'f' => '\u{0C}',
+            'r' => '\r',
+            'v' => '\u{0B}',
+            '?' => '?',
+            '0' => '\0',
+            '\'' => '\'',
+            '"' => '"',
+            '/' => '/',
+            '\\' => '\\',
+            '\n' => return Ok(None),
+            '\r' => {
+                self.consume_byte(b'\n');
+                return Ok(None);
+            }
+            'x' => self.parse_escaped_code(2, start)?,
+            'u' if !clob => self.parse_escaped_code(4, start)?,
+            'U' if !clob => self.parse_escaped_code(8, start)?,
+            _ => return Err(self.error_at(start, "invalid escape sequence")),
+        };
+
+        Ok(Some(c))
+    }
+
+    fn parse_escaped_code(&mut self, digits: usize, start: usize) -> Result<char, IonParserError> {
+        let hex = self
+            .text
+            .get(self.position..self.position + digits)
+            .filter(|hex| hex.bytes().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error_at(start, "invalid escape sequence"))?;
+
+        let code = u32::from_str_radix(hex, 16)
+            .map_err(|_| self.error_at(start, "invalid escape sequence"))?;
+        self.position += digits;
+
+        // A high surrogate needs to be followed by a low one.
+        if digits == 4 && (0xD800..0xDC00).contains(&code) {
+            if !self.starts_with("\\u") {
+                return Err(self.error_at(start, "invalid unicode escape"));
+            }
+
+            self.position += 2;
+            let low = self.parse_escaped_code(4, start)? as u32;
+
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error_at(start, "invalid unicode escape"));
+            }
+
+            let code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+
+            return std::char::from_u32(code)
+                .ok_or_else(|| self.error_at(start, "invalid unicode escape"));
+        }
+
+        std::char::from_u32(code).ok_or_else(|| self.error_at(start, "invalid unicode escape"))
+    }
+
+    fn parse_number(&mut self) -> Result<IonValue, IonParserError> {
+        let start = self.position;
+        let token = self.take_while(|c| c.is_ascii_alphanumeric() || b"_.:+-".contains(&c));
+        let bytes = token.as_bytes();
+
+        if bytes.len() > 4
+            && bytes[..4].iter().all(u8::is_ascii_digit)
+            && matches!(bytes[4], b'-' | b'T')
+        {
+            return self.parse_timestamp(&token, start);
+        }
+
+        let (negative, body) = match token.strip_prefix('-') {
+            Some(body) => (true, body),
+            None => (false, token.as_str()),
+        };
+
+        let radix = match body.get(..2) {
+            Some("0x") | Some("0X") => Some(16),
+            Some("0b") | Some("0B") => Some(2),
+            _ => None,
+        };
+
+        let value = match radix {
+            Some(radix) => parse_integer(negative, &body[2..], radix),
+            None if body.contains(['e', 'E']) => parse_float(&token),
+            None if body.contains(['.', 'd', 'D']) => {
+                let decimal =
+                    parse_decimal(&token).ok_or_else(|| self.error_at(start, "invalid decimal"))?;
+
+                if let Some(max_digits) = self.config.max_decimal_digits {
+                    if exceeds_decimal_digits(&decimal.as_bigint_and_exponent().0, max_digits) {
+                        return Err(IonParserError::DecimalTooLarge);
+                    }
+                }
+
+                Some(IonValue::Decimal(decimal))
+            }
+            None if body.len() > 1 && body.starts_with('0') => None,
+            None => parse_integer(negative, body, 10),
+        };
+
+        value.ok_or_else(|| self.error_at(start, "invalid number"))
+    }
+
+    fn parse_timestamp(&self, token: &str, start: usize) -> Result<IonValue, IonParserError> {
+        let invalid = || self.error_at(start, "invalid timestamp");
+        let mut reader = TimestampReader {
+            text: token.as_bytes(),
+            position: 0,
+        };
+
+        let year = reader
+            .number(4)
+            .filter(|year| *year > 0)
+            .ok_or_else(invalid)?;
+        let mut components = [1u32, 1, 0, 0, 0];
+        let mut nanosecond = 0;
+        let mut offset = None;
+
+        let precision = if reader.is_at("T") {
+            TimestampPrecision::Year
+        } else {
+            reader.expect(b'-').ok_or_else(invalid)?;
+            components[0] = reader.number(2).ok_or_else(invalid)?;
+
+            if reader.is_at("T") {
+                TimestampPrecision::Month
+            } else {
+                reader.expect(b'-').ok_or_else(invalid)?;
+                components[1] = reader.number(2).ok_or_else(invalid)?;
+
+                if reader.is_at("") || reader.is_at("T") {
+                    TimestampPrecision::Day
+                } else {
+                    reader.expect(b'T').ok_or_else(invalid)?;
+                    components[2] = reader.number(2).ok_or_else(invalid)?;
+                    reader.expect(b':').ok_or_else(invalid)?;
+                    components[3] = reader.number(2).ok_or_else(invalid)?;
+
+                    let precision = if reader.expect(b':').is_some() {
+                        components[4] = reader.number(2).ok_or_else(invalid)?;
+
+                        if reader.expect(b'.').is_some() {
+                            let (nanos, digits) = reader.fraction().ok_or_else(invalid)?;
+                            nanosecond = nanos;
+                            TimestampPrecision::Fraction(std::cmp::min(digits, 9))
+                        } else {
+                            TimestampPrecision::Second
+                        }
+                    } else {
+                        TimestampPrecision::Minute
+                    };
+
+                    offset = reader.offset().ok_or_else(invalid)?;
+
+                    if !reader.is_at("") {
+                        return Err(invalid());
+                    }
+
+                    precision
+                }
+            }
+        };
+
+        let [month, day, hour, minute, second] = components;
+
+        let local = NaiveDate::from_ymd_opt(year as i32, month, day)
+            .ok_or(IonParserError::InvalidTimestampComponent(
+                year as i32,
+                month,
+                day,
+            ))?
+            .and_hms_nano_opt(hour, minute, second, nanosecond)
+            .ok_or(IonParserError::InvalidDate(
+                year as i32,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                nanosecond,
+            ))?;
+
+        let utc: NaiveDateTime = local - Duration::minutes(offset.unwrap_or(0).into());
+
+        if self.config.preserve_timestamp_precision {
+            return Ok(IonValue::Timestamp(IonTimestamp::new(
+                utc, offset, precision,
+            )?));
+        }
+
+        match offset {
+            None => Ok(IonValue::DateTimeUnknownOffset(utc)),
+            Some(offset) => {
+                let offset = FixedOffset::east_opt(offset * 60)
+                    .ok_or(IonParserError::InvalidTimestampOffset)?;
+
+                Ok(IonValue::DateTime(offset.from_utc_datetime(&utc)))
+            }
+        }
+    }
+
+    fn symbol_text(&self, symbol: &SymbolToken) -> Result<Arc<str>, IonParserError> {
+        symbol
+            .text
+            .clone()
+            .ok_or(IonParserError::SymbolNotFoundInTable)
+    }
+
+    fn enter_container(&mut self) -> Result<(), IonParserError> {
+        self.position += 1;
+        self.depth += 1;
+
+        match self.config.max_depth {
+            Some(max_depth) if self.depth > max_depth => {
+                Err(IonParserError::MaxDepthExceeded(max_depth))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), IonParserError> {
+        loop {
+            self.skip_lob_whitespace();
+
+            if self.starts_with("//") {
+                self.take_while(|c| c != b'\n');
+            } else if self.starts_with("/*") {
+                let start = self.position;
+
+                match self.text[self.position + 2..].find("*/") {
+                    Some(end) => self.position += end + 4,
+                    None => return Err(self.error_at(start, "unterminated comment")),
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    // Lobs can't have comments.
+    fn skip_lob_whitespace(&mut self) {
+        self.take_while(|c| matches!(c, b' ' | b'\t' | b'\n' | b'\r' | b'\x0B' | b'\x0C'));
+    }
+
+    fn next_char(&mut self, clob: bool) -> Result<char, IonParserError> {
+        let c = self.text[self.position..]
+            .chars()
+            .next()
+            .ok_or_else(|| self.error("the text ended in the middle of a value"))?;
+
+        if clob && !c.is_ascii() {
+            return Err(self.error("clobs can only have ASCII characters"));
+        }
+
+        self.position += c.len_utf8();
+        Ok(c)
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> String {
+        let start = self.position;
+
+        while matches!(self.peek(), Some(c) if predicate(c)) {
+            self.position += 1;
+        }
+
+        self.text[start..self.position].to_string()
+    }
+
+    fn starts_with_keyword(&self, keyword: &str) -> bool {
+        self.starts_with(keyword)
+            && !matches!(self.peek_at(keyword.len()), Some(c) if is_identifier_char(c))
+    }
+
+    fn consume_byte(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn starts_with(&self, prefix: &str) -> bool {
+        self.text[self.position..].starts_with(prefix)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.text.as_bytes().get(self.position + offset).copied()
+    }
+
+    fn error(&self, reason: &str) -> IonParserError {
+        self.error_at(self.position, reason)
+    }
+
+    fn error_at(&self, position: usize, reason: &str) -> IonParserError {
+        IonParserError::InvalidText {
+            position,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+struct TimestampReader<'a> {
+    text: &'a [u8],
+    position: usize,
+}
+
+impl<'a> TimestampReader<'a> {
+    fn number(&mut self, digits: usize) -> Option<u32> {
+        let number = self.text.get(self.position..self.position + digits)?;
+
+        if !number.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        self.position += digits;
+
+        Some(
+            number
+                .iter()
+                .fold(0, |value, digit| value * 10 + u32::from(digit - b'0')),
+        )
+    }
+
+    // The nanoseconds and the number of digits of the fraction.
+    fn fraction(&mut self) -> Option<(u32, u32)> {
+        let digits = self.text[self.position..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+
+        if digits == 0 {
+            return None;
+        }
+
+        let nanos = self.text[self.position..self.position + digits]
+            .iter()
+            .chain(std::iter::repeat(&b'0'))
+            .take(9)
+            .fold(0, |value, digit| value * 10 + u32::from(digit - b'0'));
+
+        self.position += digits;
+
+        Some((nanos, digits as u32))
+    }
+
+    // The offset in minutes, `None` for the unknown offset `-00:00`.
+    fn offset(&mut self) -> Option<Option<i32>> {
+        let sign = match self.text.get(self.position)? {
+            b'Z' => {
+                self.position += 1;
+                return Some(Some(0));
+            }
+            b'+' => 1,
+            b'-' => -1,
+            _ => return None,
+        };
+
+        self.position += 1;
+        let hours = self.number(2)?;
+        self.expect(b':')?;
+        let minutes = self.number(2)?;
+
+        if hours >= 24 || minutes >= 60 {
+            return None;
+        }
+
+        if sign < 0 && hours == 0 && minutes == 0 {
+            return Some(None);
+        }
+
+        Some(Some(sign * (hours * 60 + minutes) as i32))
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        if self.text.get(self.position) == Some(&byte) {
+            self.position += 1;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn is_at(&self, rest: &str) -> bool {
+        self.text.get(self.position..) == Some(rest.as_bytes())
+    }
+}
+
+fn system_symbols() -> Vec<Option<Arc<str>>> {
+    SYSTEM_SYMBOL_TABLE
+        .iter()
+        .enumerate()
+        .map(|(id, text)| {
+            if id == 0 {
+                None
+            } else {
+                Some(Arc::from(*text))
+            }
+        })
+        .collect()
+}
+
+fn struct_fields(value: IonValue) -> Vec<(Arc<str>, IonValue)> {
+    match value {
+        IonValue::Struct(fields) => fields.into_iter().collect(),
+        IonValue::OrderedStruct(fields) => fields,
+        _ => vec![],
+    }
+}
+
+fn field<'a>(fields: &'a [(Arc<str>, IonValue)], name: &str) -> Option<&'a IonValue> {
+    fields
+        .iter()
+        .find(|(key, _)| &**key == name)
+        .map(|(_, value)| value)
+}
+
+fn is_struct(value: &IonValue) -> bool {
+    matches!(value, IonValue::Struct(_) | IonValue::OrderedStruct(_))
+}
+
+fn is_identifier_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$'
+}
+
+fn is_symbol_id(identifier: &str) -> bool {
+    identifier.len() > 1
+        && identifier.starts_with('$')
+        && identifier[1..].bytes().all(|c| c.is_ascii_digit())
+}
+
+// Underscores are only allowed between digits.
+fn remove_underscores(digits: &str) -> Option<String> {
+    let bytes = digits.as_bytes();
+
+    for (index, c) in bytes.iter().enumerate() {
+        if *c == b'_' {
+            let before = index.checked_sub(1).and_then(|index| bytes.get(index));
+            let after = bytes.get(index + 1);
+
+            let between_digits = matches!((before, after), (Some(before), Some(after))
+                if before.is_ascii_hexdigit() && after.is_ascii_hexdigit());
+
+            if !between_digits {
+                return None;
+            }
+        }
+    }
+
+    Some(digits.replace('_', ""))
+}
+
+fn parse_integer(negative: bool, digits: &str, radix: u32) -> Option<IonValue> {
+    let digits = remove_underscores(digits)?;
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+
+    let value = BigInt::parse_bytes(digits.as_bytes(), radix)?;
+    let value = if negative { -value } else { value };
+
+    match i64::try_from(&value) {
+        Ok(value) => Some(IonValue::Integer(value)),
+        Err(_) => Some(IonValue::BigInteger(value)),
+    }
+}
+
+fn parse_float(token: &str) -> Option<IonValue> {
+    let token = remove_underscores(token)?;
+    let (mantissa, exponent) = token.split_at(token.find(['e', 'E'])?);
+
+    if !is_decimal_mantissa(mantissa) || !is_exponent(&exponent[1..]) {
+        return None;
+    }
+
+    token.parse::<f64>().ok().map(IonValue::Float)
+}
+
+fn parse_decimal(token: &str) -> Option<BigDecimal> {
+    let token = remove_underscores(token)?;
+
+    let (mantissa, exponent) = match token.find(['d', 'D']) {
+        Some(index) => (&token[..index], &token[index + 1..]),
+        None => (token.as_str(), "0"),
+    };
+
+    if !is_decimal_mantissa(mantissa) || !is_exponent(exponent) {
+        return None;
+    }
+
+    BigDecimal::from_str(&format!("{}e{}", mantissa.trim_end_matches('.'), exponent)).ok()
+}
+
+// An integer without leading zeros, with optional fraction digits.
+fn is_decimal_mantissa(mantissa: &str) -> bool {
+    let mantissa = mantissa.strip_prefix('-').unwrap_or(mantissa);
+
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+        None => (mantissa, ""),
+    };
+
+    !integer.is_empty()
+        && integer.bytes().all(|c| c.is_ascii_digit())
+        && !(integer.len() > 1 && integer.starts_with('0'))
+        && fraction.bytes().all(|c| c.is_ascii_digit())
+}
+
+fn is_exponent(exponent: &str) -> bool {
+    let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+
+    !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit())
+}
+
+// Clob texts only have characters up to 0xFF, from ASCII or `\x` escapes.
+fn into_clob(text: String) -> Vec<u8> {
+    text.chars().map(|c| c as u8).collect()
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text: Vec<u8> = text.bytes().filter(|c| !c.is_ascii_whitespace()).collect();
+
+    let mut bytes = vec![];
+    let chunks = text.chunks(4);
+    let last_chunk = chunks.len().saturating_sub(1);
+
+    for (index, chunk) in chunks.enumerate() {
+        if chunk.len() != 4 {
+            return None;
+        }
+
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+
+        if padding > 2 || (padding > 0 && index != last_chunk) {
+            return None;
+        }
+
+        let mut group = 0u32;
+
+        for c in &chunk[..4 - padding] {
+            group = (group << 6) | base64_value(*c)?;
+        }
+
+        group <<= 6 * padding as u32;
+
+        let group = group.to_be_bytes();
+        bytes.extend_from_slice(&group[1..4 - padding]);
+    }
+
+    Some(bytes)
+}
+
+fn base64_value(c: u8) -> Option<u32> {
+    let value = match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    };
+
+    Some(value.into())
+}
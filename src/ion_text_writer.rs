@@ -0,0 +1,532 @@
+use crate::{IonTimestamp, IonValue, NullIonValue, SymbolToken, TimestampPrecision};
+use bigdecimal::BigDecimal;
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDateTime, SecondsFormat, TimeZone, Timelike, Utc,
+};
+use std::fmt::Write;
+use std::sync::Arc;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const OPERATOR_CHARACTERS: &str = "!#%&*+-./;<=>?@^`|~";
+
+const KEYWORDS: &[&str] = &["null", "true", "false", "nan"];
+
+/// Writes IonValues in the Ion text format. Useful for debugging binary
+/// payloads and for writing human readable fixtures.
+///
+/// It works the same way than the IonEncoder:
+///
+/// - `add` allows to add IonValue to the buffer.
+/// - `write` takes all buffered values and writes them, one per line.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonTextWriter, IonValue};
+///
+/// let mut writer = IonTextWriter::new();
+///
+/// writer.add(IonValue::SExpr(vec![
+///     IonValue::Symbol("a".into()),
+///     IonValue::Symbol("+".into()),
+///     IonValue::Symbol("b".into()),
+/// ]));
+///
+/// assert_eq!(writer.write(), "(a + b)");
+/// ```
+///
+/// Struct fields are written sorted by key so the output is stable. Ordered
+/// structs keep their own order.
+///
+/// The layout can be changed with `IonTextWriterOptions`, for example
+/// `IonTextWriter::with_options(IonTextWriterOptions::pretty())` writes
+/// indented values, one element per line.
+#[derive(Debug, Default)]
+pub struct IonTextWriter {
+    current_buffer: Vec<IonValue>,
+    options: IonTextWriterOptions,
+}
+
+/// Formatting options of the `IonTextWriter`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IonTextWriterOptions {
+    /// Only used with the `TextLayout::Default` layout.
+    pub list_format: ListFormat,
+    pub layout: TextLayout,
+}
+
+impl IonTextWriterOptions {
+    /// Options for the `TextLayout::Compact` layout.
+    pub fn compact() -> IonTextWriterOptions {
+        IonTextWriterOptions {
+            layout: TextLayout::Compact,
+            ..IonTextWriterOptions::default()
+        }
+    }
+
+    /// Options for the `TextLayout::Pretty` layout.
+    pub fn pretty() -> IonTextWriterOptions {
+        IonTextWriterOptions {
+            layout: TextLayout::Pretty,
+            ..IonTextWriterOptions::default()
+        }
+    }
+}
+
+/// How containers are written:
+///
+/// - `Default`: lists follow the `ListFormat`, structs are written as
+///   `{a: 1, b: 2}` and sexps as `(a b)`.
+/// - `Compact`: without optional spaces, like `{a:1,b:[1,2]}`.
+/// - `Pretty`: one element or field per line, indented two spaces per
+///   nesting level. Empty containers are always written as `[]`, `()` and
+///   `{}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextLayout {
+    #[default]
+    Default,
+    Compact,
+    Pretty,
+}
+
+/// How lists are written:
+///
+/// - `Compact`: `[1,2,3]`
+/// - `Spaced`: `[1, 2, 3]`, the default.
+/// - `MultiLine`: one element per line, indented two spaces per nesting
+///   level. Empty lists are always written as `[]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListFormat {
+    Compact,
+    #[default]
+    Spaced,
+    MultiLine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WriterContext {
+    Value,
+    SExpr,
+}
+
+impl IonTextWriter {
+    pub fn new() -> IonTextWriter {
+        IonTextWriter::with_options(IonTextWriterOptions::default())
+    }
+
+    pub fn with_options(options: IonTextWriterOptions) -> IonTextWriter {
+        IonTextWriter {
+            current_buffer: vec![],
+            options,
+        }
+    }
+
+    pub fn add(&mut self, value: IonValue) {
+        self.current_buffer.push(value);
+    }
+
+    pub fn write(&mut self) -> String {
+        let values: Vec<IonValue> = self.current_buffer.drain(..).collect();
+
+        values
+            .iter()
+            .map(|value| self.write_value(value))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Writes only the provided value, ignoring the buffered ones.
+    pub fn write_value(&self, value: &IonValue) -> String {
+        let mut buffer = String::new();
+
+        self.write_value_into(value, WriterContext::Value, 0, &mut buffer);
+
+        buffer
+    }
+
+    // The depth is the nesting level of the value, used for the indentation.
+    fn write_value_into(
+        &self,
+        value: &IonValue,
+        context: WriterContext,
+        depth: usize,
+        buffer: &mut String,
+    ) {
+        match value {
+            IonValue::Null(value) => buffer.push_str(null_text(value)),
+            IonValue::Bool(value) => buffer.push_str(if *value { "true" } else { "false" }),
+            IonValue::Integer(value) => write!(buffer, "{}", value).unwrap(),
+            IonValue::BigInteger(value) => write!(buffer, "{}", value).unwrap(),
+            IonValue::Float(value) => write_float(*value, buffer),
+            IonValue::Decimal(value) => write_decimal(value, buffer),
+            IonValue::DateTime(value) => write_datetime(value, buffer),
+            IonValue::DateTimeUnknownOffset(value) => {
+                buffer.push_str(&format_unknown_offset(value))
+            }
+            IonValue::Timestamp(value) => buffer.push_str(&format_ion_timestamp(value)),
+            IonValue::String(value) => write_quoted(value, '"', buffer),
+            IonValue::Symbol(value) => write_symbol(value, context, buffer),
+            IonValue::Clob(value) => write_clob(value, buffer),
+            IonValue::Blob(value) => write_blob(value, buffer),
+            IonValue::List(values) => self.write_list(values, depth, buffer),
+            IonValue::SExpr(values) => self.write_sexp(values, depth, buffer),
+            IonValue::Struct(values) => {
+                let mut fields: Vec<(&Arc<str>, &IonValue)> = values.iter().collect();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
+                self.write_struct(fields, depth, buffer)
+            }
+            IonValue::OrderedStruct(values) => self.write_struct(
+                values.iter().map(|(key, value)| (key, value)).collect(),
+                depth,
+                buffer,
+            ),
+            IonValue::Annotation(annotations, value) => {
+                for annotation in annotations {
+                    write_symbol_text(annotation, WriterContext::Value, buffer);
+                    buffer.push_str("::");
+                }
+
+                self.write_value_into(value, context, depth, buffer);
+            }
+        }
+    }
+
+    fn write_list(&self, values: &[IonValue], depth: usize, buffer: &mut String) {
+        let list_format = match self.options.layout {
+            TextLayout::Default => self.options.list_format,
+            TextLayout::Compact => ListFormat::Compact,
+            TextLayout::Pretty => ListFormat::MultiLine,
+        };
+
+        match list_format {
+            ListFormat::Compact => self.write_sequence(values, ('[', ']'), ",", depth, buffer),
+            ListFormat::Spaced => self.write_sequence(values, ('[', ']'), ", ", depth, buffer),
+            ListFormat::MultiLine => {
+                write_multi_line(values, ('[', ']'), ",", depth, buffer, |value, buffer| {
+                    self.write_value_into(value, WriterContext::Value, depth + 1, buffer)
+                })
+            }
+        }
+    }
+
+    fn write_sexp(&self, values: &[IonValue], depth: usize, buffer: &mut String) {
+        match self.options.layout {
+            TextLayout::Pretty => {
+                write_multi_line(values, ('(', ')'), "", depth, buffer, |value, buffer| {
+                    self.write_value_into(value, WriterContext::SExpr, depth + 1, buffer)
+                })
+            }
+            _ => self.write_sequence(values, ('(', ')'), " ", depth, buffer),
+        }
+    }
+
+    fn write_sequence(
+        &self,
+        values: &[IonValue],
+        delimiters: (char, char),
+        separator: &str,
+        depth: usize,
+        buffer: &mut String,
+    ) {
+        let context = if delimiters.0 == '(' {
+            WriterContext::SExpr
+        } else {
+            WriterContext::Value
+        };
+
+        buffer.push(delimiters.0);
+
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                buffer.push_str(separator);
+            }
+
+            self.write_value_into(value, context, depth + 1, buffer);
+        }
+
+        buffer.push(delimiters.1);
+    }
+
+    fn write_struct(&self, fields: Vec<(&Arc<str>, &IonValue)>, depth: usize, buffer: &mut String) {
+        let write_field = |(key, value): &(&Arc<str>, &IonValue), buffer: &mut String| {
+            write_symbol_text(key, WriterContext::Value, buffer);
+
+            if self.options.layout == TextLayout::Compact {
+                buffer.push(':');
+            } else {
+                buffer.push_str(": ");
+            }
+
+            self.write_value_into(value, WriterContext::Value, depth + 1, buffer);
+        };
+
+        if self.options.layout == TextLayout::Pretty {
+            return write_multi_line(&fields, ('{', '}'), ",", depth, buffer, write_field);
+        }
+
+        let separator = if self.options.layout == TextLayout::Compact {
+            ","
+        } else {
+            ", "
+        };
+
+        buffer.push('{');
+
+        for (index, field) in fields.iter().enumerate() {
+            if index > 0 {
+                buffer.push_str(separator);
+            }
+
+            write_field(field, buffer);
+        }
+
+        buffer.push('}');
+    }
+}
+
+// One item per line, indented one level more than the container.
+fn write_multi_line<T>(
+    items: &[T],
+    delimiters: (char, char),
+    separator: &str,
+    depth: usize,
+    buffer: &mut String,
+    mut write_item: impl FnMut(&T, &mut String),
+) {
+    buffer.push(delimiters.0);
+
+    if items.is_empty() {
+        buffer.push(delimiters.1);
+        return;
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            buffer.push_str(separator);
+        }
+
+        write_new_line(depth + 1, buffer);
+        write_item(item, buffer);
+    }
+
+    write_new_line(depth, buffer);
+    buffer.push(delimiters.1);
+}
+
+fn write_new_line(depth: usize, buffer: &mut String) {
+    buffer.push('\n');
+
+    for _ in 0..depth {
+        buffer.push_str("  ");
+    }
+}
+
+fn null_text(value: &NullIonValue) -> &'static str {
+    match value {
+        NullIonValue::Null | NullIonValue::Annotation => "null",
+        NullIonValue::Bool => "null.bool",
+        NullIonValue::Integer => "null.int",
+        NullIonValue::Float => "null.float",
+        NullIonValue::Decimal => "null.decimal",
+        NullIonValue::DateTime => "null.timestamp",
+        NullIonValue::String => "null.string",
+        NullIonValue::Symbol => "null.symbol",
+        NullIonValue::Clob => "null.clob",
+        NullIonValue::Blob => "null.blob",
+        NullIonValue::List => "null.list",
+        NullIonValue::SExpr => "null.sexp",
+        NullIonValue::Struct => "null.struct",
+    }
+}
+
+// Ion text floats always need an exponent, otherwise they would be read as
+// decimals.
+fn write_float(value: f64, buffer: &mut String) {
+    if value.is_nan() {
+        buffer.push_str("nan");
+    } else if value.is_infinite() && value.is_sign_positive() {
+        buffer.push_str("+inf");
+    } else if value.is_infinite() {
+        buffer.push_str("-inf");
+    } else {
+        write!(buffer, "{:e}", value).unwrap();
+    }
+}
+
+// Canonical form built from the coefficient and the exponent, so `100d-2` is
+// not written as `1.00`. BigDecimal has no negative zero, so `-0.` is never
+// written.
+fn write_decimal(value: &BigDecimal, buffer: &mut String) {
+    let (coefficient, scale) = value.as_bigint_and_exponent();
+
+    if scale == 0 {
+        write!(buffer, "{}.", coefficient).unwrap();
+    } else {
+        write!(buffer, "{}d{}", coefficient, -scale).unwrap();
+    }
+}
+
+fn write_datetime(value: &DateTime<FixedOffset>, buffer: &mut String) {
+    buffer.push_str(&value.to_rfc3339_opts(SecondsFormat::AutoSi, false));
+}
+
+// Same as a UTC time, but with `-00:00` instead of `+00:00`.
+pub(crate) fn format_unknown_offset(value: &NaiveDateTime) -> String {
+    let text = Utc
+        .from_utc_datetime(value)
+        .to_rfc3339_opts(SecondsFormat::AutoSi, false);
+
+    format!("{}-00:00", text.trim_end_matches("+00:00"))
+}
+
+// Only the components up to the precision are written, in the local time of
+// the offset, like `2007-02-23T12:14Z` or `2007T`.
+pub(crate) fn format_ion_timestamp(value: &IonTimestamp) -> String {
+    let local = value.to_datetime();
+
+    let date = match value.precision() {
+        TimestampPrecision::Year => return format!("{:04}T", local.year()),
+        TimestampPrecision::Month => return format!("{:04}-{:02}T", local.year(), local.month()),
+        TimestampPrecision::Day => {
+            return format!(
+                "{:04}-{:02}-{:02}",
+                local.year(),
+                local.month(),
+                local.day()
+            )
+        }
+        _ => format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}",
+            local.year(),
+            local.month(),
+            local.day(),
+            local.hour(),
+            local.minute()
+        ),
+    };
+
+    let seconds = match value.precision() {
+        TimestampPrecision::Second => format!(":{:02}", local.second()),
+        TimestampPrecision::Fraction(digits) => format!(
+            ":{:02}.{:0width$}",
+            local.second(),
+            value.fraction_coefficient(digits),
+            width = digits as usize
+        ),
+        _ => String::new(),
+    };
+
+    let offset = match value.offset_minutes() {
+        None => "-00:00".to_string(),
+        Some(0) => "Z".to_string(),
+        Some(offset) => format!(
+            "{}{:02}:{:02}",
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 60,
+            offset.abs() % 60
+        ),
+    };
+
+    format!("{}{}{}", date, seconds, offset)
+}
+
+fn write_symbol(value: &SymbolToken, context: WriterContext, buffer: &mut String) {
+    match (value.text(), value.id) {
+        (Some(text), _) => write_symbol_text(text, context, buffer),
+        (None, Some(id)) => write!(buffer, "${}", id).unwrap(),
+        (None, None) => buffer.push_str("$0"),
+    }
+}
+
+fn write_symbol_text(text: &str, context: WriterContext, buffer: &mut String) {
+    let is_operator = !text.is_empty() && text.chars().all(|c| OPERATOR_CHARACTERS.contains(c));
+
+    if is_identifier(text) || (context == WriterContext::SExpr && is_operator) {
+        buffer.push_str(text);
+    } else {
+        write_quoted(text, '\'', buffer);
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    let starts_as_identifier = match chars.next() {
+        Some(c) => c.is_ascii_alphabetic() || c == '_' || c == '$',
+        None => false,
+    };
+
+    // Symbols like `$10` are read as symbol ids, not as text.
+    let is_symbol_id =
+        text.len() > 1 && text.starts_with('$') && text[1..].chars().all(|c| c.is_ascii_digit());
+
+    starts_as_identifier
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !is_symbol_id
+        && !KEYWORDS.contains(&text)
+}
+
+fn write_quoted(text: &str, quote: char, buffer: &mut String) {
+    buffer.push(quote);
+
+    for c in text.chars() {
+        match c {
+            '\\' => buffer.push_str("\\\\"),
+            '\n' => buffer.push_str("\\n"),
+            '\r' => buffer.push_str("\\r"),
+            '\t' => buffer.push_str("\\t"),
+            c if c == quote => {
+                buffer.push('\\');
+                buffer.push(c);
+            }
+            c if c.is_control() => write!(buffer, "\\x{:02x}", c as u32).unwrap(),
+            c => buffer.push(c),
+        }
+    }
+
+    buffer.push(quote);
+}
+
+fn write_clob(value: &[u8], buffer: &mut String) {
+    buffer.push_str("{{\"");
+
+    for byte in value {
+        match byte {
+            b'"' => buffer.push_str("\\\""),
+            b'\\' => buffer.push_str("\\\\"),
+            0x20..=0x7E => buffer.push(*byte as char),
+            _ => write!(buffer, "\\x{:02x}", byte).unwrap(),
+        }
+    }
+
+    buffer.push_str("\"}}");
+}
+
+fn write_blob(value: &[u8], buffer: &mut String) {
+    buffer.push_str("{{");
+
+    for chunk in value.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+
+        let indexes = [
+            bytes[0] >> 2,
+            ((bytes[0] & 0b0000_0011) << 4) | (bytes[1] >> 4),
+            ((bytes[1] & 0b0000_1111) << 2) | (bytes[2] >> 6),
+            bytes[2] & 0b0011_1111,
+        ];
+
+        for (position, index) in indexes.iter().enumerate() {
+            if position <= chunk.len() {
+                buffer.push(BASE64_ALPHABET[*index as usize] as char);
+            } else {
+                buffer.push('=');
+            }
+        }
+    }
+
+    buffer.push_str("}}");
+}
@@ -0,0 +1,157 @@
+use crate::IonParserError;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, Offset, TimeZone, Timelike, Utc};
+use std::convert::TryFrom;
+
+/// The most precise component of a timestamp. `Fraction` has the number of
+/// digits of the fractional seconds, from 1 to 9.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TimestampPrecision {
+    Year,
+    Month,
+    Day,
+    Minute,
+    Second,
+    Fraction(u32),
+}
+
+/// An Ion timestamp keeping its precision and offset, so `2007T`,
+/// `2007-01-01T00:00Z` and `2007-01-01T00:00:00.000Z` are different values
+/// that encode and hash back as they were. The parser only returns them when
+/// `IonParserConfig::preserve_timestamp_precision` is enabled.
+///
+/// The time is stored in UTC, as in Ion binary. The offset is in minutes and
+/// `None` is the unknown offset `-00:00`. The fractional seconds are kept
+/// up to nanoseconds, so longer fractions are truncated to 9 digits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IonTimestamp {
+    datetime: NaiveDateTime,
+    offset: Option<i32>,
+    precision: TimestampPrecision,
+}
+
+impl IonTimestamp {
+    /// Fails with `InvalidTimestampOffset` if the offset is not between
+    /// -23:59 and +23:59, and with `InvalidTimestampPrecision` if the time
+    /// has components more precise than the precision, like minutes in a
+    /// timestamp with day precision, or if the fraction doesn't have 1 to 9
+    /// digits.
+    pub fn new(
+        utc: NaiveDateTime,
+        offset: Option<i32>,
+        precision: TimestampPrecision,
+    ) -> Result<IonTimestamp, IonParserError> {
+        if let Some(offset) = offset {
+            if offset.abs() >= 24 * 60 {
+                return Err(IonParserError::InvalidTimestampOffset);
+            }
+        }
+
+        let truncated = truncate_to_precision(&utc, precision)
+            .ok_or(IonParserError::InvalidTimestampPrecision)?;
+
+        if truncated != utc {
+            return Err(IonParserError::InvalidTimestampPrecision);
+        }
+
+        Ok(IonTimestamp {
+            datetime: utc,
+            offset,
+            precision,
+        })
+    }
+
+    /// The time in UTC.
+    pub fn utc(&self) -> &NaiveDateTime {
+        &self.datetime
+    }
+
+    /// The offset in minutes, `None` when it is unknown.
+    pub fn offset_minutes(&self) -> Option<i32> {
+        self.offset
+    }
+
+    pub fn precision(&self) -> TimestampPrecision {
+        self.precision
+    }
+
+    /// The time in its offset, or in UTC when the offset is unknown.
+    pub fn to_datetime(&self) -> DateTime<FixedOffset> {
+        let offset =
+            FixedOffset::east_opt(self.offset.unwrap_or(0) * 60).unwrap_or_else(|| Utc.fix());
+
+        offset.from_utc_datetime(&self.datetime)
+    }
+
+    /// The fractional seconds with as many digits as the precision, like
+    /// `0.250`, or `None` if the precision is coarser than a fraction.
+    pub fn fractional_seconds(&self) -> Option<BigDecimal> {
+        match self.precision {
+            TimestampPrecision::Fraction(digits) => Some(BigDecimal::new(
+                self.fraction_coefficient(digits).into(),
+                digits.into(),
+            )),
+            _ => None,
+        }
+    }
+
+    // The fraction as an integer with `digits` digits.
+    pub(crate) fn fraction_coefficient(&self, digits: u32) -> u32 {
+        (self.datetime.nanosecond() % 1_000_000_000) / 10u32.pow(9 - digits)
+    }
+}
+
+/// Timestamps with the precision that doesn't lose data, like the encoder
+/// does for `IonValue::DateTime`.
+impl From<DateTime<FixedOffset>> for IonTimestamp {
+    fn from(value: DateTime<FixedOffset>) -> IonTimestamp {
+        let nanosecond = value.nanosecond() % 1_000_000_000;
+
+        let (_, digits) = BigDecimal::new(nanosecond.into(), 9)
+            .normalized()
+            .as_bigint_and_exponent();
+
+        let precision = match u32::try_from(digits) {
+            Ok(digits) if digits > 0 => TimestampPrecision::Fraction(digits),
+            _ => TimestampPrecision::Second,
+        };
+
+        IonTimestamp {
+            datetime: value.naive_utc(),
+            offset: Some(value.offset().local_minus_utc() / 60),
+            precision,
+        }
+    }
+}
+
+// Sets to the default every component after the precision, or returns
+// `None` if the precision is not valid.
+fn truncate_to_precision(
+    value: &NaiveDateTime,
+    precision: TimestampPrecision,
+) -> Option<NaiveDateTime> {
+    let date = value.date();
+    let time = value.time();
+
+    let (date, time) = match precision {
+        TimestampPrecision::Year => (date.with_month(1)?.with_day(1)?, (0, 0, 0, 0)),
+        TimestampPrecision::Month => (date.with_day(1)?, (0, 0, 0, 0)),
+        TimestampPrecision::Day => (date, (0, 0, 0, 0)),
+        TimestampPrecision::Minute => (date, (time.hour(), time.minute(), 0, 0)),
+        TimestampPrecision::Second => (date, (time.hour(), time.minute(), time.second(), 0)),
+        TimestampPrecision::Fraction(digits) if (1..=9).contains(&digits) => {
+            let unit = 10u32.pow(9 - digits);
+            let nanosecond = time.nanosecond() / unit * unit;
+
+            (
+                date,
+                (time.hour(), time.minute(), time.second(), nanosecond),
+            )
+        }
+        TimestampPrecision::Fraction(_) => return None,
+    };
+
+    let (hour, minute, second, nanosecond) = time;
+
+    date.and_hms_nano_opt(hour, minute, second, nanosecond)
+}
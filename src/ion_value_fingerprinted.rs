@@ -0,0 +1,72 @@
+use crate::IonValue;
+use std::cell::Cell;
+
+/// An `IonValue` that remembers its `structural_fingerprint`, so comparing
+/// it with other `FingerprintedIonValue` many times is fast: values with
+/// different fingerprints are told apart without looking at their content.
+/// The fingerprint is computed the first time it is needed. Values with the
+/// same fingerprint are still compared as usual, so the result is always the
+/// same as comparing the `IonValue`s.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{FingerprintedIonValue, IonValue};
+///
+/// let a = FingerprintedIonValue::new(IonValue::List(vec![IonValue::Integer(1)]));
+/// let b = FingerprintedIonValue::new(IonValue::List(vec![IonValue::Integer(2)]));
+///
+/// assert_ne!(a, b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FingerprintedIonValue {
+    value: IonValue,
+    fingerprint: Cell<Option<u64>>,
+}
+
+impl FingerprintedIonValue {
+    pub fn new(value: IonValue) -> FingerprintedIonValue {
+        FingerprintedIonValue {
+            value,
+            fingerprint: Cell::new(None),
+        }
+    }
+
+    pub fn value(&self) -> &IonValue {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> IonValue {
+        self.value
+    }
+
+    /// Same as `IonValue::structural_fingerprint`, but only computed once.
+    pub fn fingerprint(&self) -> u64 {
+        match self.fingerprint.get() {
+            Some(fingerprint) => fingerprint,
+            None => {
+                let fingerprint = self.value.structural_fingerprint();
+                self.fingerprint.set(Some(fingerprint));
+                fingerprint
+            }
+        }
+    }
+}
+
+impl From<IonValue> for FingerprintedIonValue {
+    fn from(value: IonValue) -> FingerprintedIonValue {
+        FingerprintedIonValue::new(value)
+    }
+}
+
+impl PartialEq for FingerprintedIonValue {
+    fn eq(&self, other: &FingerprintedIonValue) -> bool {
+        self.fingerprint() == other.fingerprint() && self.value == other.value
+    }
+}
+
+impl Eq for FingerprintedIonValue {}
+
+impl PartialEq<IonValue> for FingerprintedIonValue {
+    fn eq(&self, other: &IonValue) -> bool {
+        self.value == *other
+    }
+}
@@ -1,18 +1,838 @@
-use crate::{IonExtractionError, IonParserError, IonValue, NullIonValue, SerdeJsonParseError};
+use crate::ion_hash::constant_time_eq;
+use crate::ion_text_writer::format_unknown_offset;
+use crate::{
+    FlattenOptions, IndexNotation, IonExtractionError, IonParser, IonParserError, IonTextWriter,
+    IonTimestamp, IonValue, IonValueRc, NullIonValue, PathSegment, SerdeJsonParseError,
+    SymbolContext, SymbolToken, TimestampPrecision,
+};
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, Offset, TimeZone, Timelike, Utc};
 use num_bigint::{BigInt, BigUint};
-use std::collections::HashMap;
+use num_traits::Zero;
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::SystemTime;
 
 use serde_json::Value;
 use IonParserError::ValueExtractionFailure;
 
+impl IonValue {
+    /// Turns the value into an `IonValueRc`, where the containers are
+    /// reference counted and can be shared without copying them.
+    pub fn into_shared(self) -> IonValueRc {
+        IonValueRc::from(self)
+    }
+
+    /// Builds an `OrderedStruct` with the fields in the order given by the
+    /// iterator. Repeated keys are kept.
+    pub fn from_pairs<K: Into<Arc<str>>, I: IntoIterator<Item = (K, IonValue)>>(
+        pairs: I,
+    ) -> IonValue {
+        IonValue::OrderedStruct(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    /// Builds the decimal `coefficient * 10^exponent`, the same way it is
+    /// stored in the binary format. It fails with `DecimalExponentTooBig`
+    /// for an exponent of `i64::MIN`, as the decimal scale is its negation.
+    pub fn decimal_from_parts(
+        coefficient: BigInt,
+        exponent: i64,
+    ) -> Result<IonValue, IonParserError> {
+        let scale = exponent
+            .checked_neg()
+            .ok_or(IonParserError::DecimalExponentTooBig)?;
+
+        Ok(IonValue::Decimal(BigDecimal::new(coefficient, scale)))
+    }
+
+    /// Builds a symbol with the id that `text` has in `context`, or with only
+    /// the text when the context doesn't have it. An encoder created with the
+    /// same context (`IonEncoder::with_symbol_context`) writes the id without
+    /// looking the text up, and interns the symbols without id as usual.
+    pub fn symbol_in<S: Into<Arc<str>>>(text: S, context: &SymbolContext) -> IonValue {
+        let text = text.into();
+        let id = context.get_id_by_symbol(&text).map(|id| id as u64);
+
+        IonValue::Symbol(SymbolToken::new(Some(text), id))
+    }
+
+    /// Parses a binary Ion document that contains exactly one value. System
+    /// values (symbol tables, NOP padding) don't count as values. If
+    /// anything else follows the value it returns `TrailingData`.
+    pub fn from_binary(data: &[u8]) -> Result<IonValue, IonParserError> {
+        IonParser::new(data).parse_single()
+    }
+
+    /// Parses all the values of a binary Ion document.
+    pub fn from_binary_all(data: &[u8]) -> Result<Vec<IonValue>, IonParserError> {
+        IonParser::new(data).consume_all()
+    }
+
+    /// Gets the field `key` of the struct and transforms it into the requested
+    /// type. If the field is a null it returns `IonParserError::UnexpectedNull`,
+    /// use `get_opt_as` if the field is nullable.
+    ///
+    /// ```rust
+    /// use ion_binary_rs::IonValue;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut ion_struct = HashMap::new();
+    /// ion_struct.insert("Year".into(), IonValue::Integer(2019));
+    /// let ion_value = IonValue::Struct(ion_struct);
+    ///
+    /// let year: i64 = ion_value.get_as("Year").unwrap();
+    ///
+    /// assert_eq!(year, 2019);
+    /// ```
+    pub fn get_as<'a, T>(&'a self, key: &str) -> Result<T, IonParserError>
+    where
+        T: TryFrom<&'a IonValue, Error = IonParserError>,
+    {
+        T::try_from(self.get_field(key)?)
+    }
+
+    /// Same as `get_as` but a null field returns `Ok(None)`.
+    pub fn get_opt_as<'a, T>(&'a self, key: &str) -> Result<Option<T>, IonParserError>
+    where
+        T: TryFrom<&'a IonValue, Error = IonParserError>,
+    {
+        match self.get_field(key)? {
+            IonValue::Null(_) => Ok(None),
+            value => T::try_from(value).map(Some),
+        }
+    }
+
+    /// Creates an UTC timestamp from the seconds since the unix epoch.
+    /// It fails with `IonParserError::DateValueTooBig` if the date is out of
+    /// the range supported by chrono.
+    pub fn timestamp_from_unix_seconds(seconds: i64) -> Result<IonValue, IonParserError> {
+        IonValue::timestamp_from_unix_seconds_with_offset(seconds, Utc.fix())
+    }
+
+    /// Same as `timestamp_from_unix_seconds` but the timestamp uses the
+    /// provided offset. The instant is the same, only the local time changes.
+    pub fn timestamp_from_unix_seconds_with_offset(
+        seconds: i64,
+        offset: FixedOffset,
+    ) -> Result<IonValue, IonParserError> {
+        offset
+            .timestamp_opt(seconds, 0)
+            .single()
+            .map(IonValue::DateTime)
+            .ok_or(IonParserError::DateValueTooBig)
+    }
+
+    /// Creates an UTC timestamp with millisecond precision from the
+    /// milliseconds since the unix epoch.
+    pub fn timestamp_from_unix_millis(millis: i64) -> Result<IonValue, IonParserError> {
+        IonValue::timestamp_from_unix_millis_with_offset(millis, Utc.fix())
+    }
+
+    /// Same as `timestamp_from_unix_millis` but the timestamp uses the
+    /// provided offset.
+    pub fn timestamp_from_unix_millis_with_offset(
+        millis: i64,
+        offset: FixedOffset,
+    ) -> Result<IonValue, IonParserError> {
+        offset
+            .timestamp_millis_opt(millis)
+            .single()
+            .map(IonValue::DateTime)
+            .ok_or(IonParserError::DateValueTooBig)
+    }
+
+    /// Creates a timestamp with an unknown offset (`-00:00`), which means
+    /// that the time is known in UTC but not the local offset of it.
+    pub fn timestamp_with_unknown_offset(utc: NaiveDateTime) -> IonValue {
+        IonValue::DateTimeUnknownOffset(utc)
+    }
+
+    /// The accessors of the timestamp components, in the local time of its
+    /// offset, or in UTC when the offset is unknown. They return `None` when
+    /// the value is not a timestamp.
+    ///
+    /// A `Timestamp` returns `None` for the components past its precision,
+    /// so `2007T` only has the year and `2007-01-01T00:00Z` has up to the
+    /// minute. `DateTime` and `DateTimeUnknownOffset` don't keep the original
+    /// precision (check the note in the encoder and
+    /// `IonParserConfig::preserve_timestamp_precision`), so they always have
+    /// every component, with the defaults for the missing ones: January the
+    /// 1st at 00:00:00 and an offset of 0.
+    pub fn year(&self) -> Option<i32> {
+        self.timestamp_component(TimestampPrecision::Year, |value| value.year())
+    }
+
+    pub fn month(&self) -> Option<u32> {
+        self.timestamp_component(TimestampPrecision::Month, |value| value.month())
+    }
+
+    pub fn day(&self) -> Option<u32> {
+        self.timestamp_component(TimestampPrecision::Day, |value| value.day())
+    }
+
+    pub fn hour(&self) -> Option<u32> {
+        self.timestamp_component(TimestampPrecision::Minute, |value| value.hour())
+    }
+
+    pub fn minute(&self) -> Option<u32> {
+        self.timestamp_component(TimestampPrecision::Minute, |value| value.minute())
+    }
+
+    pub fn second(&self) -> Option<u32> {
+        self.timestamp_component(TimestampPrecision::Second, |value| value.second())
+    }
+
+    /// The fraction of the second, like `0.25`. Like the encoder, it
+    /// assumes the precision that doesn't lose data, so it is `None` when
+    /// there is no fraction.
+    /// A `Timestamp` returns its fraction with the digits of its precision.
+    pub fn fractional_seconds(&self) -> Option<BigDecimal> {
+        if let IonValue::Timestamp(value) = self {
+            return value.fractional_seconds();
+        }
+
+        // Leap seconds are represented with more than 1_000_000_000 nanos.
+        let nanosecond = self.as_datetime()?.nanosecond() % 1_000_000_000;
+
+        if nanosecond == 0 {
+            return None;
+        }
+
+        Some(BigDecimal::new(nanosecond.into(), 9).normalized())
+    }
+
+    /// It also returns `None` when the offset is unknown.
+    pub fn offset_minutes(&self) -> Option<i32> {
+        match self {
+            IonValue::DateTime(value) => Some(value.offset().local_minus_utc() / 60),
+            IonValue::Timestamp(value) => value.offset_minutes(),
+            _ => None,
+        }
+    }
+
+    /// Writes the timestamp as RFC 3339, like `2019-03-14T15:09:26.25+01:00`.
+    /// It returns `None` when the value is not a timestamp.
+    ///
+    /// RFC 3339 needs every component up to the seconds, while Ion allows
+    /// less precision and an unknown offset. A `Timestamp` less precise than
+    /// seconds, like `2007T`, returns `None` (`to_ion_text` writes it). The
+    /// other timestamps have every component (check the timestamp
+    /// accessors), so they never fail because of the precision.
+    /// The unknown offset is written as `-00:00`, as RFC 3339 does.
+    pub fn to_rfc3339(&self) -> Option<String> {
+        match self {
+            IonValue::DateTime(value) => Some(value.to_rfc3339()),
+            IonValue::DateTimeUnknownOffset(value) => Some(format_unknown_offset(value)),
+            IonValue::Timestamp(value) if value.precision() < TimestampPrecision::Second => None,
+            IonValue::Timestamp(value) => match value.offset_minutes() {
+                Some(_) => Some(value.to_datetime().to_rfc3339()),
+                None => Some(format_unknown_offset(value.utc())),
+            },
+            _ => None,
+        }
+    }
+
+    /// Writes the timestamp as Ion text, the same way `IonTextWriter` does.
+    /// It returns `None` when the value is not a timestamp.
+    pub fn to_ion_text(&self) -> Option<String> {
+        self.as_datetime().map(|_| self.to_string())
+    }
+
+    fn timestamp_component<C>(
+        &self,
+        precision: TimestampPrecision,
+        component: impl FnOnce(DateTime<FixedOffset>) -> C,
+    ) -> Option<C> {
+        match self {
+            IonValue::Timestamp(value) if value.precision() < precision => None,
+            _ => self.as_datetime().map(component),
+        }
+    }
+
+    fn as_datetime(&self) -> Option<DateTime<FixedOffset>> {
+        match self {
+            IonValue::DateTime(value) => Some(*value),
+            IonValue::DateTimeUnknownOffset(value) => Some(Utc.from_utc_datetime(value).into()),
+            IonValue::Timestamp(value) => Some(value.to_datetime()),
+            _ => None,
+        }
+    }
+
+    /// Gets the field `key` of the struct ignoring the case of the field
+    /// names. It goes through all the fields, so it is O(n). When several
+    /// fields match, an exact match wins, otherwise the first one found is
+    /// returned (for `Struct` there is no specific order). It returns `None`
+    /// if there is no match or the value is not a struct.
+    pub fn get_ci(&self, key: &str) -> Option<&IonValue> {
+        let matches = |name: &str| {
+            name.chars()
+                .flat_map(char::to_lowercase)
+                .eq(key.chars().flat_map(char::to_lowercase))
+        };
+
+        match self {
+            IonValue::Struct(values) => values.get(key).or_else(|| {
+                values
+                    .iter()
+                    .find(|(name, _)| matches(name))
+                    .map(|(_, value)| value)
+            }),
+            IonValue::OrderedStruct(values) => values
+                .iter()
+                .find(|(name, _)| &**name == key)
+                .or_else(|| values.iter().find(|(name, _)| matches(name)))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Follows the path through structs, lists and sexps and returns the
+    /// value at the end of it. An empty path returns the value itself. It
+    /// returns `None` if any segment is missing or doesn't match the type of
+    /// the value.
+    pub fn get_path(&self, path: &[PathSegment]) -> Option<&IonValue> {
+        path.iter()
+            .try_fold(self, |value, segment| match (segment, value) {
+                (PathSegment::Field(key), value) => value.get_field(key).ok(),
+                (PathSegment::Index(index), IonValue::List(values))
+                | (PathSegment::Index(index), IonValue::SExpr(values)) => values.get(*index),
+                _ => None,
+            })
+    }
+
+    /// Same as `get_path` but the path is written like `owner.items[0].name`:
+    /// field names separated by dots and list indexes between brackets.
+    /// Field names with dots, brackets or quotes can be quoted with `"` or
+    /// `'`, like `owner."first.name"`, escaping the quote with a backslash.
+    /// An empty string returns the value itself. It returns `None` if the
+    /// path is not valid or the value is not found.
+    pub fn select(&self, path: &str) -> Option<&IonValue> {
+        self.get_path(&parse_path_expression(path)?)
+    }
+
+    /// Flattens the value into a map from the path of each scalar to the
+    /// scalar, like `owner.name` or `items.0`, which is useful for indexing
+    /// documents. Check `flatten_with` for the details.
+    pub fn flatten(&self) -> HashMap<String, IonValue> {
+        self.flatten_with(&FlattenOptions::default())
+    }
+
+    /// Flattens the value with the provided separator and index notation.
+    /// Structs, lists and sexps are traversed and everything else,
+    /// including nulls, is a scalar. Empty containers don't add anything.
+    /// Annotations are dropped. When an ordered struct repeats a field, the
+    /// first one wins. A scalar on its own is returned with an empty path.
+    pub fn flatten_with(&self, options: &FlattenOptions) -> HashMap<String, IonValue> {
+        let mut result = HashMap::new();
+
+        self.flatten_into(String::new(), options, &mut result);
+
+        result
+    }
+
+    fn flatten_into(
+        &self,
+        path: String,
+        options: &FlattenOptions,
+        result: &mut HashMap<String, IonValue>,
+    ) {
+        let join = |segment: &str| {
+            if path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}{}{}", path, options.separator, segment)
+            }
+        };
+
+        match self {
+            IonValue::Struct(values) => {
+                for (key, value) in values {
+                    value.flatten_into(join(key), options, result);
+                }
+            }
+            IonValue::OrderedStruct(values) => {
+                let mut seen = HashSet::new();
+
+                for (key, value) in values {
+                    if seen.insert(key) {
+                        value.flatten_into(join(key), options, result);
+                    }
+                }
+            }
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    let path = match options.index_notation {
+                        IndexNotation::Segment => join(&index.to_string()),
+                        IndexNotation::Brackets => format!("{}[{}]", path, index),
+                    };
+
+                    value.flatten_into(path, options, result);
+                }
+            }
+            IonValue::Annotation(_, value) => value.flatten_into(path, options, result),
+            value => {
+                result.entry(path).or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    /// Same as `==`, but blobs, clobs and strings are compared in a time that
+    /// doesn't depend on their content, for values holding secrets like
+    /// keys or hashes. Only the length can be told apart. Any other type is
+    /// compared with `==`.
+    pub fn ct_eq(&self, other: &IonValue) -> bool {
+        match (self, other) {
+            (IonValue::Blob(a), IonValue::Blob(b)) | (IonValue::Clob(a), IonValue::Clob(b)) => {
+                constant_time_eq(a, b)
+            }
+            (IonValue::String(a), IonValue::String(b)) => {
+                constant_time_eq(a.as_bytes(), b.as_bytes())
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// A fast, non cryptographic, hash of the value, useful to quickly tell
+    /// apart values before comparing them. Equal values have the same
+    /// fingerprint and different ones very likely don't. It is meant to be
+    /// used in memory, so it may change between versions of the library.
+    /// Check `IonHash` for a hash that can be stored or shared.
+    pub fn structural_fingerprint(&self) -> u64 {
+        let mut hasher = FnvHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the text of strings and symbols, borrowed when possible.
+    /// Symbols without text return their id as `$10`, like in Ion text,
+    /// and `$0` if they don't have an id either. It returns `None` for any
+    /// other type.
+    pub fn text(&self) -> Option<Cow<'_, str>> {
+        match self {
+            IonValue::String(text) => Some(Cow::Borrowed(text)),
+            IonValue::Symbol(symbol) => match (symbol.text(), symbol.id) {
+                (Some(text), _) => Some(Cow::Borrowed(text)),
+                (None, Some(id)) => Some(Cow::Owned(format!("${}", id))),
+                (None, None) => Some(Cow::Borrowed("$0")),
+            },
+            _ => None,
+        }
+    }
+
+    /// Splits a tagged value, like `variant_a::{...}`, into its only
+    /// annotation and the annotated value. It returns `None` if the value
+    /// isn't annotated or if it has more than one annotation.
+    pub fn as_tagged(&self) -> Option<(&str, &IonValue)> {
+        match self {
+            IonValue::Annotation(annotations, value) => match (&annotations[..], &**value) {
+                (_, IonValue::Annotation(_, _)) => None,
+                ([tag], value) => Some((tag, value)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns a copy where the struct fields follow the order of the fields
+    /// in `template`, which is handy to compare with JSON fixtures. Fields
+    /// missing in the template go at the end, in their original order, or
+    /// sorted by key for (unordered) structs. Nested structs, lists, sexps
+    /// and annotations are reordered with the matching part of the
+    /// template. Structs are returned as `OrderedStruct`, as they are the
+    /// only ones that keep an order, so take the template from an
+    /// `OrderedStruct` too.
+    pub fn reorder_to_match(&self, template: &IonValue) -> IonValue {
+        self.reorder_with(Some(template))
+    }
+
+    fn reorder_with(&self, template: Option<&IonValue>) -> IonValue {
+        let template = match template {
+            Some(IonValue::Annotation(_, template)) => Some(&**template),
+            template => template,
+        };
+
+        match self {
+            IonValue::Struct(values) => {
+                let mut fields: Vec<_> = values.iter().collect();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
+
+                IonValue::OrderedStruct(IonValue::reorder_fields(fields, template))
+            }
+            IonValue::OrderedStruct(values) => {
+                let fields = values.iter().map(|(key, value)| (key, value)).collect();
+
+                IonValue::OrderedStruct(IonValue::reorder_fields(fields, template))
+            }
+            IonValue::List(values) | IonValue::SExpr(values) => {
+                let template_values = match template {
+                    Some(IonValue::List(values)) | Some(IonValue::SExpr(values)) => &values[..],
+                    _ => &[],
+                };
+
+                let values = values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| value.reorder_with(template_values.get(index)))
+                    .collect();
+
+                if let IonValue::List(_) = self {
+                    IonValue::List(values)
+                } else {
+                    IonValue::SExpr(values)
+                }
+            }
+            IonValue::Annotation(annotations, value) => {
+                IonValue::Annotation(annotations.clone(), Box::new(value.reorder_with(template)))
+            }
+            value => value.clone(),
+        }
+    }
+
+    fn reorder_fields(
+        mut fields: Vec<(&Arc<str>, &IonValue)>,
+        template: Option<&IonValue>,
+    ) -> Vec<(Arc<str>, IonValue)> {
+        let template_fields: Vec<(&Arc<str>, &IonValue)> = match template {
+            Some(IonValue::Struct(values)) => values.iter().collect(),
+            Some(IonValue::OrderedStruct(values)) => {
+                values.iter().map(|(key, value)| (key, value)).collect()
+            }
+            _ => vec![],
+        };
+
+        let mut result = Vec::with_capacity(fields.len());
+
+        for (template_key, template_value) in template_fields {
+            let (matching, rest): (Vec<_>, Vec<_>) = fields
+                .into_iter()
+                .partition(|(key, _)| *key == template_key);
+
+            fields = rest;
+
+            for (key, value) in matching {
+                result.push((key.clone(), value.reorder_with(Some(template_value))));
+            }
+        }
+
+        for (key, value) in fields {
+            result.push((key.clone(), value.reorder_with(None)));
+        }
+
+        result
+    }
+
+    /// Returns the fields of the struct sorted by key, useful for stable
+    /// output or comparisons. Duplicated keys, only possible in ordered
+    /// structs, keep the first value. It returns `None` if the value is not a
+    /// struct.
+    pub fn as_sorted_map(&self) -> Option<BTreeMap<Arc<str>, &IonValue>> {
+        match self {
+            IonValue::Struct(values) => Some(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value))
+                    .collect(),
+            ),
+            IonValue::OrderedStruct(values) => {
+                let mut map = BTreeMap::new();
+
+                for (key, value) in values {
+                    map.entry(key.clone()).or_insert(value);
+                }
+
+                Some(map)
+            }
+            _ => None,
+        }
+    }
+
+    /// Merges the fields of the overlay struct into this struct, replacing
+    /// the fields that are in both. If any of them is not a struct, the
+    /// result is the overlay. Ordered structs keep their order, with the new
+    /// fields at the end.
+    pub fn merge(&self, overlay: &IonValue) -> IonValue {
+        self.merge_fields(overlay, false)
+    }
+
+    /// Same as `merge` but the fields that are structs in both values are
+    /// merged recursively instead of replaced. Any other overlay value,
+    /// lists included, replaces the base value wholesale.
+    pub fn merge_deep(&self, overlay: &IonValue) -> IonValue {
+        self.merge_fields(overlay, true)
+    }
+
+    fn merge_fields(&self, overlay: &IonValue, deep: bool) -> IonValue {
+        let overlay_fields: Vec<(&Arc<str>, &IonValue)> = match overlay {
+            IonValue::Struct(values) => values.iter().collect(),
+            IonValue::OrderedStruct(values) => values.iter().map(|(k, v)| (k, v)).collect(),
+            _ => return overlay.clone(),
+        };
+
+        let merge_value = |base: Option<&IonValue>, value: &IonValue| match base {
+            Some(base) if deep => base.merge_fields(value, true),
+            _ => value.clone(),
+        };
+
+        match self {
+            IonValue::Struct(values) => {
+                let mut result = values.clone();
+
+                for (key, value) in overlay_fields {
+                    let merged = merge_value(result.get(key), value);
+                    result.insert(key.clone(), merged);
+                }
+
+                IonValue::Struct(result)
+            }
+            IonValue::OrderedStruct(values) => {
+                let mut result = values.clone();
+
+                for (key, value) in overlay_fields {
+                    match result.iter_mut().find(|(name, _)| name == key) {
+                        Some(field) => field.1 = merge_value(Some(&field.1), value),
+                        None => result.push((key.clone(), value.clone())),
+                    }
+                }
+
+                IonValue::OrderedStruct(result)
+            }
+            _ => overlay.clone(),
+        }
+    }
+
+    pub(crate) fn get_field(&self, key: &str) -> Result<&IonValue, IonParserError> {
+        match self {
+            IonValue::Struct(values) => values
+                .get(key)
+                .ok_or_else(|| IonParserError::FieldNotFound(key.to_string())),
+            IonValue::OrderedStruct(values) => values
+                .iter()
+                .find(|(name, _)| &**name == key)
+                .map(|(_, value)| value)
+                .ok_or_else(|| IonParserError::FieldNotFound(key.to_string())),
+            _ => Err(ValueExtractionFailure(
+                IonExtractionError::TypeNotSupported(self.clone()),
+            )),
+        }
+    }
+}
+
+/// Amount of bytes of blobs, clobs and strings shown by `Debug` when no
+/// precision is given in the format string.
+const DEBUG_DEFAULT_PREFIX_LEN: usize = 64;
+
+/// Works like a derived `Debug` except that blobs, clobs and strings longer
+/// than the prefix are truncated and finish with `...(N bytes)`, so huge
+/// values don't flood the logs. The prefix is 64 bytes by default and it can
+/// be changed with the precision of the format string, e.g. `{:.16?}`.
+///
+/// ```rust
+/// use ion_binary_rs::IonValue;
+///
+/// let value = IonValue::Blob(vec![0; 1000]);
+///
+/// assert_eq!(format!("{:.2?}", value), "Blob([0, 0, ...(1000 bytes)])");
+/// ```
+impl fmt::Debug for IonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix_len = f.precision().unwrap_or(DEBUG_DEFAULT_PREFIX_LEN);
+
+        match self {
+            IonValue::Null(value) => f.debug_tuple("Null").field(value).finish(),
+            IonValue::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
+            IonValue::Integer(value) => f.debug_tuple("Integer").field(value).finish(),
+            IonValue::BigInteger(value) => f.debug_tuple("BigInteger").field(value).finish(),
+            // The precision is meant for the prefix, not for the float digits.
+            IonValue::Float(value) => f
+                .debug_tuple("Float")
+                .field(&format_args!("{:?}", value))
+                .finish(),
+            IonValue::Decimal(value) => f.debug_tuple("Decimal").field(value).finish(),
+            IonValue::DateTime(value) => f.debug_tuple("DateTime").field(value).finish(),
+            IonValue::DateTimeUnknownOffset(value) => {
+                f.debug_tuple("DateTimeUnknownOffset").field(value).finish()
+            }
+            IonValue::Timestamp(value) => f.debug_tuple("Timestamp").field(value).finish(),
+            IonValue::String(value) => f
+                .debug_tuple("String")
+                .field(&TruncatedStr(value, prefix_len))
+                .finish(),
+            IonValue::Symbol(value) => f.debug_tuple("Symbol").field(value).finish(),
+            IonValue::Clob(value) => f
+                .debug_tuple("Clob")
+                .field(&TruncatedBytes(value, prefix_len))
+                .finish(),
+            IonValue::Blob(value) => f
+                .debug_tuple("Blob")
+                .field(&TruncatedBytes(value, prefix_len))
+                .finish(),
+            IonValue::List(values) => f.debug_tuple("List").field(values).finish(),
+            IonValue::SExpr(values) => f.debug_tuple("SExpr").field(values).finish(),
+            IonValue::Struct(values) => f.debug_tuple("Struct").field(values).finish(),
+            IonValue::OrderedStruct(values) => {
+                f.debug_tuple("OrderedStruct").field(values).finish()
+            }
+            IonValue::Annotation(annotations, value) => f
+                .debug_tuple("Annotation")
+                .field(annotations)
+                .field(value)
+                .finish(),
+        }
+    }
+}
+
+/// Writes the value in the Ion text format, check `IonTextWriter`.
+impl fmt::Display for IonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&IonTextWriter::new().write_value(self))
+    }
+}
+
+/// Equal values have the same hash, so IonValue can be used as a key of a
+/// HashMap. This has nothing to do with Ion Hash, check `IonHash` for it.
+impl Hash for IonValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            IonValue::Null(value) => value.hash(state),
+            IonValue::Bool(value) => value.hash(state),
+            IonValue::Integer(value) => value.hash(state),
+            IonValue::BigInteger(value) => value.hash(state),
+            // 0.0 and -0.0 are equal. NaN is never equal, so it doesn't matter.
+            IonValue::Float(value) if *value == 0.0 => 0.0f64.to_bits().hash(state),
+            IonValue::Float(value) => value.to_bits().hash(state),
+            IonValue::Decimal(value) => hash_decimal(value, state),
+            IonValue::DateTime(value) => value.hash(state),
+            IonValue::DateTimeUnknownOffset(value) => value.hash(state),
+            IonValue::Timestamp(value) => value.hash(state),
+            IonValue::String(value) => value.hash(state),
+            IonValue::Symbol(value) => value.hash(state),
+            IonValue::Clob(value) | IonValue::Blob(value) => value.hash(state),
+            IonValue::List(values) | IonValue::SExpr(values) => values.hash(state),
+            // The fields have no order, so their hashes are added up.
+            IonValue::Struct(values) => {
+                let fields_hash = values.iter().fold(0u64, |fields_hash, field| {
+                    let mut hasher = DefaultHasher::new();
+                    field.hash(&mut hasher);
+                    fields_hash.wrapping_add(hasher.finish())
+                });
+
+                values.len().hash(state);
+                fields_hash.hash(state);
+            }
+            IonValue::OrderedStruct(values) => values.hash(state),
+            IonValue::Annotation(annotations, value) => {
+                annotations.hash(state);
+                value.hash(state);
+            }
+        }
+    }
+}
+
+// BigDecimal hashes its digits with a zero for each unit of a negative scale,
+// so `1d9223372036854775807` would never finish. The coefficient without
+// trailing zeros and the exponent give the same hash to equal decimals.
+fn hash_decimal<H: Hasher>(value: &BigDecimal, state: &mut H) {
+    let (mut coefficient, scale) = value.as_bigint_and_exponent();
+
+    if coefficient.is_zero() {
+        return 0u8.hash(state);
+    }
+
+    let mut scale = i128::from(scale);
+
+    while (&coefficient % 10u32).is_zero() {
+        coefficient /= 10u32;
+        scale -= 1;
+    }
+
+    coefficient.hash(state);
+    scale.hash(state);
+}
+
+// FNV-1a, a simple and fast hash for the structural fingerprint.
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+struct TruncatedBytes<'a>(&'a [u8], usize);
+
+impl fmt::Debug for TruncatedBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let TruncatedBytes(bytes, prefix_len) = *self;
+
+        if bytes.len() <= prefix_len {
+            return f.debug_list().entries(bytes).finish();
+        }
+
+        f.debug_list()
+            .entries(&bytes[..prefix_len])
+            .entry(&format_args!("...({} bytes)", bytes.len()))
+            .finish()
+    }
+}
+
+struct TruncatedStr<'a>(&'a str, usize);
+
+impl fmt::Debug for TruncatedStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let TruncatedStr(text, prefix_len) = *self;
+
+        if text.len() <= prefix_len {
+            return write!(f, "{:?}", text);
+        }
+
+        let mut end = prefix_len;
+
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        write!(f, "{:?}...({} bytes)", &text[..end], text.len())
+    }
+}
+
 impl TryFrom<IonValue> for std::collections::HashMap<String, IonValue> {
     type Error = IonParserError;
     fn try_from(value: IonValue) -> Result<Self, Self::Error> {
         match value {
-            IonValue::Struct(value) => Ok(value),
+            IonValue::Struct(value) => Ok(value
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect()),
+            IonValue::OrderedStruct(value) => Ok(value
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect()),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -25,7 +845,11 @@ impl TryFrom<IonValue> for String {
 
     fn try_from(value: IonValue) -> Result<Self, IonParserError> {
         match value {
-            IonValue::String(value) | IonValue::Symbol(value) => Ok(value),
+            IonValue::String(value) => Ok(value),
+            IonValue::Symbol(SymbolToken {
+                text: Some(value), ..
+            }) => Ok(value.to_string()),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -44,6 +868,7 @@ impl TryFrom<IonValue> for u64 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -60,6 +885,7 @@ impl TryFrom<IonValue> for i64 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -78,6 +904,7 @@ impl TryFrom<IonValue> for u32 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -96,6 +923,7 @@ impl TryFrom<IonValue> for i32 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -114,6 +942,7 @@ impl TryFrom<IonValue> for BigUint {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -128,6 +957,7 @@ impl TryFrom<IonValue> for BigInt {
         match value {
             IonValue::Integer(value) => Ok(BigInt::from(value)),
             IonValue::BigInteger(value) => Ok(value),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -143,6 +973,7 @@ impl TryFrom<IonValue> for BigDecimal {
             IonValue::Decimal(value) => Ok(value),
             IonValue::Integer(value) => Ok(BigDecimal::from(value)),
             IonValue::BigInteger(value) => Ok(BigDecimal::from(value)),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -164,6 +995,7 @@ impl TryFrom<IonValue> for f64 {
                 })
                 .map(|value| value as f64),
             IonValue::Float(value) => Ok(value),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -185,6 +1017,7 @@ impl TryFrom<IonValue> for f32 {
                 })
                 .map(|value| value as f32),
             IonValue::Float(value) => Ok(value as f32),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -198,6 +1031,9 @@ impl TryFrom<IonValue> for DateTime<Utc> {
     fn try_from(value: IonValue) -> Result<Self, IonParserError> {
         match value {
             IonValue::DateTime(value) => Ok(value.with_timezone(&Utc)),
+            IonValue::DateTimeUnknownOffset(value) => Ok(Utc.from_utc_datetime(&value)),
+            IonValue::Timestamp(value) => Ok(Utc.from_utc_datetime(value.utc())),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -211,6 +1047,12 @@ impl TryFrom<IonValue> for DateTime<FixedOffset> {
     fn try_from(value: IonValue) -> Result<Self, IonParserError> {
         match value {
             IonValue::DateTime(value) => Ok(value),
+            IonValue::DateTimeUnknownOffset(_) => Err(IonParserError::TimestampWithUnknownOffset),
+            IonValue::Timestamp(value) => match value.offset_minutes() {
+                Some(_) => Ok(value.to_datetime()),
+                None => Err(IonParserError::TimestampWithUnknownOffset),
+            },
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -218,12 +1060,27 @@ impl TryFrom<IonValue> for DateTime<FixedOffset> {
     }
 }
 
+/// Timestamps before 1970-01-01T00:00:00Z fail with
+/// `TimestampBeforeUnixEpoch`, and a `Timestamp` less precise than seconds
+/// (like a date) fails with `TimestampWithoutSeconds`. `DateTime` doesn't
+/// keep the precision, so a date parsed without
+/// `IonParserConfig::preserve_timestamp_precision` gives the start of the
+/// day.
+impl TryFrom<IonValue> for SystemTime {
+    type Error = IonParserError;
+
+    fn try_from(value: IonValue) -> Result<Self, IonParserError> {
+        SystemTime::try_from(&value)
+    }
+}
+
 impl TryFrom<IonValue> for bool {
     type Error = IonParserError;
 
     fn try_from(value: IonValue) -> Result<Self, IonParserError> {
         match value {
             IonValue::Bool(value) => Ok(value),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -238,6 +1095,7 @@ impl TryFrom<IonValue> for Vec<u8> {
         match value {
             IonValue::Clob(value) => Ok(value),
             IonValue::Blob(value) => Ok(value),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null)),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value),
             )),
@@ -286,6 +1144,14 @@ impl TryFrom<IonValue> for serde_json::Value {
                 }
                 Ok(Value::Object(result_map))
             }
+            IonValue::OrderedStruct(values) => {
+                let mut result_map = serde_json::Map::with_capacity(values.len());
+
+                for (key, ion_value) in values {
+                    result_map.insert(key.to_string(), Value::try_from(ion_value)?);
+                }
+                Ok(Value::Object(result_map))
+            }
             _ => Err(IonParserError::TypeNotSupported(value)),
         }
     }
@@ -298,6 +1164,7 @@ impl TryFrom<&IonValue> for Vec<IonValue> {
         match value {
             IonValue::List(value) => Ok(value.to_vec()),
             IonValue::SExpr(value) => Ok(value.to_vec()),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -308,10 +1175,16 @@ impl TryFrom<&IonValue> for Vec<IonValue> {
 impl TryFrom<&IonValue> for HashMap<String, IonValue> {
     type Error = ();
     fn try_from(value: &IonValue) -> Result<Self, Self::Error> {
-        if let IonValue::Struct(value) = value {
-            Ok(value.clone())
-        } else {
-            Err(())
+        match value {
+            IonValue::Struct(value) => Ok(value
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect()),
+            IonValue::OrderedStruct(value) => Ok(value
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect()),
+            _ => Err(()),
         }
     }
 }
@@ -321,7 +1194,11 @@ impl TryFrom<&IonValue> for String {
 
     fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
         match value {
-            IonValue::String(value) | IonValue::Symbol(value) => Ok(value.clone()),
+            IonValue::String(value) => Ok(value.clone()),
+            IonValue::Symbol(SymbolToken {
+                text: Some(value), ..
+            }) => Ok(value.to_string()),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -340,6 +1217,7 @@ impl TryFrom<&IonValue> for u64 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -356,6 +1234,7 @@ impl TryFrom<&IonValue> for i64 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -374,6 +1253,7 @@ impl TryFrom<&IonValue> for u32 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -392,6 +1272,7 @@ impl TryFrom<&IonValue> for i32 {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -410,6 +1291,7 @@ impl TryFrom<&IonValue> for BigUint {
             IonValue::BigInteger(value) => value.try_into().map_err(|e| {
                 ValueExtractionFailure(IonExtractionError::NumericTransformationError(Box::new(e)))
             }),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -424,6 +1306,7 @@ impl TryFrom<&IonValue> for BigInt {
         match value {
             IonValue::Integer(value) => Ok(BigInt::from(*value)),
             IonValue::BigInteger(value) => Ok(value.clone()),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -445,6 +1328,7 @@ impl TryFrom<&IonValue> for f64 {
                 })
                 .map(|value| value as f64),
             IonValue::Float(value) => Ok(*value),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -466,6 +1350,7 @@ impl TryFrom<&IonValue> for f32 {
                 })
                 .map(|value| value as f32),
             IonValue::Float(value) => Ok(*value as f32),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -479,6 +1364,9 @@ impl TryFrom<&IonValue> for DateTime<Utc> {
     fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
         match value {
             IonValue::DateTime(value) => Ok(value.with_timezone(&Utc)),
+            IonValue::DateTimeUnknownOffset(value) => Ok(Utc.from_utc_datetime(value)),
+            IonValue::Timestamp(value) => Ok(Utc.from_utc_datetime(value.utc())),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -492,6 +1380,12 @@ impl TryFrom<&IonValue> for DateTime<FixedOffset> {
     fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
         match value {
             IonValue::DateTime(value) => Ok(*value),
+            IonValue::DateTimeUnknownOffset(_) => Err(IonParserError::TimestampWithUnknownOffset),
+            IonValue::Timestamp(value) => match value.offset_minutes() {
+                Some(_) => Ok(value.to_datetime()),
+                None => Err(IonParserError::TimestampWithUnknownOffset),
+            },
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -499,12 +1393,34 @@ impl TryFrom<&IonValue> for DateTime<FixedOffset> {
     }
 }
 
+/// Same as `TryFrom<IonValue> for SystemTime`.
+impl TryFrom<&IonValue> for SystemTime {
+    type Error = IonParserError;
+
+    fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
+        if let IonValue::Timestamp(timestamp) = value {
+            if timestamp.precision() < TimestampPrecision::Second {
+                return Err(IonParserError::TimestampWithoutSeconds);
+            }
+        }
+
+        let datetime = DateTime::<Utc>::try_from(value)?;
+
+        if datetime.timestamp() < 0 {
+            return Err(IonParserError::TimestampBeforeUnixEpoch);
+        }
+
+        Ok(SystemTime::from(datetime))
+    }
+}
+
 impl TryFrom<&IonValue> for bool {
     type Error = IonParserError;
 
     fn try_from(value: &IonValue) -> Result<Self, IonParserError> {
         match value {
             IonValue::Bool(value) => Ok(*value),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -519,6 +1435,7 @@ impl TryFrom<&IonValue> for Vec<u8> {
         match value {
             IonValue::Clob(value) => Ok(value.to_vec()),
             IonValue::Blob(value) => Ok(value.to_vec()),
+            IonValue::Null(null) => Err(IonParserError::UnexpectedNull(null.clone())),
             _ => Err(ValueExtractionFailure(
                 IonExtractionError::TypeNotSupported(value.clone()),
             )),
@@ -621,6 +1538,18 @@ impl From<DateTime<Utc>> for IonValue {
     }
 }
 
+impl From<SystemTime> for IonValue {
+    fn from(value: SystemTime) -> IonValue {
+        IonValue::from(DateTime::<Utc>::from(value))
+    }
+}
+
+impl From<IonTimestamp> for IonValue {
+    fn from(value: IonTimestamp) -> IonValue {
+        IonValue::Timestamp(value)
+    }
+}
+
 impl From<bool> for IonValue {
     fn from(value: bool) -> IonValue {
         IonValue::Bool(value)
@@ -681,9 +1610,9 @@ impl<I: Into<IonValue> + Clone> From<&[I]> for IonValue {
     }
 }
 
-impl<I: Into<IonValue>, K: Into<String>> From<HashMap<K, I>> for IonValue {
+impl<I: Into<IonValue>, K: Into<Arc<str>>> From<HashMap<K, I>> for IonValue {
     fn from(values: HashMap<K, I>) -> Self {
-        let mut vec: HashMap<String, IonValue> = HashMap::new();
+        let mut vec: HashMap<Arc<str>, IonValue> = HashMap::new();
 
         for (key, value) in values.into_iter() {
             vec.insert(key.into(), value.into());
@@ -748,3 +1677,81 @@ impl TryFrom<serde_json::Value> for IonValue {
         }
     }
 }
+
+// Parses the path expressions of `IonValue::select`.
+fn parse_path_expression(path: &str) -> Option<Vec<PathSegment>> {
+    let mut segments = vec![];
+    let mut chars = path.chars().peekable();
+
+    if path.is_empty() {
+        return Some(segments);
+    }
+
+    loop {
+        let segment = match *chars.peek()? {
+            '[' => {
+                chars.next();
+
+                let mut digits = String::new();
+
+                loop {
+                    match chars.next()? {
+                        ']' => break,
+                        c if c.is_ascii_digit() => digits.push(c),
+                        _ => return None,
+                    }
+                }
+
+                PathSegment::Index(digits.parse().ok()?)
+            }
+            '"' | '\'' => {
+                let quote = chars.next()?;
+
+                let mut key = String::new();
+
+                loop {
+                    match chars.next()? {
+                        '\\' => key.push(chars.next()?),
+                        c if c == quote => break,
+                        c => key.push(c),
+                    }
+                }
+
+                PathSegment::Field(key)
+            }
+            _ => {
+                let mut key = String::new();
+
+                while let Some(&c) = chars.peek() {
+                    if c == '.' || c == '[' {
+                        break;
+                    }
+
+                    key.push(c);
+                    chars.next();
+                }
+
+                if key.is_empty() {
+                    return None;
+                }
+
+                PathSegment::Field(key)
+            }
+        };
+
+        segments.push(segment);
+
+        match chars.peek() {
+            None => return Some(segments),
+            Some('.') => {
+                chars.next();
+
+                if let Some('[') = chars.peek() {
+                    return None;
+                }
+            }
+            Some('[') => {}
+            Some(_) => return None,
+        }
+    }
+}
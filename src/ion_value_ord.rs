@@ -0,0 +1,222 @@
+use crate::{IonParserError, IonValue, TimestampPrecision};
+use chrono::NaiveDateTime;
+use num_bigint::BigInt;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// An `IonValue` with a total order, so it can be used as a `BTreeMap` key.
+/// Values are ordered first by type, in the order of the Ion type codes
+/// (null, bool, int, float, decimal, timestamp, symbol, string, clob, blob,
+/// list, sexp, struct), and then by value:
+///
+/// - Integers and BigIntegers, decimals and timestamps are compared
+///   numerically, so `1.0` and `1.00` are equal, as well as the same instant
+///   with different offsets. `IonValue` doesn't keep the precision of
+///   decimals, so it isn't used.
+/// - A timestamp with an unknown offset goes right before the same instant
+///   with a known offset. Then the timestamps without precision (all but
+///   `Timestamp`) go first, followed by the coarser precisions.
+/// - Symbols are compared by text, and symbols without text by id.
+/// - Lists and sexps are compared element by element.
+/// - Structs are compared by their fields sorted by key, so the field order
+///   of an OrderedStruct doesn't matter.
+/// - An annotated value goes right after the same value without
+///   annotations, ordered by the annotations.
+///
+/// As NaN can't be ordered, values containing a NaN float are rejected.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonValue, OrdIonValue};
+/// use std::collections::BTreeMap;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert(OrdIonValue::new(IonValue::Integer(2)).unwrap(), "two");
+/// map.insert(OrdIonValue::new(IonValue::Bool(true)).unwrap(), "true");
+///
+/// assert_eq!(map.values().collect::<Vec<_>>(), vec![&"true", &"two"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrdIonValue(IonValue);
+
+impl OrdIonValue {
+    /// Wraps the value, returning `IonParserError::NanNotOrderable` if it
+    /// has a NaN float anywhere.
+    pub fn new(value: IonValue) -> Result<OrdIonValue, IonParserError> {
+        if has_nan(&value) {
+            return Err(IonParserError::NanNotOrderable);
+        }
+
+        Ok(OrdIonValue(value))
+    }
+
+    pub fn value(&self) -> &IonValue {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> IonValue {
+        self.0
+    }
+}
+
+impl PartialEq for OrdIonValue {
+    fn eq(&self, other: &OrdIonValue) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrdIonValue {}
+
+impl PartialOrd for OrdIonValue {
+    fn partial_cmp(&self, other: &OrdIonValue) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdIonValue {
+    fn cmp(&self, other: &OrdIonValue) -> Ordering {
+        compare(&self.0, &other.0)
+    }
+}
+
+fn has_nan(value: &IonValue) -> bool {
+    match value {
+        IonValue::Float(value) => value.is_nan(),
+        IonValue::List(values) | IonValue::SExpr(values) => values.iter().any(has_nan),
+        IonValue::Struct(values) => values.values().any(has_nan),
+        IonValue::OrderedStruct(values) => values.iter().any(|(_, value)| has_nan(value)),
+        IonValue::Annotation(_, value) => has_nan(value),
+        _ => false,
+    }
+}
+
+fn compare(a: &IonValue, b: &IonValue) -> Ordering {
+    let (a_annotations, a) = split_annotations(a);
+    let (b_annotations, b) = split_annotations(b);
+
+    compare_values(a, b).then_with(|| a_annotations.cmp(&b_annotations))
+}
+
+// Nested annotations are joined, as they are the same as one annotation
+// with all of them.
+fn split_annotations(value: &IonValue) -> (Vec<&Arc<str>>, &IonValue) {
+    let mut annotations = vec![];
+    let mut value = value;
+
+    while let IonValue::Annotation(value_annotations, annotated) = value {
+        annotations.extend(value_annotations);
+        value = annotated;
+    }
+
+    (annotations, value)
+}
+
+fn compare_values(a: &IonValue, b: &IonValue) -> Ordering {
+    match (a, b) {
+        (IonValue::Null(a), IonValue::Null(b)) => a.cmp(b),
+        (IonValue::Bool(a), IonValue::Bool(b)) => a.cmp(b),
+        (IonValue::Integer(a), IonValue::Integer(b)) => a.cmp(b),
+        (IonValue::BigInteger(a), IonValue::BigInteger(b)) => a.cmp(b),
+        (IonValue::Integer(a), IonValue::BigInteger(b)) => BigInt::from(*a).cmp(b),
+        (IonValue::BigInteger(a), IonValue::Integer(b)) => a.cmp(&BigInt::from(*b)),
+        // NaN is rejected when the OrdIonValue is created.
+        (IonValue::Float(a), IonValue::Float(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (IonValue::Decimal(a), IonValue::Decimal(b)) => a.cmp(b),
+        (a, b) if type_rank(a) == TIMESTAMP_RANK && type_rank(b) == TIMESTAMP_RANK => {
+            timestamp_key(a).cmp(&timestamp_key(b))
+        }
+        (IonValue::Symbol(a), IonValue::Symbol(b)) => {
+            a.text()
+                .cmp(&b.text())
+                .then_with(|| match (a.text(), b.text()) {
+                    (None, None) => a.id.cmp(&b.id),
+                    _ => Ordering::Equal,
+                })
+        }
+        (IonValue::String(a), IonValue::String(b)) => a.cmp(b),
+        (IonValue::Clob(a), IonValue::Clob(b)) => a.cmp(b),
+        (IonValue::Blob(a), IonValue::Blob(b)) => a.cmp(b),
+        (IonValue::List(a), IonValue::List(b)) | (IonValue::SExpr(a), IonValue::SExpr(b)) => {
+            compare_elements(a, b)
+        }
+        (a, b) if type_rank(a) == STRUCT_RANK && type_rank(b) == STRUCT_RANK => {
+            compare_fields(&sorted_fields(a), &sorted_fields(b))
+        }
+        (a, b) => type_rank(a).cmp(&type_rank(b)),
+    }
+}
+
+fn compare_elements(a: &[IonValue], b: &[IonValue]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        match compare(a, b) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+    }
+
+    a.len().cmp(&b.len())
+}
+
+fn compare_fields(a: &[(&Arc<str>, &IonValue)], b: &[(&Arc<str>, &IonValue)]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        match compare_field(a, b) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+    }
+
+    a.len().cmp(&b.len())
+}
+
+fn compare_field(a: &(&Arc<str>, &IonValue), b: &(&Arc<str>, &IonValue)) -> Ordering {
+    a.0.cmp(b.0).then_with(|| compare(a.1, b.1))
+}
+
+fn sorted_fields(value: &IonValue) -> Vec<(&Arc<str>, &IonValue)> {
+    let mut fields: Vec<(&Arc<str>, &IonValue)> = match value {
+        IonValue::Struct(values) => values.iter().collect(),
+        IonValue::OrderedStruct(values) => values.iter().map(|(k, v)| (k, v)).collect(),
+        _ => vec![],
+    };
+
+    fields.sort_by(compare_field);
+
+    fields
+}
+
+// The instant, if the offset is known and the precision, if it was kept.
+fn timestamp_key(value: &IonValue) -> (NaiveDateTime, bool, Option<TimestampPrecision>) {
+    match value {
+        IonValue::DateTime(value) => (value.naive_utc(), true, None),
+        IonValue::DateTimeUnknownOffset(value) => (*value, false, None),
+        IonValue::Timestamp(value) => (
+            *value.utc(),
+            value.offset_minutes().is_some(),
+            Some(value.precision()),
+        ),
+        _ => (NaiveDateTime::MIN, false, None),
+    }
+}
+
+const TIMESTAMP_RANK: u8 = 5;
+const STRUCT_RANK: u8 = 12;
+
+fn type_rank(value: &IonValue) -> u8 {
+    match value {
+        IonValue::Null(_) => 0,
+        IonValue::Bool(_) => 1,
+        IonValue::Integer(_) | IonValue::BigInteger(_) => 2,
+        IonValue::Float(_) => 3,
+        IonValue::Decimal(_) => 4,
+        IonValue::DateTime(_) | IonValue::DateTimeUnknownOffset(_) | IonValue::Timestamp(_) => {
+            TIMESTAMP_RANK
+        }
+        IonValue::Symbol(_) => 6,
+        IonValue::String(_) => 7,
+        IonValue::Clob(_) => 8,
+        IonValue::Blob(_) => 9,
+        IonValue::List(_) => 10,
+        IonValue::SExpr(_) => 11,
+        IonValue::Struct(_) | IonValue::OrderedStruct(_) => STRUCT_RANK,
+        IonValue::Annotation(_, value) => type_rank(value),
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{IonTimestamp, IonValue, NullIonValue, PathSegment, SymbolToken};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Same as IonValue but the containers are behind an `Rc`, so cloning a
+/// value, or sharing a subtree between several parents, doesn't copy the
+/// elements. Useful for read heavy workloads over a parsed document. It is
+/// created with `IonValue::into_shared` and it can be turned back with
+/// `to_ion_value`.
+///
+/// ```rust,no_run
+/// use ion_binary_rs::{IonValue, IonValueRc};
+///
+/// let list = IonValue::List(vec![IonValue::Integer(1)]).into_shared();
+/// let copy = list.clone();
+///
+/// if let (IonValueRc::List(a), IonValueRc::List(b)) = (&list, &copy) {
+///     assert!(std::rc::Rc::ptr_eq(a, b));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum IonValueRc {
+    Null(NullIonValue),
+    Bool(bool),
+    Integer(i64),
+    BigInteger(BigInt),
+    Float(f64),
+    Decimal(BigDecimal),
+    DateTime(DateTime<FixedOffset>),
+    DateTimeUnknownOffset(NaiveDateTime),
+    Timestamp(IonTimestamp),
+    String(String),
+    Symbol(SymbolToken),
+    Clob(Vec<u8>),
+    Blob(Vec<u8>),
+    List(Rc<Vec<IonValueRc>>),
+    SExpr(Rc<Vec<IonValueRc>>),
+    Struct(Rc<HashMap<Arc<str>, IonValueRc>>),
+    OrderedStruct(Rc<Vec<(Arc<str>, IonValueRc)>>),
+    Annotation(Vec<Arc<str>>, Rc<IonValueRc>),
+}
+
+impl IonValueRc {
+    /// Gets the field `key` of the struct. Ordered structs return the first
+    /// field with that name. It returns `None` if there is no field or the
+    /// value is not a struct.
+    pub fn get(&self, key: &str) -> Option<&IonValueRc> {
+        match self {
+            IonValueRc::Struct(values) => values.get(key),
+            IonValueRc::OrderedStruct(values) => values
+                .iter()
+                .find(|(name, _)| &**name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Works like `IonValue::get_path`.
+    pub fn get_path(&self, path: &[PathSegment]) -> Option<&IonValueRc> {
+        path.iter()
+            .try_fold(self, |value, segment| match (segment, value) {
+                (PathSegment::Field(key), value) => value.get(key),
+                (PathSegment::Index(index), IonValueRc::List(values))
+                | (PathSegment::Index(index), IonValueRc::SExpr(values)) => values.get(*index),
+                _ => None,
+            })
+    }
+
+    /// Builds back an IonValue, copying the whole tree.
+    pub fn to_ion_value(&self) -> IonValue {
+        match self {
+            IonValueRc::Null(value) => IonValue::Null(value.clone()),
+            IonValueRc::Bool(value) => IonValue::Bool(*value),
+            IonValueRc::Integer(value) => IonValue::Integer(*value),
+            IonValueRc::BigInteger(value) => IonValue::BigInteger(value.clone()),
+            IonValueRc::Float(value) => IonValue::Float(*value),
+            IonValueRc::Decimal(value) => IonValue::Decimal(value.clone()),
+            IonValueRc::DateTime(value) => IonValue::DateTime(*value),
+            IonValueRc::DateTimeUnknownOffset(value) => IonValue::DateTimeUnknownOffset(*value),
+            IonValueRc::Timestamp(value) => IonValue::Timestamp(value.clone()),
+            IonValueRc::String(value) => IonValue::String(value.clone()),
+            IonValueRc::Symbol(value) => IonValue::Symbol(value.clone()),
+            IonValueRc::Clob(value) => IonValue::Clob(value.clone()),
+            IonValueRc::Blob(value) => IonValue::Blob(value.clone()),
+            IonValueRc::List(values) => {
+                IonValue::List(values.iter().map(IonValueRc::to_ion_value).collect())
+            }
+            IonValueRc::SExpr(values) => {
+                IonValue::SExpr(values.iter().map(IonValueRc::to_ion_value).collect())
+            }
+            IonValueRc::Struct(values) => IonValue::Struct(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_ion_value()))
+                    .collect(),
+            ),
+            IonValueRc::OrderedStruct(values) => IonValue::OrderedStruct(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_ion_value()))
+                    .collect(),
+            ),
+            IonValueRc::Annotation(annotations, value) => {
+                IonValue::Annotation(annotations.clone(), Box::new(value.to_ion_value()))
+            }
+        }
+    }
+}
+
+impl From<IonValue> for IonValueRc {
+    fn from(value: IonValue) -> IonValueRc {
+        match value {
+            IonValue::Null(value) => IonValueRc::Null(value),
+            IonValue::Bool(value) => IonValueRc::Bool(value),
+            IonValue::Integer(value) => IonValueRc::Integer(value),
+            IonValue::BigInteger(value) => IonValueRc::BigInteger(value),
+            IonValue::Float(value) => IonValueRc::Float(value),
+            IonValue::Decimal(value) => IonValueRc::Decimal(value),
+            IonValue::DateTime(value) => IonValueRc::DateTime(value),
+            IonValue::DateTimeUnknownOffset(value) => IonValueRc::DateTimeUnknownOffset(value),
+            IonValue::Timestamp(value) => IonValueRc::Timestamp(value),
+            IonValue::String(value) => IonValueRc::String(value),
+            IonValue::Symbol(value) => IonValueRc::Symbol(value),
+            IonValue::Clob(value) => IonValueRc::Clob(value),
+            IonValue::Blob(value) => IonValueRc::Blob(value),
+            IonValue::List(values) => {
+                IonValueRc::List(Rc::new(values.into_iter().map(IonValueRc::from).collect()))
+            }
+            IonValue::SExpr(values) => {
+                IonValueRc::SExpr(Rc::new(values.into_iter().map(IonValueRc::from).collect()))
+            }
+            IonValue::Struct(values) => IonValueRc::Struct(Rc::new(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, IonValueRc::from(value)))
+                    .collect(),
+            )),
+            IonValue::OrderedStruct(values) => IonValueRc::OrderedStruct(Rc::new(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, IonValueRc::from(value)))
+                    .collect(),
+            )),
+            IonValue::Annotation(annotations, value) => {
+                IonValueRc::Annotation(annotations, Rc::new(IonValueRc::from(*value)))
+            }
+        }
+    }
+}
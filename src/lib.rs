@@ -14,8 +14,8 @@
 //!
 //! First of all, you need to be aware of the trade offs that we took for this library:
 //!
-//! - The API returns strings instead of Symbols. If needed we can add symbol, but we
-//! think string is the the most ergonomic way.
+//! - The API returns shared strings (`Arc<str>`) for struct keys and annotations, as we think
+//! it is the most ergonomic way that avoids copying the symbol text. Symbol values are returned as a `SymbolToken` with their text and id.
 //! - When parsing/decoding you can add shared tables for binary blobs that doesn't have
 //! all the required symbols.
 //!
@@ -50,15 +50,15 @@
 //!
 //! let mut ion_struct = HashMap::new();
 //!
-//! ion_struct.insert("Model".to_string(), IonValue::String("CLK 350".to_string()));
-//! ion_struct.insert("Type".to_string(), IonValue::String("Sedan".to_string()));
-//! ion_struct.insert("Color".to_string(), IonValue::String("White".to_string()));
+//! ion_struct.insert("Model".into(), IonValue::String("CLK 350".to_string()));
+//! ion_struct.insert("Type".into(), IonValue::String("Sedan".to_string()));
+//! ion_struct.insert("Color".into(), IonValue::String("White".to_string()));
 //! ion_struct.insert(
-//!     "VIN".to_string(),
+//!     "VIN".into(),
 //!     IonValue::String("1C4RJFAG0FC625797".to_string()),
 //! );
-//! ion_struct.insert("Make".to_string(), IonValue::String("Mercedes".to_string()));
-//! ion_struct.insert("Year".to_string(), IonValue::Integer(2019));
+//! ion_struct.insert("Make".into(), IonValue::String("Mercedes".to_string()));
+//! ion_struct.insert("Year".into(), IonValue::Integer(2019));
 //!
 //! let ion_value = IonValue::Struct(ion_struct);
 //!
@@ -81,15 +81,15 @@
 //!
 //! let mut ion_struct = HashMap::new();
 //!
-//! ion_struct.insert("Model".to_string(), IonValue::String("CLK 350".to_string()));
-//! ion_struct.insert("Type".to_string(), IonValue::String("Sedan".to_string()));
-//! ion_struct.insert("Color".to_string(), IonValue::String("White".to_string()));
+//! ion_struct.insert("Model".into(), IonValue::String("CLK 350".to_string()));
+//! ion_struct.insert("Type".into(), IonValue::String("Sedan".to_string()));
+//! ion_struct.insert("Color".into(), IonValue::String("White".to_string()));
 //! ion_struct.insert(
-//!     "VIN".to_string(),
+//!     "VIN".into(),
 //!     IonValue::String("1C4RJFAG0FC625797".to_string()),
 //! );
-//! ion_struct.insert("Make".to_string(), IonValue::String("Mercedes".to_string()));
-//! ion_struct.insert("Year".to_string(), IonValue::Integer(2019));
+//! ion_struct.insert("Make".into(), IonValue::String("Mercedes".to_string()));
+//! ion_struct.insert("Year".into(), IonValue::Integer(2019));
 //!
 //! let ion_value = IonValue::Struct(ion_struct);
 //!
@@ -130,22 +130,54 @@
 pub(crate) mod binary_encoder;
 pub(crate) mod binary_parser;
 pub(crate) mod binary_parser_types;
+pub(crate) mod ion_deserializer;
 pub(crate) mod ion_encoder;
 pub(crate) mod ion_hash;
 pub(crate) mod ion_hash_encoder;
+pub(crate) mod ion_incremental_parser;
 pub(crate) mod ion_parser;
 pub(crate) mod ion_parser_types;
+pub(crate) mod ion_patch;
+pub(crate) mod ion_reader;
+pub(crate) mod ion_schema;
+pub(crate) mod ion_serializer;
+pub(crate) mod ion_struct_builder;
+pub(crate) mod ion_text_parser;
+pub(crate) mod ion_text_writer;
+pub(crate) mod ion_timestamp;
+pub(crate) mod ion_value_fingerprinted;
 pub(crate) mod ion_value_impl;
+pub(crate) mod ion_value_ord;
+pub(crate) mod ion_value_rc;
 pub(crate) mod symbol_table;
 
 #[cfg(test)]
 mod tests;
 
-pub use binary_parser_types::ParsingError;
-pub use ion_encoder::IonEncoder;
-pub use ion_hash::IonHash;
-pub use ion_parser::IonParser;
+pub use binary_parser_types::{ParsingError, ValueLength, ValueType};
+pub use ion_deserializer::{from_reader, from_value, Annotated};
+pub use ion_encoder::{EncodeMode, EncodeSymbolOrder, IonEncoder};
+pub use ion_hash::{DocumentHasher, IonHash};
+pub use ion_incremental_parser::IncrementalIonParser;
+pub use ion_parser::{
+    AnnotationCallback, IonParser, IonParserConfig, ListCursor, ParseStats, StructCursor,
+    SymbolTableCallback, Values, DEFAULT_MAX_DECIMAL_DIGITS,
+};
 pub use ion_parser_types::{
-    IonExtractionError, IonParserError, IonValue, NullIonValue, SerdeJsonParseError,
+    FlattenOptions, IndexNotation, IonExtractionError, IonParserError, IonValue, IonVersion,
+    NullIonValue, PathSegment, SerdeJsonParseError,
+};
+pub use ion_patch::IonPatch;
+pub use ion_reader::{read, IonReader};
+pub use ion_schema::{
+    infer_schema, IonSchema, SchemaField, SchemaNode, SchemaType, ValidationError,
 };
-pub use symbol_table::{Symbol, SymbolContextError};
+pub use ion_serializer::{to_value, to_vec, to_writer};
+pub use ion_struct_builder::StructBuilder;
+pub use ion_text_parser::IonTextParser;
+pub use ion_text_writer::{IonTextWriter, IonTextWriterOptions, ListFormat, TextLayout};
+pub use ion_timestamp::{IonTimestamp, TimestampPrecision};
+pub use ion_value_fingerprinted::FingerprintedIonValue;
+pub use ion_value_ord::OrdIonValue;
+pub use ion_value_rc::IonValueRc;
+pub use symbol_table::{Symbol, SymbolContext, SymbolContextError, SymbolToken};
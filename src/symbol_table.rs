@@ -1,16 +1,88 @@
 use crate::binary_parser_types::SYSTEM_SYMBOL_TABLE;
 use log::trace;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 /// A table symbol. It can b used together with the "with_shared_table" method
 /// in order to define new shared tables.
+///
+/// The text is shared with the symbols read by the parser, so reading a
+/// symbol many times doesn't allocate its text every time.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Symbol {
-    Symbol(String),
+    Symbol(Arc<str>),
     Dummy,
 }
 
-#[derive(Eq, PartialEq, Debug)]
+/// A symbol value. It contains the text of the symbol, the id of the symbol
+/// in the symbol table, or both. Symbols read by the parser have both, while
+/// symbols created by the user usually only have the text. The encoder will
+/// assign an id to them.
+///
+/// Two symbols with text are equal if their text is equal, regardless of the
+/// ids. Symbols without text are compared by id.
+///
+/// The parser shares the text with the `Symbol` of the symbol table instead
+/// of copying it.
+#[derive(Debug, Clone, Eq)]
+pub struct SymbolToken {
+    pub text: Option<Arc<str>>,
+    pub id: Option<u64>,
+}
+
+impl SymbolToken {
+    pub fn new(text: Option<Arc<str>>, id: Option<u64>) -> SymbolToken {
+        SymbolToken { text, id }
+    }
+
+    /// Creates a symbol with the given text and no id.
+    pub fn from_text<S: Into<Arc<str>>>(text: S) -> SymbolToken {
+        SymbolToken::new(Some(text.into()), None)
+    }
+
+    /// Creates a symbol with unknown text.
+    pub fn from_id(id: u64) -> SymbolToken {
+        SymbolToken::new(None, Some(id))
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+}
+
+impl PartialEq for SymbolToken {
+    fn eq(&self, other: &SymbolToken) -> bool {
+        match (&self.text, &other.text) {
+            (Some(text), Some(other_text)) => text == other_text,
+            _ => self.text == other.text && self.id == other.id,
+        }
+    }
+}
+
+// Consistent with `PartialEq`: the id only counts when there is no text.
+impl Hash for SymbolToken {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &self.text {
+            Some(text) => text.hash(state),
+            None => self.id.hash(state),
+        }
+    }
+}
+
+impl From<String> for SymbolToken {
+    fn from(text: String) -> SymbolToken {
+        SymbolToken::from_text(text)
+    }
+}
+
+impl From<&str> for SymbolToken {
+    fn from(text: &str) -> SymbolToken {
+        SymbolToken::from_text(text)
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub struct LocalSymbolTable(Vec<Symbol>);
 
 impl LocalSymbolTable {
@@ -19,7 +91,7 @@ impl LocalSymbolTable {
             SYSTEM_SYMBOL_TABLE
                 .to_vec()
                 .iter()
-                .map(|s| Symbol::Symbol(s.to_string()))
+                .map(|s| Symbol::Symbol(Arc::from(*s)))
                 .collect(),
         )
     }
@@ -43,7 +115,7 @@ impl LocalSymbolTable {
     pub fn get_id_by_symbol(&self, symbol: &str) -> Option<usize> {
         match self.0.iter().enumerate().find(|(_, value)| {
             if let Symbol::Symbol(value) = value {
-                *value == symbol
+                **value == *symbol
             } else {
                 false
             }
@@ -64,7 +136,7 @@ impl LocalSymbolTable {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SharedSymbolTable {
     name: String,
     version: u32,
@@ -98,7 +170,7 @@ impl SharedSymbolTable {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Import {
     pub(crate) name: String,
     pub(crate) version: Option<u32>,
@@ -115,23 +187,35 @@ pub enum SymbolContextError {
     NewTableIsNotSuperSetOfPrevious,
 }
 
-#[derive(Debug)]
+// The shared tables by name, with their latest version and every version.
+type SharedTables = HashMap<String, (u32, HashMap<u32, SharedSymbolTable>)>;
+
+/// The symbols known at a given point of an ion stream: the current local
+/// table plus the shared tables that can be imported. It can be used in order
+/// to encode symbols against an already known table with
+/// `IonEncoder::with_symbol_context`.
+///
+/// The tables are shared between clones and only copied when a clone changes
+/// them, so cloning a context is cheap.
+#[derive(Debug, Clone)]
 pub struct SymbolContext {
-    current_table: LocalSymbolTable,
-    shared_tables: HashMap<String, (u32, HashMap<u32, SharedSymbolTable>)>,
+    current_table: Arc<LocalSymbolTable>,
+    shared_tables: Arc<SharedTables>,
 }
 
 impl SymbolContext {
     pub fn new() -> SymbolContext {
         SymbolContext {
-            current_table: LocalSymbolTable::new(),
-            shared_tables: HashMap::new(),
+            current_table: Arc::new(LocalSymbolTable::new()),
+            shared_tables: Arc::new(HashMap::new()),
         }
     }
 
-    pub fn set_new_table_from_current(&mut self, symbols: Vec<Symbol>) {
+    pub(crate) fn set_new_table_from_current(&mut self, symbols: Vec<Symbol>) {
+        let current_table = Arc::make_mut(&mut self.current_table);
+
         for symbol in symbols.into_iter() {
-            self.current_table.add_symbol(symbol);
+            current_table.add_symbol(symbol);
         }
     }
 
@@ -147,7 +231,9 @@ impl SymbolContext {
             symbols: symbols.to_vec(),
         };
 
-        match self.shared_tables.get_mut(&name) {
+        let shared_tables = Arc::make_mut(&mut self.shared_tables);
+
+        match shared_tables.get_mut(&name) {
             Some(table_collection) => match table_collection.1.get_mut(&version) {
                 Some(_) => Err(SymbolContextError::TableVersionAlreadyThere),
                 None => {
@@ -173,32 +259,35 @@ impl SymbolContext {
                 let mut new_hashmap = HashMap::new();
                 new_hashmap.insert(version, new_table);
                 let new_tuple = (version, new_hashmap);
-                self.shared_tables.insert(name, new_tuple);
+                shared_tables.insert(name, new_tuple);
 
                 Ok(())
             }
         }
     }
 
-    pub fn assert_new_table_is_superset(
+    pub(crate) fn assert_new_table_is_superset(
         table: &SharedSymbolTable,
         version: &u32,
         tables: &HashMap<u32, SharedSymbolTable>,
     ) -> Result<(), SymbolContextError> {
-        for index in (*version - 1)..=0 {
-            if let Some(existing_table) = tables.get(&index) {
-                if !table.is_superset(existing_table) {
-                    return Err(SymbolContextError::NewTableIsNotSuperSetOfPrevious);
-                } else {
-                    return Ok(());
-                }
+        // Compared against the closest previous version. The version comes
+        // from the stream, so it can't be used to iterate.
+        let previous = tables
+            .keys()
+            .filter(|existing| *existing < version)
+            .max()
+            .and_then(|existing| tables.get(existing));
+
+        match previous {
+            Some(existing_table) if !table.is_superset(existing_table) => {
+                Err(SymbolContextError::NewTableIsNotSuperSetOfPrevious)
             }
+            _ => Ok(()),
         }
-
-        Ok(())
     }
 
-    pub fn set_new_table(
+    pub(crate) fn set_new_table(
         &mut self,
         imports: &[Import],
         symbols: &[Symbol],
@@ -264,7 +353,7 @@ impl SymbolContext {
             new_table
         );
 
-        self.current_table = new_table;
+        self.current_table = Arc::new(new_table);
 
         Ok(())
     }
@@ -273,20 +362,34 @@ impl SymbolContext {
         self.current_table.get_symbol_by_id(id)
     }
 
-    pub fn insert_symbol(&mut self, symbol: &str) -> usize {
+    pub fn get_id_by_symbol(&self, symbol: &str) -> Option<usize> {
+        self.current_table.get_id_by_symbol(symbol)
+    }
+
+    pub(crate) fn insert_symbol(&mut self, symbol: &str) -> usize {
         match self.current_table.get_id_by_symbol(symbol) {
             Some(id) => id,
-            None => self
-                .current_table
-                .add_symbol(Symbol::Symbol(symbol.to_string())),
+            None => {
+                Arc::make_mut(&mut self.current_table).add_symbol(Symbol::Symbol(Arc::from(symbol)))
+            }
         }
     }
 
-    pub fn dump_all_local_symbols(&self) -> Vec<String> {
+    // Drops the symbols after the first `len` ones of the current table.
+    pub(crate) fn truncate_current_table(&mut self, len: usize) {
+        Arc::make_mut(&mut self.current_table).0.truncate(len);
+    }
+
+    // Number of symbols of the current table without the system ones.
+    pub(crate) fn local_symbols_len(&self) -> usize {
+        self.current_table.0.len() - SYSTEM_SYMBOL_TABLE.len()
+    }
+
+    pub(crate) fn dump_all_local_symbols(&self) -> Vec<String> {
         self.current_table.list_all_symbols()[10..]
             .iter()
             .map(|s| match s {
-                Symbol::Symbol(name) => name.clone(),
+                Symbol::Symbol(name) => name.to_string(),
                 _ => "".to_string(),
             })
             .collect()
@@ -109,7 +109,7 @@ fn list_with_value_larger_than_size() {
     let ion_element = read_file_testsuite!("bad/listWithValueLargerThanSize");
     let mut parser = IonParser::new(ion_element);
     let value = parser.consume_value().unwrap_err();
-    let expected = IonParserError::ListLengthWasTooShort;
+    let expected = IonParserError::BinaryError(ParsingError::ChildExceedsParent);
     assert_eq!(expected, value);
 }
 
@@ -165,7 +165,7 @@ fn negative_int_zero() {
     let ion_element = read_file_testsuite!("bad/negativeIntZero");
     let mut parser = IonParser::new(ion_element);
     let value = parser.consume_value().unwrap_err();
-    let expected = IonParserError::InvalidNegativeInt;
+    let expected = IonParserError::BinaryError(ParsingError::NegativeZeroInt);
     assert_eq!(expected, value);
 }
 
@@ -174,7 +174,7 @@ fn negative_int_zero_ln() {
     let ion_element = read_file_testsuite!("bad/negativeIntZeroLn");
     let mut parser = IonParser::new(ion_element);
     let value = parser.consume_value().unwrap_err();
-    let expected = IonParserError::InvalidNegativeInt;
+    let expected = IonParserError::BinaryError(ParsingError::NegativeZeroInt);
     assert_eq!(expected, value);
 }
 
@@ -7,7 +7,7 @@ use crate::{IonParserError, IonValue};
 
 #[test]
 fn serde_from_ion_symbol() {
-    let bad_value = IonValue::Symbol("any".to_string());
+    let bad_value = IonValue::Symbol("any".into());
     let result: Result<Value, IonParserError> = bad_value.clone().try_into();
     let error = result.unwrap_err();
 
@@ -26,7 +26,7 @@ fn serde_from_ion_datetime() {
 
 #[test]
 fn serde_from_ion_annotation() {
-    let bad_value = IonValue::Annotation(vec!["one".to_string()], Box::new(IonValue::Bool(true)));
+    let bad_value = IonValue::Annotation(vec!["one".into()], Box::new(IonValue::Bool(true)));
     let result: Result<Value, IonParserError> = bad_value.clone().try_into();
     let error = result.unwrap_err();
 
@@ -9,7 +9,7 @@ fn timestamp_leap_day_non_leap_year_1() {
     let ion_timestamp = read_file_testsuite!("bad/timestamp/outOfRange/leapDayNonLeapYear_1");
     let mut parser = IonParser::new(ion_timestamp);
     let value = parser.consume_value().unwrap_err();
-    let expected = IonParserError::InvalidDate(2001, 2, 29, 0, 0, 0, 0);
+    let expected = IonParserError::InvalidTimestampComponent(2001, 2, 29);
     assert_eq!(expected, value);
 }
 
@@ -18,7 +18,7 @@ fn timestamp_leap_day_non_leap_year_2() {
     let ion_timestamp = read_file_testsuite!("bad/timestamp/outOfRange/leapDayNonLeapYear_2");
     let mut parser = IonParser::new(ion_timestamp);
     let value = parser.consume_value().unwrap_err();
-    let expected = IonParserError::InvalidDate(2001, 2, 29, 0, 0, 0, 0);
+    let expected = IonParserError::InvalidTimestampComponent(2001, 2, 29);
     assert_eq!(expected, value)
 }
 
@@ -81,6 +81,6 @@ fn timestamp_sept_31() {
     let ion_timestamp = read_file_testsuite!("bad/timestamp/timestampSept31");
     let mut parser = IonParser::new(ion_timestamp);
     let value = parser.consume_value().unwrap_err();
-    let expected = IonParserError::InvalidDate(2015, 9, 31, 0, 0, 0, 0);
+    let expected = IonParserError::InvalidTimestampComponent(2015, 9, 31);
     assert_eq!(expected, value);
 }
@@ -302,7 +302,7 @@ fn typecodes_type_3_length_0() {
     let ion_typecode = read_file_testsuite!("bad/typecodes/type_3_length_0");
     let mut parser = IonParser::new(ion_typecode);
     let value = parser.consume_value().unwrap_err();
-    let expected = IonParserError::InvalidNegativeInt;
+    let expected = IonParserError::BinaryError(ParsingError::NegativeZeroInt);
     assert_eq!(expected, value);
 }
 
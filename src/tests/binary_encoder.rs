@@ -1,10 +1,17 @@
 use crate::binary_encoder::encode_ion_value;
-use crate::{IonParser, IonValue};
+use crate::{IonParser, IonValue, IonVersion};
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, FixedOffset};
-use num_bigint::BigInt;
+use num_bigint::{BigInt, BigUint, Sign};
 use std::str::FromStr;
 
+// The values are encoded without an Ion Version Marker.
+fn parse_value(bytes: &[u8]) -> IonValue {
+    let mut parser = IonParser::new(bytes);
+    parser.assume_version(IonVersion::V1_0);
+    parser.consume_value().unwrap().0
+}
+
 #[test]
 fn encode_integer_i64() {
     #[allow(overflowing_literals)]
@@ -34,7 +41,7 @@ fn encode_integer_i64() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
@@ -48,7 +55,7 @@ fn encode_integer_big() {
 
     let bytes = encode_ion_value(&ion_value);
 
-    let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+    let resulting_ion_value = parse_value(&bytes);
 
     assert_eq!(ion_value, resulting_ion_value);
 }
@@ -61,11 +68,30 @@ fn encode_integer_big_negative() {
 
     let bytes = encode_ion_value(&ion_value);
 
-    let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+    let resulting_ion_value = parse_value(&bytes);
 
     assert_eq!(ion_value, resulting_ion_value);
 }
 
+#[test]
+fn encode_integer_zero_is_positive() {
+    let negative_zero = BigInt::from_biguint(Sign::Minus, BigUint::from(0u8));
+
+    let values = vec![
+        IonValue::Integer(0),
+        IonValue::Integer(-0),
+        IonValue::BigInteger(BigInt::from(0)),
+        IonValue::BigInteger(negative_zero),
+    ];
+
+    for ion_value in values {
+        let bytes = encode_ion_value(&ion_value);
+
+        assert_eq!(bytes, vec![0x20]);
+        assert_eq!(parse_value(&bytes), IonValue::Integer(0));
+    }
+}
+
 /*#[test]
 fn encode_integer_float32() {
     let values: Vec<f32> = vec![
@@ -82,7 +108,7 @@ fn encode_integer_float32() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
@@ -94,7 +120,7 @@ fn encode_integer_float32_nan() {
 
     let bytes = encode_ion_value(&ion_value);
 
-    let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+    let resulting_ion_value = parse_value(&bytes);
 
     if let IonValue::Float32(value) = resulting_ion_value {
         if !value.is_nan() {
@@ -122,7 +148,7 @@ fn encode_integer_float64() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
@@ -134,7 +160,7 @@ fn encode_integer_float64_nan() {
 
     let bytes = encode_ion_value(&ion_value);
 
-    let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+    let resulting_ion_value = parse_value(&bytes);
 
     if let IonValue::Float(value) = resulting_ion_value {
         if !value.is_nan() {
@@ -163,7 +189,7 @@ fn encode_integer_decimal() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
@@ -186,7 +212,7 @@ fn encode_integer_string() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
@@ -207,7 +233,7 @@ fn encode_integer_clob() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
@@ -228,7 +254,7 @@ fn encode_integer_blob() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
@@ -251,7 +277,7 @@ fn encode_integer_datetime() {
 
         let bytes = encode_ion_value(&ion_value);
 
-        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        let resulting_ion_value = parse_value(&bytes);
 
         assert_eq!(ion_value, resulting_ion_value);
     }
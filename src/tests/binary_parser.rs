@@ -8,6 +8,7 @@ fn decode_value_null() {
     let ion_test = [0b_0000_1111u8].reader();
 
     let mut lexer = IonBinaryParser::new(Box::new(ion_test));
+    lexer.set_current_ion_version((1, 0));
 
     assert_eq!(
         lexer.consume_value_header(),
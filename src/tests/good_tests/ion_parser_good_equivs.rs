@@ -39,15 +39,15 @@ fn equivs_nop_pad_non_empty_struct() {
         assert_eq!(list.len(), 3);
         assert_eq!(
             list[0],
-            IonValue::Struct(hashmap!("name".into() => IonValue::Bool(true)))
+            IonValue::Struct(hashmap!("name".to_string() => IonValue::Bool(true)))
         );
         assert_eq!(
             list[1],
-            IonValue::Struct(hashmap!("name".into() => IonValue::Bool(true)))
+            IonValue::Struct(hashmap!("name".to_string() => IonValue::Bool(true)))
         );
         assert_eq!(
             list[2],
-            IonValue::Struct(hashmap!("name".into() => IonValue::Bool(true)))
+            IonValue::Struct(hashmap!("name".to_string() => IonValue::Bool(true)))
         );
         assert_eq!(list[0], list[1]);
         assert_eq!(list[1], list[2]);
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, convert::TryInto, str::FromStr};
+use std::{collections::HashMap, convert::TryInto, str::FromStr, sync::Arc};
 
 use num_bigint::BigInt;
 use serde_json::{json, Value};
@@ -59,8 +59,8 @@ fn serde_from_ion_string() {
 fn serde_from_ion_list() {
     let internal_vector = vec![IonValue::Float(2.2), IonValue::Float(1.2)];
 
-    let mut internal_hashmap = HashMap::<String, IonValue>::new();
-    internal_hashmap.insert("first".to_string(), IonValue::Bool(true));
+    let mut internal_hashmap = HashMap::<Arc<str>, IonValue>::new();
+    internal_hashmap.insert("first".into(), IonValue::Bool(true));
 
     let mut json_map = HashMap::<String, Value>::new();
     json_map.insert("first".to_string(), json!(true));
@@ -84,12 +84,12 @@ fn serde_from_ion_list() {
 
 #[test]
 fn serde_from_ion_struct() {
-    let mut hash_map = HashMap::<String, IonValue>::new();
-    hash_map.insert("bool".to_string(), IonValue::Bool(true));
-    hash_map.insert("int".to_string(), IonValue::Integer(3));
-    hash_map.insert("float".to_string(), IonValue::Float(12.3));
+    let mut hash_map = HashMap::<Arc<str>, IonValue>::new();
+    hash_map.insert("bool".into(), IonValue::Bool(true));
+    hash_map.insert("int".into(), IonValue::Integer(3));
+    hash_map.insert("float".into(), IonValue::Float(12.3));
     hash_map.insert(
-        "vector".to_string(),
+        "vector".into(),
         IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
     );
 
@@ -169,10 +169,10 @@ fn ion_from_serde_list() {
 #[test]
 fn ion_from_serde_struct() {
     let mut internal_hashmap = HashMap::<String, Value>::new();
-    internal_hashmap.insert("first".to_string(), json!(2));
+    internal_hashmap.insert("first".into(), json!(2));
 
-    let mut ion_internal_hashmap = HashMap::<String, IonValue>::new();
-    ion_internal_hashmap.insert("first".to_string(), IonValue::Integer(2));
+    let mut ion_internal_hashmap = HashMap::<Arc<str>, IonValue>::new();
+    ion_internal_hashmap.insert("first".into(), IonValue::Integer(2));
 
     let mut hashmap = HashMap::<String, Value>::new();
     hashmap.insert("bool".to_string(), json!(true));
@@ -183,15 +183,15 @@ fn ion_from_serde_struct() {
     let value_struct = json!(hashmap);
     let ion_struct: IonValue = value_struct.try_into().unwrap();
 
-    let mut ion_hashmap = HashMap::<String, IonValue>::new();
-    ion_hashmap.insert("bool".to_string(), IonValue::Bool(true));
-    ion_hashmap.insert("int".to_string(), IonValue::Integer(2));
-    ion_hashmap.insert("float".to_string(), IonValue::Float(5.8));
+    let mut ion_hashmap = HashMap::<Arc<str>, IonValue>::new();
+    ion_hashmap.insert("bool".into(), IonValue::Bool(true));
+    ion_hashmap.insert("int".into(), IonValue::Integer(2));
+    ion_hashmap.insert("float".into(), IonValue::Float(5.8));
     ion_hashmap.insert(
-        "vector".to_string(),
+        "vector".into(),
         IonValue::List(vec![IonValue::Bool(true), IonValue::Bool(false)]),
     );
-    ion_hashmap.insert("map".to_string(), IonValue::Struct(ion_internal_hashmap));
+    ion_hashmap.insert("map".into(), IonValue::Struct(ion_internal_hashmap));
 
     assert_eq!(ion_struct, IonValue::Struct(ion_hashmap));
 }
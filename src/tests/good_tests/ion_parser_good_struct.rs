@@ -13,7 +13,7 @@ fn struct_annotated_empty() {
     assert_eq!(
         parser.consume_value().unwrap().0,
         IonValue::Annotation(
-            ["max_id".to_string()].to_vec(),
+            ["max_id".into()].to_vec(),
             Box::new(IonValue::Struct(HashMap::new()))
         )
     );
@@ -28,7 +28,7 @@ fn struct_annotated_ordered() {
     assert_eq!(
         parser.consume_value().unwrap().0,
         IonValue::Annotation(
-            ["symbols".to_string(), "max_id".to_string()].to_vec(),
+            ["symbols".into(), "max_id".into()].to_vec(),
             Box::new(IonValue::Struct(hashmap!(
                 "version".to_string() => IonValue::Bool(false),
                 "imports".to_string() => IonValue::Bool(true),
@@ -59,7 +59,7 @@ fn struct_len14() {
     assert_eq!(
         parser.consume_value().unwrap().0,
         IonValue::Struct(hashmap!(
-            "name".into() => IonValue::String("123456789ABC".into())
+            "name".to_string() => IonValue::String("123456789ABC".into())
         ))
     );
 }
@@ -73,7 +73,7 @@ fn struct_len15() {
     assert_eq!(
         parser.consume_value().unwrap().0,
         IonValue::Struct(hashmap!(
-            "name".into() => IonValue::String("123456789ABCD".into())
+            "name".to_string() => IonValue::String("123456789ABCD".into())
         ))
     );
 }
@@ -4,6 +4,7 @@ use crate::IonValue;
 use std::fs::File;
 use std::io::BufReader;
 
+// Timestamps without time have an unknown offset.
 #[test]
 fn timestamp_timestamp2011() {
     let ion_blob = read_file_testsuite!("good/timestamp/timestamp2011");
@@ -14,8 +15,11 @@ fn timestamp_timestamp2011() {
 
     assert_eq!(
         value,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-01-01T00:00:00+00:00").unwrap()
+        IonValue::DateTimeUnknownOffset(
+            chrono::NaiveDate::from_ymd_opt(2011, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
         )
     );
 }
@@ -30,8 +34,11 @@ fn timestamp_timestamp2011_02() {
 
     assert_eq!(
         value,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-01T00:00:00+00:00").unwrap()
+        IonValue::DateTimeUnknownOffset(
+            chrono::NaiveDate::from_ymd_opt(2011, 2, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
         )
     );
 }
@@ -46,8 +53,11 @@ fn timestamp_timestamp2011_02_20() {
 
     assert_eq!(
         value,
-        IonValue::DateTime(
-            chrono::DateTime::parse_from_rfc3339("2011-02-20T00:00:00+00:00").unwrap()
+        IonValue::DateTimeUnknownOffset(
+            chrono::NaiveDate::from_ymd_opt(2011, 2, 20)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
         )
     );
 }
@@ -949,72 +949,72 @@ fn typecodes_t13() {
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::Null(NullIonValue::Null))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::Null(NullIonValue::Null))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::Null(NullIonValue::Null))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::Null(NullIonValue::Null))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("0".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("0".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("00".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("00".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("0000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("0000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("00000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("00000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("000000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("000000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("0000000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("0000000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("00000000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("00000000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("000000000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("000000000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("0000000000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("0000000000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("00000000000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("00000000000".into()))),
     );
 
     assert_eq!(
         parser.consume_value().unwrap().0,
-        IonValue::Struct(hashmap!("$ion".into() => IonValue::String("000000000000".into()))),
+        IonValue::Struct(hashmap!("$ion".to_string() => IonValue::String("000000000000".into()))),
     );
 
     assert_eq!(
@@ -0,0 +1,236 @@
+use crate::hashmap;
+use crate::{
+    from_reader, from_value, Annotated, IonEncoder, IonParserError, IonTimestamp, IonValue,
+    NullIonValue, SymbolToken, TimestampPrecision,
+};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use serde::de::{self, EnumAccess, VariantAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+enum Shape {
+    Point,
+    Circle(f64),
+}
+
+impl<'de> Deserialize<'de> for Shape {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum("Shape", &["Point", "Circle"], ShapeVisitor)
+    }
+}
+
+struct ShapeVisitor;
+
+impl<'de> Visitor<'de> for ShapeVisitor {
+    type Value = Shape;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a shape")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Shape, A::Error> {
+        let (variant, value) = data.variant::<String>()?;
+
+        match variant.as_str() {
+            "Point" => value.unit_variant().map(|_| Shape::Point),
+            "Circle" => value.newtype_variant().map(Shape::Circle),
+            _ => Err(de::Error::unknown_variant(&variant, &["Point", "Circle"])),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Car {
+    model: String,
+    year: i64,
+    engine: Engine,
+    color: Option<String>,
+    plate: Option<String>,
+    #[serde(deserialize_with = "deserialize_bytes")]
+    photo: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Engine {
+    cylinders: i64,
+    hybrid: bool,
+}
+
+// Reads the blob as bytes instead of as a sequence of numbers.
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("bytes")
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+            Ok(value)
+        }
+    }
+
+    deserializer.deserialize_byte_buf(BytesVisitor)
+}
+
+fn encode(value: IonValue) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+    encoder.add(value);
+    encoder.encode()
+}
+
+#[test]
+fn deserialize_struct_into_map() {
+    let bytes = encode(IonValue::Struct(hashmap!(
+        "a".to_string() => IonValue::Integer(1),
+        "b".to_string() => IonValue::Integer(2)
+    )));
+
+    let value: HashMap<String, i64> = from_reader(&bytes[..]).unwrap();
+
+    assert_eq!(value.len(), 2);
+    assert_eq!(value["a"], 1);
+    assert_eq!(value["b"], 2);
+}
+
+#[test]
+fn deserialize_derived_struct() {
+    let bytes = encode(IonValue::Struct(hashmap!(
+        "model".to_string() => IonValue::String("CLK 350".to_string()),
+        "year".to_string() => IonValue::Integer(2007),
+        "engine".to_string() => IonValue::Struct(hashmap!(
+            "cylinders".to_string() => IonValue::Integer(6),
+            "hybrid".to_string() => IonValue::Bool(false)
+        )),
+        "color".to_string() => IonValue::Null(NullIonValue::String),
+        "photo".to_string() => IonValue::Blob(vec![0xff, 0xd8, 0xff])
+    )));
+
+    let car: Car = from_reader(&bytes[..]).unwrap();
+
+    assert_eq!(
+        car,
+        Car {
+            model: "CLK 350".to_string(),
+            year: 2007,
+            engine: Engine {
+                cylinders: 6,
+                hybrid: false,
+            },
+            color: None,
+            plate: None,
+            photo: vec![0xff, 0xd8, 0xff],
+        }
+    );
+}
+
+#[test]
+fn deserialize_scalars() {
+    let value = IonValue::List(vec![
+        IonValue::Symbol(SymbolToken::from_text("symbol")),
+        IonValue::String("string".to_string()),
+        IonValue::Bool(true),
+        IonValue::Float(1.5),
+        IonValue::Null(NullIonValue::Integer),
+    ]);
+
+    let value: (String, String, bool, f64, Option<i64>) = from_value(value).unwrap();
+
+    assert_eq!(
+        value,
+        ("symbol".to_string(), "string".to_string(), true, 1.5, None)
+    );
+}
+
+#[test]
+fn deserialize_timestamps_into_strings() {
+    let utc = NaiveDate::from_ymd_opt(2007, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let value = IonValue::List(vec![
+        IonValue::DateTime(DateTime::from(Utc.from_utc_datetime(&utc))),
+        IonValue::Timestamp(IonTimestamp::new(utc, None, TimestampPrecision::Year).unwrap()),
+    ]);
+
+    let value: (String, String) = from_value(value).unwrap();
+
+    assert_eq!(
+        value,
+        ("2007-01-01T00:00:00+00:00".to_string(), "2007T".to_string())
+    );
+}
+
+#[test]
+fn deserialize_blob_into_bytes() {
+    let bytes = encode(IonValue::Blob(vec![1, 2, 3]));
+
+    let value: Vec<u8> = from_reader(&bytes[..]).unwrap();
+
+    assert_eq!(value, vec![1, 2, 3]);
+}
+
+#[test]
+fn deserialize_annotations() {
+    let value = IonValue::Annotation(vec!["meters".into()], Box::new(IonValue::Integer(3)));
+
+    let annotated: Annotated<i64> = from_value(value.clone()).unwrap();
+
+    assert_eq!(
+        annotated,
+        Annotated {
+            annotations: vec!["meters".to_string()],
+            value: 3,
+        }
+    );
+
+    let unannotated: Annotated<i64> = from_value(IonValue::Integer(3)).unwrap();
+
+    assert_eq!(unannotated.annotations, Vec::<String>::new());
+
+    let skipped: i64 = from_value(value).unwrap();
+
+    assert_eq!(skipped, 3);
+}
+
+#[test]
+fn deserialize_enums() {
+    let point: Shape = from_value(IonValue::String("Point".to_string())).unwrap();
+    let circle: Shape = from_value(IonValue::Struct(hashmap!(
+        "Circle".to_string() => IonValue::Float(2.0)
+    )))
+    .unwrap();
+
+    assert_eq!(point, Shape::Point);
+    assert_eq!(circle, Shape::Circle(2.0));
+
+    assert!(matches!(
+        from_value::<Shape>(IonValue::Integer(1)),
+        Err(IonParserError::Deserialization(_))
+    ));
+}
+
+#[test]
+fn deserialize_wrong_type() {
+    assert!(matches!(
+        from_value::<i64>(IonValue::String("1".to_string())),
+        Err(IonParserError::Deserialization(_))
+    ));
+}
+
+#[test]
+fn deserialize_trailing_data() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Integer(1));
+    encoder.add(IonValue::Integer(2));
+    let bytes = encoder.encode();
+
+    assert_eq!(
+        from_reader::<_, i64>(&bytes[..]),
+        Err(IonParserError::TrailingData)
+    );
+}
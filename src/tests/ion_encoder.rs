@@ -1,7 +1,12 @@
 use crate::hashmap;
-use crate::{IonEncoder, IonParser, IonValue};
+use crate::{
+    EncodeMode, EncodeSymbolOrder, IonEncoder, IonParser, IonParserConfig, IonParserError,
+    IonValue, IonVersion, ParsingError, SymbolContext, SymbolToken,
+};
 use bigdecimal::BigDecimal;
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDate};
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::str::FromStr;
 
 #[test]
@@ -41,7 +46,10 @@ fn encode_list() {
 
     let bytes = encoder.encode_value(&ion_value);
 
-    let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+    // `encode_value` doesn't write the Ion Version Marker.
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.assume_version(IonVersion::V1_0);
+    let resulting_ion_value = parser.consume_value().unwrap().0;
 
     assert_eq!(ion_value, resulting_ion_value);
 }
@@ -54,7 +62,10 @@ fn encode_empty_list() {
 
     let bytes = encoder.encode_value(&ion_value);
 
-    let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+    // `encode_value` doesn't write the Ion Version Marker.
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.assume_version(IonVersion::V1_0);
+    let resulting_ion_value = parser.consume_value().unwrap().0;
 
     assert_eq!(ion_value, resulting_ion_value);
 }
@@ -81,3 +92,582 @@ fn encode_struct() {
 
     assert_eq!(ion_value, resulting_ion_value);
 }
+
+#[test]
+fn encode_symbol_with_symbol_context() {
+    let mut context = SymbolContext::new();
+
+    let id = context.insert_symbol("Model");
+
+    assert_eq!(id, 10);
+
+    let mut encoder = IonEncoder::with_symbol_context(context);
+
+    let bytes = encoder.encode_value(&IonValue::Symbol("Model".into()));
+
+    assert_eq!(bytes, vec![0x71, 0x0A]);
+
+    let bytes = encoder.encode_value(&IonValue::Symbol("Type".into()));
+
+    assert_eq!(bytes, vec![0x71, 0x0B]);
+
+    let ion_value = IonValue::List(vec![
+        IonValue::Symbol("Type".into()),
+        IonValue::Symbol("Model".into()),
+    ]);
+
+    encoder.add(ion_value.clone());
+    let bytes = encoder.encode();
+
+    let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+
+    assert_eq!(ion_value, resulting_ion_value);
+}
+
+#[test]
+fn encode_symbol_built_in_a_symbol_context() {
+    let mut context = SymbolContext::new();
+    context.insert_symbol("Make");
+    context.insert_symbol("Model");
+
+    let model = IonValue::symbol_in("Model", &context);
+    let year = IonValue::symbol_in("Year", &context);
+
+    let ids: Vec<Option<u64>> = [&model, &year]
+        .iter()
+        .map(|value| match value {
+            IonValue::Symbol(symbol) => symbol.id,
+            value => panic!("Expected a symbol, found {:?}", value),
+        })
+        .collect();
+
+    assert_eq!(ids, vec![Some(11), None]);
+    assert_eq!(year, IonValue::Symbol("Year".into()));
+
+    let mut encoder = IonEncoder::with_symbol_context(context);
+
+    assert_eq!(encoder.encode_value(&model), vec![0x71, 0x0B]);
+    assert_eq!(encoder.encode_value(&year), vec![0x71, 0x0C]);
+
+    // An encoder with other symbols interns the text instead of using the id.
+    let mut encoder = IonEncoder::new();
+
+    assert_eq!(encoder.encode_value(&model), vec![0x71, 0x0A]);
+}
+
+#[test]
+fn encode_with_minimal_table_only_writes_used_symbols() {
+    let mut context = SymbolContext::new();
+
+    for symbol in &["Make", "Type", "Color", "Year"] {
+        context.insert_symbol(symbol);
+    }
+
+    let ion_value = IonValue::Struct(hashmap!(
+        "Model".to_string() => IonValue::String("CLK 350".to_string())
+    ));
+
+    let mut encoder = IonEncoder::with_symbol_context(context.clone());
+    encoder.add(ion_value.clone());
+    let with_table = encoder.encode();
+
+    let mut encoder = IonEncoder::with_symbol_context(context);
+    encoder.set_encode_mode(EncodeMode::MinimalTable);
+    encoder.add(ion_value.clone());
+    let minimal_table = encoder.encode();
+
+    assert!(minimal_table.len() < with_table.len());
+
+    for bytes in &[with_table, minimal_table] {
+        let resulting_ion_value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+        assert_eq!(ion_value, resulting_ion_value);
+    }
+}
+
+#[test]
+fn encode_with_minimal_table_resets_the_table_on_each_encode() {
+    let mut encoder = IonEncoder::new();
+    encoder.set_encode_mode(EncodeMode::MinimalTable);
+
+    encoder.add(IonValue::Symbol("Make".into()));
+    let first = encoder.encode();
+
+    encoder.add(IonValue::Symbol("Type".into()));
+    let second = encoder.encode();
+
+    assert_eq!(first.len(), second.len());
+
+    let resulting_ion_value = IonParser::new(&second[..]).consume_value().unwrap().0;
+    assert_eq!(IonValue::Symbol("Type".into()), resulting_ion_value);
+}
+
+#[test]
+fn encode_with_minimal_table_keeps_the_symbol_context() {
+    let mut context = SymbolContext::new();
+
+    for symbol in &["Make", "Type", "Color", "Year"] {
+        context.insert_symbol(symbol);
+    }
+
+    let mut encoder = IonEncoder::with_symbol_context(context);
+    encoder.set_encode_mode(EncodeMode::MinimalTable);
+
+    encoder.add(IonValue::Symbol("Model".into()));
+    let minimal_table = encoder.encode();
+
+    let resulting_ion_value = IonParser::new(&minimal_table[..])
+        .consume_value()
+        .unwrap()
+        .0;
+    assert_eq!(IonValue::Symbol("Model".into()), resulting_ion_value);
+
+    // The symbols of the context keep their ids.
+    assert_eq!(
+        encoder.encode_value(&IonValue::Symbol("Year".into())),
+        vec![0x71, 0x0D]
+    );
+
+    encoder.set_encode_mode(EncodeMode::WithTable);
+    encoder.add(IonValue::Symbol("Color".into()));
+    let with_table = encoder.encode();
+
+    // The context is written again, without the symbols of the minimal one.
+    assert!(with_table.windows(4).any(|window| window == b"Make"));
+    assert!(!with_table.windows(5).any(|window| window == b"Model"));
+}
+
+#[test]
+fn encode_padded_to_a_target_length() {
+    let ion_value = IonValue::Struct(hashmap!(
+        "Model".to_string() => IonValue::String("CLK 350".to_string())
+    ));
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(ion_value.clone());
+    encoder.pad_to(64).unwrap();
+    let bytes = encoder.encode();
+
+    assert_eq!(bytes.len(), 64);
+
+    let resulting_ion_values = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert_eq!(resulting_ion_values, vec![ion_value]);
+}
+
+#[test]
+fn encode_padded_to_every_length() {
+    let header_len = IonEncoder::new().encode().len();
+
+    for target_len in header_len..header_len + 300 {
+        let mut encoder = IonEncoder::new();
+        encoder.pad_to(target_len).unwrap();
+        let bytes = encoder.encode();
+
+        assert_eq!(bytes.len(), target_len);
+        assert_eq!(IonParser::new(&bytes[..]).consume_all().unwrap(), vec![]);
+    }
+}
+
+#[test]
+fn encode_padded_to_a_too_small_target() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::String("CLK 350".to_string()));
+
+    let len = encoder.clone().encode().len();
+
+    assert_eq!(
+        encoder.pad_to(4),
+        Err(IonParserError::PadTargetTooSmall { len, target: 4 })
+    );
+}
+
+fn encode_with_cars_table(value: IonValue) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+
+    encoder
+        .with_shared_table(
+            "cars".to_string(),
+            2,
+            &["Make".to_string(), "Model".to_string()],
+        )
+        .unwrap();
+
+    encoder.add(value);
+    encoder.encode()
+}
+
+#[test]
+fn encode_with_shared_table_declares_imports() {
+    let value = IonValue::Struct(hashmap!(
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "Model".to_string() => IonValue::String("CLK 350".to_string()),
+        "Color".to_string() => IonValue::String("White".to_string())
+    ));
+
+    let bytes = encode_with_cars_table(value.clone());
+
+    let table = Rc::new(RefCell::new(vec![]));
+    let table_bytes = table.clone();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.on_symbol_table(Box::new(move |bytes| {
+        table_bytes.borrow_mut().extend(bytes)
+    }));
+    parser
+        .with_shared_table(
+            "cars".to_string(),
+            2,
+            &["Make".to_string(), "Model".to_string()],
+        )
+        .unwrap();
+
+    assert_eq!(parser.consume_all().unwrap(), vec![value]);
+
+    // Without the annotation wrapper, `ee <length> 81 83`, the table is read
+    // as a plain struct. The shared symbols are imported, so only the local
+    // one is declared.
+    let table = table.borrow();
+    assert_eq!(&table[2..4], &[0x81, 0x83]);
+
+    let mut table_struct = vec![0xe0, 0x01, 0x00, 0xea];
+    table_struct.extend_from_slice(&table[4..]);
+
+    let config = IonParserConfig {
+        preserve_struct_order: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        IonParser::with_config(&table_struct[..], config)
+            .parse_single()
+            .unwrap(),
+        IonValue::OrderedStruct(vec![
+            (
+                "imports".into(),
+                IonValue::List(vec![IonValue::OrderedStruct(vec![
+                    ("name".into(), IonValue::String("cars".to_string())),
+                    ("version".into(), IonValue::Integer(2)),
+                    ("max_id".into(), IonValue::Integer(2)),
+                ])])
+            ),
+            (
+                "symbols".into(),
+                IonValue::List(vec![IonValue::String("Color".to_string())])
+            ),
+        ])
+    );
+}
+
+#[test]
+fn encode_with_shared_table_needs_the_catalog_to_decode() {
+    let value = IonValue::Struct(hashmap!(
+        "Make".to_string() => IonValue::String("Mercedes".to_string())
+    ));
+
+    let bytes = encode_with_cars_table(value);
+
+    // Without the table the reader only knows that the import has 2 symbols.
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap_err(),
+        IonParserError::SymbolNotFoundInTable
+    );
+}
+
+#[test]
+fn encode_with_shared_table_and_minimal_table() {
+    let mut encoder = IonEncoder::new();
+    encoder.set_encode_mode(EncodeMode::MinimalTable);
+    encoder
+        .with_shared_table("cars".to_string(), 1, &["Make".to_string()])
+        .unwrap();
+
+    let first = IonValue::Struct(hashmap!(
+        "Make".to_string() => IonValue::Integer(1),
+        "Year".to_string() => IonValue::Integer(2019)
+    ));
+    let second = IonValue::Struct(hashmap!(
+        "Make".to_string() => IonValue::Integer(2),
+        "Type".to_string() => IonValue::Integer(3)
+    ));
+
+    encoder.add(first.clone());
+    let first_bytes = encoder.encode();
+    encoder.add(second.clone());
+    let second_bytes = encoder.encode();
+
+    assert!(!second_bytes.windows(4).any(|window| window == b"Year"));
+
+    for (bytes, value) in [(first_bytes, first), (second_bytes, second)] {
+        let mut parser = IonParser::new(&bytes[..]);
+        parser
+            .with_shared_table("cars".to_string(), 1, &["Make".to_string()])
+            .unwrap();
+
+        assert_eq!(parser.consume_all().unwrap(), vec![value]);
+    }
+}
+
+fn build_multi_struct_document() -> Vec<IonValue> {
+    (0..3)
+        .map(|index| {
+            IonValue::Struct(hashmap!(
+                "Model".to_string() => IonValue::String("CLK 350".to_string()),
+                "Year".to_string() => IonValue::Integer(2019 + index),
+                "Make".to_string() => IonValue::Symbol("Mercedes".into()),
+                "Owner".to_string() => IonValue::Annotation(
+                    vec!["person".into()],
+                    Box::new(IonValue::Struct(hashmap!(
+                        "Name".to_string() => IonValue::String("Jane".to_string())
+                    )))
+                )
+            ))
+        })
+        .collect()
+}
+
+fn encode_with_symbol_order(values: Vec<IonValue>, symbol_order: EncodeSymbolOrder) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+    encoder.set_symbol_order(symbol_order);
+
+    for value in values {
+        encoder.add(value);
+    }
+
+    encoder.encode()
+}
+
+#[test]
+fn encode_with_symbol_order_is_reproducible() {
+    let document = build_multi_struct_document();
+
+    for symbol_order in [EncodeSymbolOrder::FirstUse, EncodeSymbolOrder::Sorted] {
+        let first = encode_with_symbol_order(document.clone(), symbol_order);
+        let second = encode_with_symbol_order(document.clone(), symbol_order);
+
+        assert_eq!(first, second);
+        assert_eq!(IonParser::new(&first[..]).consume_all().unwrap(), document);
+    }
+}
+
+#[test]
+fn encode_with_sorted_symbols_ignores_struct_order() {
+    // Built separately, so the fields of the structs are iterated in a
+    // different order.
+    let first = encode_with_symbol_order(build_multi_struct_document(), EncodeSymbolOrder::Sorted);
+    let second = encode_with_symbol_order(build_multi_struct_document(), EncodeSymbolOrder::Sorted);
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn encode_with_sorted_symbols_assigns_ids_alphabetically() {
+    let value = IonValue::List(vec![
+        IonValue::Symbol("c".into()),
+        IonValue::Symbol("a".into()),
+        IonValue::Symbol("b".into()),
+    ]);
+
+    let bytes = encode_with_symbol_order(vec![value], EncodeSymbolOrder::Sorted);
+
+    let ids: Vec<Option<u64>> = match IonParser::new(&bytes[..]).consume_value().unwrap().0 {
+        IonValue::List(values) => values
+            .into_iter()
+            .map(|value| match value {
+                IonValue::Symbol(symbol) => symbol.id,
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+
+    assert_eq!(ids, vec![Some(12), Some(10), Some(11)]);
+}
+
+#[test]
+fn encode_without_ivm_and_parse_assuming_version() {
+    let value = IonValue::Struct(hashmap!(
+        "name".to_string() => IonValue::String("Mercedes".to_string()),
+        "tags".to_string() => IonValue::List(vec![IonValue::Symbol("fast".into())])
+    ));
+
+    let mut encoder = IonEncoder::new();
+    encoder.with_ivm(false);
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    assert_ne!(&bytes[..4], &[0xE0, 0x01, 0x00, 0xEA]);
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.assume_version(IonVersion::V1_0);
+
+    assert_eq!(parser.consume_all().unwrap(), vec![value]);
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_value(),
+        Err(IonParserError::BinaryError(
+            ParsingError::MissingVersionMarker
+        ))
+    );
+}
+
+#[test]
+fn try_encode_rejects_a_symbol_without_text_or_id() {
+    let value = IonValue::List(vec![IonValue::Symbol(SymbolToken::new(None, None))]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+
+    assert_eq!(
+        encoder.try_encode(),
+        Err(IonParserError::SymbolWithoutTextOrId)
+    );
+
+    // The value is still there to be fixed or encoded anyway.
+    assert!(!encoder.encode().is_empty());
+}
+
+#[test]
+fn try_encode_rejects_a_symbol_id_not_in_the_system_or_imported_tables() {
+    for id in [10, 30] {
+        let mut encoder = IonEncoder::new();
+        encoder.add(IonValue::Symbol("Model".into()));
+        encoder.add(IonValue::Symbol(SymbolToken::from_id(id)));
+
+        assert_eq!(
+            encoder.try_encode(),
+            Err(IonParserError::SymbolIdNotImported(id))
+        );
+    }
+}
+
+#[test]
+fn try_encode_accepts_system_and_imported_symbol_ids() {
+    let mut encoder = IonEncoder::new();
+    encoder
+        .with_shared_table(
+            "cars".to_string(),
+            1,
+            &["Make".to_string(), "Model".to_string()],
+        )
+        .unwrap();
+    encoder.add(IonValue::Symbol(SymbolToken::from_id(4)));
+    encoder.add(IonValue::Symbol(SymbolToken::from_id(11)));
+    let bytes = encoder.try_encode().unwrap();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser
+        .with_shared_table(
+            "cars".to_string(),
+            1,
+            &["Make".to_string(), "Model".to_string()],
+        )
+        .unwrap();
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Symbol("name".into()),
+            IonValue::Symbol("Model".into())
+        ]
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder
+        .with_shared_table("cars".to_string(), 1, &["Make".to_string()])
+        .unwrap();
+    encoder.add(IonValue::Symbol(SymbolToken::from_id(11)));
+
+    assert_eq!(
+        encoder.try_encode(),
+        Err(IonParserError::SymbolIdNotImported(11))
+    );
+}
+
+#[test]
+fn encode_writes_a_symbol_id_not_in_the_system_or_imported_tables_as_unknown() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Symbol("Model".into()));
+    encoder.add(IonValue::Symbol(SymbolToken::from_id(10)));
+    let bytes = encoder.encode();
+
+    let values = IonParser::new(&bytes[..]).consume_all().unwrap();
+
+    assert_eq!(values[0], IonValue::Symbol("Model".into()));
+    assert_eq!(values[1], IonValue::Symbol("$0".into()));
+}
+
+#[test]
+fn try_encode_rejects_invalid_annotations() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Annotation(vec![], Box::new(IonValue::Integer(1))));
+
+    assert_eq!(
+        encoder.try_encode(),
+        Err(IonParserError::EmptyAnnotationList)
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Annotation(
+        vec!["a".into()],
+        Box::new(IonValue::Annotation(
+            vec!["b".into()],
+            Box::new(IonValue::Integer(1)),
+        )),
+    ));
+
+    assert_eq!(encoder.try_encode(), Err(IonParserError::NestedAnnotations));
+}
+
+#[test]
+fn try_encode_rejects_years_out_of_ion_range() {
+    let datetime = NaiveDate::from_ymd_opt(10000, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let value = IonValue::DateTimeUnknownOffset(datetime);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value);
+
+    assert_eq!(
+        encoder.try_encode(),
+        Err(IonParserError::TimestampYearOutOfRange(10000))
+    );
+}
+
+#[test]
+fn try_encode_encodes_valid_values() {
+    let value = IonValue::Annotation(
+        vec!["car".into()],
+        Box::new(IonValue::Symbol(SymbolToken::from_text("sedan"))),
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.try_encode().unwrap();
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![value]
+    );
+}
+
+#[test]
+fn encode_to_a_writer() {
+    let values = vec![
+        IonValue::Symbol("sedan".into()),
+        IonValue::String("CLK 350".to_string()),
+    ];
+
+    let mut encoder = IonEncoder::new();
+    let mut output = vec![0xff];
+
+    for value in &values {
+        encoder.add(value.clone());
+    }
+
+    let len = encoder.encode_to(&mut output).unwrap();
+
+    assert_eq!(len, output.len() - 1);
+    assert_eq!(IonParser::new(&output[1..]).consume_all().unwrap(), values);
+}
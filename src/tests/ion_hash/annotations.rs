@@ -18,12 +18,12 @@ fn ion_hash_annotation_2() {
     let value = IonValue::Annotation(
         vec!["Annot 1".into(), "Annot 2".into(), "Annot 3".into()],
         Box::new(IonValue::Struct(hashmap!(
-            "e".into() => IonValue::Integer(5),
-            "a".into() => IonValue::Integer(1),
-            "l".into() => IonValue::Integer(12),
-            "b".into() => IonValue::Integer(2),
-            "i".into() => IonValue::Integer(9),
-            "n".into() => IonValue::Integer(14)
+            "e".to_string() => IonValue::Integer(5),
+            "a".to_string() => IonValue::Integer(1),
+            "l".to_string() => IonValue::Integer(12),
+            "b".to_string() => IonValue::Integer(2),
+            "i".to_string() => IonValue::Integer(9),
+            "n".to_string() => IonValue::Integer(14)
         ))),
     );
 
@@ -31,3 +31,54 @@ fn ion_hash_annotation_2() {
 
     assert_eq!(b"\x6e\xbf\xeb\xda\xd9\xf4\xab\x09\xc3\x3b\x3e\xbb\xad\xc8\xbb\x77\x6c\x2e\xe2\x14\x5f\x00\xac\x71\x7c\xb9\x03\x72\xe7\x95\x60\x55", &hash[..]);
 }
+
+// The hashes of the following tests were computed following the Ion Hash
+// spec, where the annotation serialization wraps the serialization of the
+// container: B E0 s(annot 1) ... s(annot n) s(container) E
+#[test]
+fn ion_hash_annotated_struct() {
+    let value = IonValue::Annotation(
+        vec!["car".into()],
+        Box::new(IonValue::Struct(hashmap!(
+            "Year".to_string() => IonValue::Integer(2019),
+            "Make".to_string() => IonValue::String("Mercedes".into())
+        ))),
+    );
+
+    let hash = IonHash::default_digest(&value);
+
+    assert_eq!(b"\xc7\x03\x72\xa0\x04\x2f\x35\xbe\xe7\xb4\x3e\x41\x96\xf7\x62\x6e\xca\x87\x54\xf0\xbe\xe1\xf2\x57\x85\xca\xac\x34\xba\x16\x06\x56", &hash[..]);
+}
+
+#[test]
+fn ion_hash_annotated_list() {
+    let value = IonValue::Annotation(
+        vec!["a".into(), "b".into()],
+        Box::new(IonValue::List(vec![
+            IonValue::Integer(1),
+            IonValue::String("two".into()),
+            IonValue::Symbol("three".into()),
+        ])),
+    );
+
+    let hash = IonHash::default_digest(&value);
+
+    assert_eq!(b"\xfb\x46\x57\xfa\xca\x4a\x51\x36\x5d\xc8\xdf\x87\xea\x92\xbb\xb6\x68\x6f\x03\x0c\x01\xc4\x6e\xb8\x90\xe3\x00\x04\x2f\x4d\xdb\x3e", &hash[..]);
+}
+
+#[test]
+fn ion_hash_annotated_struct_inside_annotated_list() {
+    let value = IonValue::Annotation(
+        vec!["outer".into()],
+        Box::new(IonValue::List(vec![IonValue::Annotation(
+            vec!["inner".into()],
+            Box::new(IonValue::Struct(hashmap!(
+                "a".to_string() => IonValue::Integer(-1)
+            ))),
+        )])),
+    );
+
+    let hash = IonHash::default_digest(&value);
+
+    assert_eq!(b"\x44\x5b\x34\x93\x70\x8a\xd5\x25\xf1\xdd\x7a\x6a\x20\x8a\x52\xb0\xf4\xb1\x6d\x15\xb8\xcf\x7a\x80\x17\xd6\xe5\x9c\x36\xf3\xb3\x87", &hash[..]);
+}
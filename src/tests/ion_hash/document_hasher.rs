@@ -0,0 +1,64 @@
+use crate::{DocumentHasher, IonHash, IonValue};
+use sha2::Sha256;
+
+fn documents() -> Vec<IonValue> {
+    vec![
+        IonValue::String("Document 1".to_string()),
+        IonValue::Integer(2),
+        IonValue::List(vec![IonValue::Bool(true)]),
+    ]
+}
+
+#[test]
+fn document_hasher_empty() {
+    assert_eq!(DocumentHasher::new().digest(), Vec::<u8>::new());
+}
+
+#[test]
+fn document_hasher_single_document() {
+    let document = IonValue::String("Document 1".to_string());
+
+    let mut hasher = DocumentHasher::new();
+    hasher.add_document(&document);
+
+    assert_eq!(hasher.digest(), IonHash::digest::<Sha256>(&document));
+}
+
+#[test]
+fn document_hasher_dots_the_sorted_hashes() {
+    let mut hashes: Vec<IonHash<Sha256>> = documents()
+        .iter()
+        .map(IonHash::from_ion_value::<Sha256>)
+        .collect();
+
+    hashes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut expected = IonHash::from_hashes_bytes::<Sha256>(hashes[0].get());
+    expected.dot(IonHash::from_hashes_bytes::<Sha256>(hashes[1].get()));
+    expected.dot(IonHash::from_hashes_bytes::<Sha256>(hashes[2].get()));
+
+    let mut hasher = DocumentHasher::new();
+
+    for document in documents() {
+        hasher.add_document(&document);
+    }
+
+    assert_eq!(hasher.digest(), expected.get());
+}
+
+#[test]
+fn document_hasher_does_not_depend_on_order() {
+    let mut hasher = DocumentHasher::new();
+
+    for document in documents() {
+        hasher.add_document(&document);
+    }
+
+    let mut reversed_hasher = DocumentHasher::new();
+
+    for document in documents().iter().rev() {
+        reversed_hasher.add_document_hash(&IonHash::digest::<Sha256>(document));
+    }
+
+    assert_eq!(hasher.digest(), reversed_hasher.digest());
+}
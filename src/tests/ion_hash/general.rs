@@ -1,7 +1,8 @@
 use crate::hashmap;
-use crate::{IonHash, IonValue, NullIonValue};
+use crate::{IonHash, IonParserError, IonValue, NullIonValue};
 use bigdecimal::BigDecimal;
 use sha2::Sha256;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 #[test]
@@ -75,45 +76,238 @@ fn build_big_struct() -> IonValue {
     ));
 
     let long_struct = IonValue::Struct(hashmap!(
-        "000021i".into() => IonValue::Integer(9),
-        "012i".into() => IonValue::Integer(9),
-        "01d".into() => IonValue::Integer(4),
-        "01h".into() => IonValue::Integer(8),
-        "11n".into() => IonValue::Float(std::f64::NAN),
-        "12l".into() => IonValue::Integer(12),
-        "1d".into() => IonValue::Integer(4),
-        "21l".into() => IonValue::Integer(12),
-        "2h".into() => list,
-        "aaa".into() => IonValue::Integer(1),
-        "aak".into() => IonValue::Integer(11),
-        "ae".into() => IonValue::Integer(5),
-        "b".into() => qldb_struct,
-        "bb".into() => IonValue::Integer(2),
-        "cb".into() => IonValue::Integer(2),
-        "c".into() => IonValue::Integer(3),
-        "d".into() => IonValue::Null(NullIonValue::Clob),
-        "9f".into() => IonValue::Integer(6),
-        "09f".into() => IonValue::Decimal(BigDecimal::from_str("92407156491786485918754613897564897561387954629341564305176435762934857629384756024751649587623498561204576329654.1239476129586128957624351682956187465187324618724691845696216935").unwrap()),
-        "g".into() => IonValue::Integer(7),
-        "00h".into() => IonValue::Integer(8),
-        "0h".into() => IonValue::Integer(8),
-        "i".into() => IonValue::Integer(9),
-        "j".into() => IonValue::Integer(10),
-        "k".into() => IonValue::Null(NullIonValue::Float),
-        "00001l".into() => IonValue::Integer(12),
-        "00002l".into() => IonValue::Integer(12),
-        "10000l".into() => IonValue::Integer(12),
-        "l".into() => IonValue::Integer(12),
-        "m".into() => IonValue::Integer(13),
-        "n".into() => IonValue::Integer(14)
+        "000021i".to_string() => IonValue::Integer(9),
+        "012i".to_string() => IonValue::Integer(9),
+        "01d".to_string() => IonValue::Integer(4),
+        "01h".to_string() => IonValue::Integer(8),
+        "11n".to_string() => IonValue::Float(std::f64::NAN),
+        "12l".to_string() => IonValue::Integer(12),
+        "1d".to_string() => IonValue::Integer(4),
+        "21l".to_string() => IonValue::Integer(12),
+        "2h".to_string() => list,
+        "aaa".to_string() => IonValue::Integer(1),
+        "aak".to_string() => IonValue::Integer(11),
+        "ae".to_string() => IonValue::Integer(5),
+        "b".to_string() => qldb_struct,
+        "bb".to_string() => IonValue::Integer(2),
+        "cb".to_string() => IonValue::Integer(2),
+        "c".to_string() => IonValue::Integer(3),
+        "d".to_string() => IonValue::Null(NullIonValue::Clob),
+        "9f".to_string() => IonValue::Integer(6),
+        "09f".to_string() => IonValue::Decimal(BigDecimal::from_str("92407156491786485918754613897564897561387954629341564305176435762934857629384756024751649587623498561204576329654.1239476129586128957624351682956187465187324618724691845696216935").unwrap()),
+        "g".to_string() => IonValue::Integer(7),
+        "00h".to_string() => IonValue::Integer(8),
+        "0h".to_string() => IonValue::Integer(8),
+        "i".to_string() => IonValue::Integer(9),
+        "j".to_string() => IonValue::Integer(10),
+        "k".to_string() => IonValue::Null(NullIonValue::Float),
+        "00001l".to_string() => IonValue::Integer(12),
+        "00002l".to_string() => IonValue::Integer(12),
+        "10000l".to_string() => IonValue::Integer(12),
+        "l".to_string() => IonValue::Integer(12),
+        "m".to_string() => IonValue::Integer(13),
+        "n".to_string() => IonValue::Integer(14)
     ));
 
     IonValue::Struct(hashmap!(
-        "e".into() => IonValue::Integer(5),
-        "a".into() => long_struct,
-        "l".into() => IonValue::Integer(12),
-        "b".into() => IonValue::Integer(2),
-        "i".into() => IonValue::Integer(9),
-        "n".into() => IonValue::Float(123.12)
+        "e".to_string() => IonValue::Integer(5),
+        "a".to_string() => long_struct,
+        "l".to_string() => IonValue::Integer(12),
+        "b".to_string() => IonValue::Integer(2),
+        "i".to_string() => IonValue::Integer(9),
+        "n".to_string() => IonValue::Float(123.12)
     ))
 }
+
+#[test]
+fn ion_hash_verify() {
+    let value = IonValue::String("Hello".to_string());
+
+    let hash = IonHash::digest::<Sha256>(&value);
+
+    assert!(IonHash::verify::<Sha256>(&value, &hash));
+
+    let mut wrong_hash = hash.clone();
+    wrong_hash[31] ^= 1;
+
+    assert!(!IonHash::verify::<Sha256>(&value, &wrong_hash));
+    assert!(!IonHash::verify::<Sha256>(&value, &hash[..16]));
+    assert!(!IonHash::verify::<Sha256>(
+        &IonValue::String("Bye".to_string()),
+        &hash
+    ));
+}
+
+fn build_qldb_revision(data: IonValue, metadata: IonValue, hash: Vec<u8>) -> IonValue {
+    IonValue::Struct(hashmap!(
+        "blockAddress".to_string() => IonValue::Struct(hashmap!(
+            "strandId".to_string() => IonValue::String("JdxjkR9bSYB5jMHWcI464T".to_string()),
+            "sequenceNo".to_string() => IonValue::Integer(1234)
+        )),
+        "hash".to_string() => IonValue::Blob(hash),
+        "data".to_string() => data,
+        "metadata".to_string() => metadata
+    ))
+}
+
+#[test]
+fn ion_hash_verify_qldb_revision() {
+    let data = IonValue::Struct(hashmap!(
+        "VIN".to_string() => IonValue::String("1N4AL11D75C109151".to_string()),
+        "Make".to_string() => IonValue::String("Nissan".to_string())
+    ));
+
+    let metadata = IonValue::Struct(hashmap!(
+        "id".to_string() => IonValue::String("3Qv67yjXEwB9SjmvkuG6Cp".to_string()),
+        "version".to_string() => IonValue::Integer(0)
+    ));
+
+    // Computed apart from this crate, following the Ion Hash specification
+    // and the QLDB rule to join the data and metadata hashes.
+    let hash = vec![
+        0x7b, 0xa4, 0xab, 0x67, 0xd3, 0x43, 0x15, 0xeb, 0xf8, 0x87, 0x93, 0xfd, 0x1c, 0x3f, 0xc4,
+        0x18, 0x7d, 0xa2, 0x05, 0xda, 0x4c, 0x23, 0x6f, 0xfa, 0xaa, 0x17, 0x73, 0xe0, 0xa6, 0x03,
+        0xba, 0xab,
+    ];
+
+    let revision = build_qldb_revision(data.clone(), metadata.clone(), hash.clone());
+
+    assert_eq!(IonHash::verify_qldb_revision(&revision), Ok(true));
+
+    let tampered_data = data.merge(&IonValue::Struct(hashmap!(
+        "Make".to_string() => IonValue::String("Mercedes".to_string())
+    )));
+
+    let tampered = build_qldb_revision(tampered_data, metadata, hash);
+
+    assert_eq!(IonHash::verify_qldb_revision(&tampered), Ok(false));
+}
+
+#[test]
+fn ion_hash_verify_qldb_revision_without_data() {
+    let revision = IonValue::Struct(hashmap!(
+        "hash".to_string() => IonValue::Blob(vec![0; 32]),
+        "metadata".to_string() => IonValue::Struct(HashMap::new())
+    ));
+
+    assert_eq!(
+        IonHash::verify_qldb_revision(&revision),
+        Err(IonParserError::FieldNotFound("data".to_string()))
+    );
+}
+
+#[test]
+fn ion_hash_update_struct_field() {
+    let mut fields = hashmap!(
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "Model".to_string() => IonValue::String("CLK 350".to_string()),
+        "Year".to_string() => IonValue::Integer(2019)
+    );
+
+    let mut hash = IonHash::from_ion_value::<Sha256>(&IonValue::Struct(fields.clone()));
+
+    let old_field_hash = IonHash::struct_field_hash::<Sha256>("Year", &IonValue::Integer(2019));
+    let new_field_hash = IonHash::struct_field_hash::<Sha256>("Year", &IonValue::Integer(2021));
+
+    assert!(hash.update_struct_field(&old_field_hash, &new_field_hash));
+
+    fields.insert("Year".into(), IonValue::Integer(2021));
+
+    assert_eq!(
+        hash.get(),
+        &IonHash::digest::<Sha256>(&IonValue::Struct(fields))[..]
+    );
+
+    assert!(!hash.update_struct_field(&old_field_hash, &new_field_hash));
+}
+
+#[test]
+fn ion_hash_update_struct_field_needs_a_struct_hash() {
+    let mut hash = IonHash::from_ion_value::<Sha256>(&IonValue::Integer(2019));
+
+    let field_hash = IonHash::struct_field_hash::<Sha256>("Year", &IonValue::Integer(2019));
+
+    assert!(!hash.update_struct_field(&field_hash, &field_hash));
+
+    let mut hash = IonHash::from_ion_value::<Sha256>(&IonValue::Struct(hashmap!(
+        "Year".to_string() => IonValue::Integer(2019)
+    )));
+
+    hash.add_ion_value(&IonValue::Integer(1));
+
+    assert!(!hash.update_struct_field(&field_hash, &field_hash));
+}
+
+#[test]
+fn ion_hash_nan_bit_patterns() {
+    let canonical = IonHash::digest::<Sha256>(&IonValue::Float(f64::NAN));
+
+    for bits in &[
+        0x7FF8_0000_0000_0001u64,
+        0xFFF8_0000_0000_0000,
+        0x7FF0_0000_0000_0001,
+    ] {
+        let nan = f64::from_bits(*bits);
+
+        assert!(nan.is_nan());
+        assert_eq!(IonHash::digest::<Sha256>(&IonValue::Float(nan)), canonical);
+    }
+}
+
+#[test]
+fn ion_hash_empty_containers() {
+    use crate::ion_hash_encoder::encode_value;
+
+    let list = IonValue::List(vec![]);
+    let sexp = IonValue::SExpr(vec![]);
+    let ion_struct = IonValue::Struct(HashMap::new());
+
+    // The representation is just the type qualifier between the markers.
+    assert_eq!(encode_value::<Sha256>(&list), vec![0x0B, 0xB0, 0x0E]);
+    assert_eq!(encode_value::<Sha256>(&sexp), vec![0x0B, 0xC0, 0x0E]);
+    assert_eq!(encode_value::<Sha256>(&ion_struct), vec![0x0B, 0xD0, 0x0E]);
+
+    let list_hash = IonHash::digest::<Sha256>(&list);
+    let sexp_hash = IonHash::digest::<Sha256>(&sexp);
+    let struct_hash = IonHash::digest::<Sha256>(&ion_struct);
+
+    assert_eq!(b"\x11\x66\xd9\xe6\x81\xe0\x66\x4f\x6c\x6e\x15\x03\x88\xd4\xc6\x81\x74\xab\xc8\x16\x29\x72\x4a\xfb\x8b\xa0\x38\x19\x69\xb9\x46\xc6", &list_hash[..]);
+    assert_eq!(b"\x75\xe1\x74\x5a\x6e\x93\xfa\x4b\x8f\x9b\xf2\x10\x83\x8a\xfb\x04\xba\xc7\x46\x40\xcb\x4e\xee\x47\x66\x42\x3a\xac\x00\x19\x3d\xa1", &sexp_hash[..]);
+    assert_eq!(b"\xdc\x3f\xf8\xe5\x50\xc8\x33\x23\x6b\xbe\xe9\x2d\x16\x37\x62\x69\x8b\x7b\x0b\x7b\x68\xa1\xaf\x1b\x06\x02\x43\x58\x07\x41\xb7\xa6", &struct_hash[..]);
+
+    assert_ne!(list_hash, sexp_hash);
+    assert_ne!(list_hash, struct_hash);
+    assert_ne!(sexp_hash, struct_hash);
+
+    assert_eq!(
+        IonHash::digest::<Sha256>(&IonValue::OrderedStruct(vec![])),
+        struct_hash
+    );
+}
+
+#[test]
+fn ion_hash_reset_between_values() {
+    let first = IonValue::String("Mercedes".to_string());
+    let second = IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]);
+
+    let mut hash = IonHash::with_hasher::<Sha256>();
+
+    hash.add_ion_value(&first);
+    assert_eq!(hash.get(), &IonHash::digest::<Sha256>(&first)[..]);
+
+    hash.reset();
+    assert!(hash.get().is_empty());
+
+    hash.add_ion_value(&second);
+    assert_eq!(hash.get(), &IonHash::digest::<Sha256>(&second)[..]);
+
+    hash.reset();
+    hash.dot(IonHash::from_ion_value::<Sha256>(&first));
+    hash.dot(IonHash::from_ion_value::<Sha256>(&second));
+
+    let mut fresh = IonHash::with_hasher::<Sha256>();
+    fresh.dot(IonHash::from_ion_value::<Sha256>(&first));
+    fresh.dot(IonHash::from_ion_value::<Sha256>(&second));
+
+    assert_eq!(hash.get(), fresh.get());
+}
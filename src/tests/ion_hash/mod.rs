@@ -3,6 +3,7 @@ pub mod blob;
 pub mod bool;
 pub mod clob;
 pub mod decimal;
+pub mod document_hasher;
 pub mod float;
 pub mod general;
 pub mod integer;
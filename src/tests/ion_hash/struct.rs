@@ -1,5 +1,6 @@
 use crate::hashmap;
 use crate::{IonHash, IonValue};
+use sha2::{Digest, Sha256};
 
 #[test]
 fn ion_hash_3() {
@@ -8,15 +9,15 @@ fn ion_hash_3() {
 
     let mut ion_struct = HashMap::new();
 
-    ion_struct.insert("Model".to_string(), IonValue::String("CLK 350".to_string()));
-    ion_struct.insert("Type".to_string(), IonValue::String("Sedan".to_string()));
-    ion_struct.insert("Color".to_string(), IonValue::String("White".to_string()));
+    ion_struct.insert("Model".into(), IonValue::String("CLK 350".to_string()));
+    ion_struct.insert("Type".into(), IonValue::String("Sedan".to_string()));
+    ion_struct.insert("Color".into(), IonValue::String("White".to_string()));
     ion_struct.insert(
-        "VIN".to_string(),
+        "VIN".into(),
         IonValue::String("1C4RJFAG0FC625797".to_string()),
     );
-    ion_struct.insert("Make".to_string(), IonValue::String("Mercedes".to_string()));
-    ion_struct.insert("Year".to_string(), IonValue::Integer(2019));
+    ion_struct.insert("Make".into(), IonValue::String("Mercedes".to_string()));
+    ion_struct.insert("Year".into(), IonValue::Integer(2019));
 
     let ion_value = IonValue::Struct(ion_struct);
 
@@ -30,7 +31,7 @@ fn ion_hash_3() {
 #[test]
 fn ion_hash_simple_struct() {
     let value = IonValue::Struct(hashmap!(
-        "e".into() => IonValue::Integer(5)
+        "e".to_string() => IonValue::Integer(5)
     ));
 
     let hash = IonHash::default_digest(&value);
@@ -41,20 +42,20 @@ fn ion_hash_simple_struct() {
 #[test]
 fn ion_hash_long_struct() {
     let value = IonValue::Struct(hashmap!(
-        "e".into() => IonValue::Integer(5),
-        "a".into() => IonValue::Integer(1),
-        "l".into() => IonValue::Integer(12),
-        "b".into() => IonValue::Integer(2),
-        "i".into() => IonValue::Integer(9),
-        "n".into() => IonValue::Integer(14),
-        "c".into() => IonValue::Integer(3),
-        "j".into() => IonValue::Integer(10),
-        "d".into() => IonValue::Integer(4),
-        "f".into() => IonValue::Integer(6),
-        "h".into() => IonValue::Integer(8),
-        "k".into() => IonValue::Integer(11),
-        "m".into() => IonValue::Integer(13),
-        "g".into() => IonValue::Integer(7)
+        "e".to_string() => IonValue::Integer(5),
+        "a".to_string() => IonValue::Integer(1),
+        "l".to_string() => IonValue::Integer(12),
+        "b".to_string() => IonValue::Integer(2),
+        "i".to_string() => IonValue::Integer(9),
+        "n".to_string() => IonValue::Integer(14),
+        "c".to_string() => IonValue::Integer(3),
+        "j".to_string() => IonValue::Integer(10),
+        "d".to_string() => IonValue::Integer(4),
+        "f".to_string() => IonValue::Integer(6),
+        "h".to_string() => IonValue::Integer(8),
+        "k".to_string() => IonValue::Integer(11),
+        "m".to_string() => IonValue::Integer(13),
+        "g".to_string() => IonValue::Integer(7)
     ));
 
     let hash = IonHash::default_digest(&value);
@@ -65,40 +66,100 @@ fn ion_hash_long_struct() {
 #[test]
 fn ion_hash_long_long_struct() {
     let value = IonValue::Struct(hashmap!(
-        "000021i".into() => IonValue::Integer(9),
-        "012i".into() => IonValue::Integer(9),
-        "01d".into() => IonValue::Integer(4),
-        "01h".into() => IonValue::Integer(8),
-        "11n".into() => IonValue::Integer(14),
-        "12l".into() => IonValue::Integer(12),
-        "1d".into() => IonValue::Integer(4),
-        "21l".into() => IonValue::Integer(12),
-        "2h".into() => IonValue::Integer(8),
-        "aaa".into() => IonValue::Integer(1),
-        "aak".into() => IonValue::Integer(11),
-        "ae".into() => IonValue::Integer(5),
-        "b".into() => IonValue::Integer(2),
-        "bb".into() => IonValue::Integer(2),
-        "cb".into() => IonValue::Integer(2),
-        "c".into() => IonValue::Integer(3),
-        "d".into() => IonValue::Integer(4),
-        "9f".into() => IonValue::Integer(6),
-        "09f".into() => IonValue::Integer(6),
-        "g".into() => IonValue::Integer(7),
-        "00h".into() => IonValue::Integer(8),
-        "0h".into() => IonValue::Integer(8),
-        "i".into() => IonValue::Integer(9),
-        "j".into() => IonValue::Integer(10),
-        "k".into() => IonValue::Integer(11),
-        "00001l".into() => IonValue::Integer(12),
-        "00002l".into() => IonValue::Integer(12),
-        "10000l".into() => IonValue::Integer(12),
-        "l".into() => IonValue::Integer(12),
-        "m".into() => IonValue::Integer(13),
-        "n".into() => IonValue::Integer(14)
+        "000021i".to_string() => IonValue::Integer(9),
+        "012i".to_string() => IonValue::Integer(9),
+        "01d".to_string() => IonValue::Integer(4),
+        "01h".to_string() => IonValue::Integer(8),
+        "11n".to_string() => IonValue::Integer(14),
+        "12l".to_string() => IonValue::Integer(12),
+        "1d".to_string() => IonValue::Integer(4),
+        "21l".to_string() => IonValue::Integer(12),
+        "2h".to_string() => IonValue::Integer(8),
+        "aaa".to_string() => IonValue::Integer(1),
+        "aak".to_string() => IonValue::Integer(11),
+        "ae".to_string() => IonValue::Integer(5),
+        "b".to_string() => IonValue::Integer(2),
+        "bb".to_string() => IonValue::Integer(2),
+        "cb".to_string() => IonValue::Integer(2),
+        "c".to_string() => IonValue::Integer(3),
+        "d".to_string() => IonValue::Integer(4),
+        "9f".to_string() => IonValue::Integer(6),
+        "09f".to_string() => IonValue::Integer(6),
+        "g".to_string() => IonValue::Integer(7),
+        "00h".to_string() => IonValue::Integer(8),
+        "0h".to_string() => IonValue::Integer(8),
+        "i".to_string() => IonValue::Integer(9),
+        "j".to_string() => IonValue::Integer(10),
+        "k".to_string() => IonValue::Integer(11),
+        "00001l".to_string() => IonValue::Integer(12),
+        "00002l".to_string() => IonValue::Integer(12),
+        "10000l".to_string() => IonValue::Integer(12),
+        "l".to_string() => IonValue::Integer(12),
+        "m".to_string() => IonValue::Integer(13),
+        "n".to_string() => IonValue::Integer(14)
     ));
 
     let hash = IonHash::default_digest(&value);
 
     assert_eq!(b"\xc5\xb0\xb2\x7c\x35\x54\xec\x01\x4f\x66\x49\x6c\x6a\x84\x7f\x3b\xaa\xfe\x0d\x23\xe5\x5b\x91\x1a\xd3\x1f\xb8\x71\xce\xd7\xf7\x8b", &hash[..]);
 }
+
+#[test]
+fn ion_hash_struct_field_hashes() {
+    let value = IonValue::Struct(hashmap!(
+        "e".to_string() => IonValue::Integer(5),
+        "a".to_string() => IonValue::Integer(1)
+    ));
+
+    let field_hashes = IonHash::struct_field_hashes::<Sha256>(&value).unwrap();
+
+    assert_eq!(
+        field_hashes,
+        vec![
+            ("a".into(), b"\xdb\x0f\x50\x5f\xdd\x2e\xc4\xb9\xff\x1c\xa0\x19\xe2\xc4\x0b\x7f\x33\xff\x2c\x3e\x03\xe7\x60\xf8\x94\x7f\x40\x27\xca\xd4\xf3\xe0".to_vec()),
+            ("e".into(), b"\x81\xbb\xb5\xa6\xdc\x28\x43\x94\x4c\xb2\x6e\xd4\xa1\x9f\x39\x6e\x28\xbf\x91\xfd\x71\x09\x38\x10\xf8\x7f\xde\x3d\xe0\x05\xd9\x2f".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn ion_hash_struct_field_hashes_match_struct_hash() {
+    let value = IonValue::Struct(hashmap!(
+        "e".to_string() => IonValue::Integer(5)
+    ));
+
+    let field_hashes = IonHash::struct_field_hashes::<Sha256>(&value).unwrap();
+
+    // Same vector as ion_hash_simple_struct: the struct hash is the digest of
+    // the escaped field digests between the struct markers.
+    let mut buffer = vec![0x0B, 0xD0];
+    for byte in &field_hashes[0].1 {
+        if let 0x0B | 0x0C | 0x0E = byte {
+            buffer.push(0x0C);
+        }
+        buffer.push(*byte);
+    }
+    buffer.push(0x0E);
+
+    assert_eq!(b"\x51\xdb\x1a\xe9\x86\x74\x3b\x61\x43\xa8\x37\x43\x67\x99\xb0\x9e\x73\xf1\x0b\x2b\xa8\x29\x9d\xe2\x8c\x19\x37\x73\x6f\xbb\x63\xb8", &Sha256::digest(&buffer)[..]);
+}
+
+#[test]
+fn ion_hash_struct_field_hashes_keeps_ordered_struct_order() {
+    let value = IonValue::OrderedStruct(vec![
+        ("e".into(), IonValue::Integer(5)),
+        ("a".into(), IonValue::Integer(1)),
+    ]);
+
+    let names: Vec<String> = IonHash::struct_field_hashes::<Sha256>(&value)
+        .unwrap()
+        .into_iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    assert_eq!(names, vec!["e".to_string(), "a".to_string()]);
+    assert_eq!(
+        IonHash::struct_field_hashes::<Sha256>(&IonValue::Integer(5)),
+        None
+    );
+}
@@ -3,7 +3,7 @@ use sha2::Sha256;
 
 #[test]
 fn ion_hash_symbol() {
-    let value = IonValue::Symbol("Hola".to_string());
+    let value = IonValue::Symbol("Hola".into());
 
     let hash = IonHash::digest::<Sha256>(&value);
 
@@ -0,0 +1,84 @@
+use crate::{
+    IncrementalIonParser, IonEncoder, IonParserError, IonValue, ParsingError, SymbolToken,
+};
+
+#[test]
+fn incremental_parser_fed_one_byte_at_a_time() {
+    let value = IonValue::List(vec![
+        IonValue::Symbol("Hello".into()),
+        IonValue::String("World".to_string()),
+        IonValue::Integer(2021),
+    ]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    let mut parser = IncrementalIonParser::new();
+
+    for (index, byte) in bytes.iter().enumerate() {
+        assert!(matches!(
+            parser.consume_value(),
+            Err(IonParserError::Incomplete { .. })
+        ));
+
+        parser.feed(&[*byte][..]);
+
+        assert_eq!(parser.pending_bytes(), index + 1);
+    }
+
+    assert_eq!(parser.consume_value().unwrap().0, value);
+    assert_eq!(parser.pending_bytes(), 0);
+}
+
+#[test]
+fn incremental_parser_needed_hint() {
+    let mut parser = IncrementalIonParser::new();
+
+    // A string of 5 bytes with only 2 of them available
+    parser.feed(&b"\xe0\x01\0\xea\x85ab"[..]);
+
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::Incomplete { needed: 3 })
+    );
+
+    parser.feed(&b"cde\x21\x07"[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::String("abcde".to_string())
+    );
+    assert_eq!(parser.consume_value().unwrap().0, IonValue::Integer(7));
+}
+
+#[test]
+fn incremental_parser_keeps_real_errors() {
+    let mut parser = IncrementalIonParser::new();
+
+    parser.feed(&b"\xe0\x01\0\xea\x30"[..]);
+
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::BinaryError(ParsingError::NegativeZeroInt))
+    );
+}
+
+#[test]
+fn incremental_parser_waits_for_a_truncated_symbol_table() {
+    let ion_test = b"\xe0\x01\0\xea\xea\x81\x83\xd7\x87\xb5\x84Year\x71\x0a";
+
+    let mut parser = IncrementalIonParser::new();
+
+    parser.feed(&ion_test[..12]);
+    assert!(matches!(
+        parser.consume_value(),
+        Err(IonParserError::Incomplete { .. })
+    ));
+
+    parser.feed(&ion_test[12..]);
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::Symbol(SymbolToken::new(Some("Year".into()), Some(10)))
+    );
+}
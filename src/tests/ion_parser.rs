@@ -1,6 +1,22 @@
-use crate::{ion_parser::IonParser, ion_parser_types::IonValue};
+use crate::binary_encoder::encode_blob;
+use crate::binary_parser_types::ValueType;
+use crate::hashmap;
+use crate::ion_parser::{exceeds_decimal_digits, verify_int_sign, IonParser};
+use crate::ion_parser_types::{IonValue, NullIonValue};
+use crate::{
+    IonEncoder, IonParserConfig, IonParserError, IonVersion, ParseStats, ParsingError,
+    SymbolContext, SymbolToken, DEFAULT_MAX_DECIMAL_DIGITS,
+};
+use bigdecimal::BigDecimal;
+use chrono::DateTime;
 use env_logger::Env;
+use num_bigint::{BigInt, Sign};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[test]
 fn decode_full_ion() {
@@ -14,18 +30,1662 @@ fn decode_full_ion() {
 
     let mut expected = HashMap::new();
 
-    expected.insert("Model".to_string(), IonValue::String("CLK 350".to_string()));
-    expected.insert("Type".to_string(), IonValue::String("Sedan".to_string()));
-    expected.insert("Color".to_string(), IonValue::String("White".to_string()));
+    expected.insert("Model".into(), IonValue::String("CLK 350".to_string()));
+    expected.insert("Type".into(), IonValue::String("Sedan".to_string()));
+    expected.insert("Color".into(), IonValue::String("White".to_string()));
     expected.insert(
-        "VIN".to_string(),
+        "VIN".into(),
         IonValue::String("1C4RJFAG0FC625797".to_string()),
     );
-    expected.insert("Make".to_string(), IonValue::String("Mercedes".to_string()));
-    expected.insert("Year".to_string(), IonValue::Integer(2019));
+    expected.insert("Make".into(), IonValue::String("Mercedes".to_string()));
+    expected.insert("Year".into(), IonValue::Integer(2019));
 
     assert_eq!(
         parser.consume_value().unwrap().0,
         IonValue::Struct(expected)
     );
 }
+
+#[test]
+fn parser_with_config_max_depth() {
+    // [[[]]]
+    let ion_test = b"\xe0\x01\0\xea\xb2\xb1\xb0";
+
+    let config = IonParserConfig {
+        max_depth: Some(2),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::MaxDepthExceeded(2)
+    );
+
+    let config = IonParserConfig {
+        max_depth: Some(3),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::List(vec![IonValue::List(vec![IonValue::List(vec![])])])
+    );
+}
+
+#[test]
+fn parser_with_config_max_value_length() {
+    // "abc"
+    let ion_test = b"\xe0\x01\0\xea\x83abc";
+
+    let config = IonParserConfig {
+        max_value_length: Some(2),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::ValueTooLarge(2)
+    );
+
+    let config = IonParserConfig {
+        max_value_length: Some(3),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::String("abc".to_string())
+    );
+}
+
+#[test]
+fn max_value_length_is_checked_before_reading_the_value() {
+    // Blob declaring a length of 268435455 bytes without any content.
+    let ion_test = b"\xe0\x01\0\xea\xae\x7f\x7f\x7f\xff";
+
+    let config = IonParserConfig {
+        max_value_length: Some(1024),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::ValueTooLarge(1024)
+    );
+}
+
+#[test]
+fn decode_float_four_bytes_widens_f32() {
+    let ion_test = b"\xe0\x01\0\xea\x44\x3d\xcc\xcc\xcd";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let (value, consumed) = parser.consume_value().unwrap();
+
+    assert_eq!(value, IonValue::Float(f64::from(0.1f32)));
+    assert_ne!(value, IonValue::Float(0.1f64));
+    assert_eq!(consumed, 5);
+}
+
+#[test]
+fn consume_value_recovering_skips_bad_value() {
+    // "a", {$15: 1}, "b". The struct field symbol doesn't exist.
+    let ion_test = b"\xe0\x01\0\xea\x81a\xd3\x8f\x21\x01\x81b";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value_recovering().unwrap().0,
+        IonValue::String("a".to_string())
+    );
+    assert_eq!(
+        parser.consume_value_recovering().unwrap_err(),
+        (IonParserError::SymbolNotFoundInTable, true)
+    );
+    assert_eq!(
+        parser.consume_value_recovering().unwrap().0,
+        IonValue::String("b".to_string())
+    );
+    assert_eq!(
+        parser.consume_value_recovering().unwrap_err(),
+        (
+            IonParserError::BinaryError(ParsingError::NoDataToRead),
+            false
+        )
+    );
+}
+
+#[test]
+fn consume_value_recovering_skips_to_next_version_marker() {
+    // "a", an annotation with an invalid length, some garbage and then "b"
+    // after a new ion version marker.
+    let ion_test = b"\xe0\x01\0\xea\x81a\xe1\x00\x12\xe0\x01\0\xea\x81b";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let mut values = vec![];
+
+    loop {
+        match parser.consume_value_recovering() {
+            Ok((value, _)) => values.push(value),
+            Err((_, true)) => continue,
+            Err((_, false)) => break,
+        }
+    }
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::String("a".to_string()),
+            IonValue::String("b".to_string())
+        ]
+    );
+}
+
+#[test]
+fn consume_blob_streaming_matches_full_decode() {
+    let blob: Vec<u8> = (0..100_000u32).map(|n| (n % 251) as u8).collect();
+
+    let mut ion_test = b"\xe0\x01\0\xea".to_vec();
+    ion_test.append(&mut encode_blob(10, &blob));
+
+    let mut streamed = vec![];
+    let mut chunks = 0;
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let length = parser
+        .consume_blob_streaming(|chunk| {
+            chunks += 1;
+            streamed.extend_from_slice(chunk);
+        })
+        .unwrap();
+
+    assert_eq!(length, Some(blob.len()));
+    assert!(chunks > 1);
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(parser.consume_value().unwrap().0, IonValue::Blob(streamed));
+}
+
+#[test]
+fn consume_blob_streaming_rejects_other_types() {
+    let ion_test = b"\xe0\x01\0\xea\x81a";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_blob_streaming(|_| {}).unwrap_err(),
+        IonParserError::UnexpectedValueType(ValueType::String)
+    );
+}
+
+#[test]
+fn unimplemented_error_carries_value_type() {
+    let error = IonParserError::Unimplemented(ValueType::Decimal);
+
+    assert_eq!(error, IonParserError::Unimplemented(ValueType::Decimal));
+    assert_ne!(error, IonParserError::Unimplemented(ValueType::Timestamp));
+    assert_eq!(error.to_string(), "Ion type Decimal not implemented");
+}
+
+#[test]
+fn on_annotation_is_called_for_top_level_annotations() {
+    let annotated = IonValue::Annotation(
+        vec!["qldb_doc".into(), "v1".into()],
+        Box::new(IonValue::Integer(5)),
+    );
+
+    let nested = IonValue::List(vec![IonValue::Annotation(
+        vec!["inner".into()],
+        Box::new(IonValue::Bool(true)),
+    )]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(annotated.clone());
+    encoder.add(nested.clone());
+    let ion_test = encoder.encode();
+
+    let found = Rc::new(RefCell::new(vec![]));
+    let found_in_callback = found.clone();
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    parser.on_annotation(Box::new(move |names, value| {
+        found_in_callback
+            .borrow_mut()
+            .push((names.to_vec(), value.clone()));
+    }));
+
+    assert_eq!(parser.consume_all().unwrap(), vec![annotated, nested]);
+
+    assert_eq!(
+        *found.borrow(),
+        vec![(vec!["qldb_doc".into(), "v1".into()], IonValue::Integer(5))]
+    );
+}
+
+#[test]
+fn preserve_struct_order_round_trip() {
+    let ordered = IonValue::OrderedStruct(vec![
+        ("Year".into(), IonValue::Integer(2019)),
+        ("Make".into(), IonValue::String("Mercedes".to_string())),
+        (
+            "Engine".into(),
+            IonValue::OrderedStruct(vec![
+                ("Type".into(), IonValue::String("V6".to_string())),
+                ("Cylinders".into(), IonValue::Integer(6)),
+            ]),
+        ),
+        ("Color".into(), IonValue::String("White".to_string())),
+    ]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(ordered.clone());
+    let bytes = encoder.encode();
+
+    let config = IonParserConfig {
+        preserve_struct_order: true,
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&bytes[..], config);
+    let parsed = parser.consume_value().unwrap().0;
+
+    assert_eq!(parsed, ordered);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(parsed);
+
+    assert_eq!(encoder.encode(), bytes);
+}
+
+#[test]
+fn structs_are_not_ordered_by_default() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::OrderedStruct(vec![(
+        "Year".into(),
+        IonValue::Integer(2019),
+    )]));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    let mut expected = HashMap::new();
+    expected.insert("Year".into(), IonValue::Integer(2019));
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::Struct(expected)
+    );
+}
+
+#[test]
+fn skip_byte_order_mark_before_version_marker() {
+    let ion_test = b"\xef\xbb\xbf\xe0\x01\0\xea\x81a\x21\x05";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![IonValue::String("a".to_string()), IonValue::Integer(5)]
+    );
+}
+
+#[test]
+fn reject_stray_bytes_before_version_marker() {
+    let streams: [&[u8]; 3] = [
+        b"\xef\xbb\xbf\x81a",
+        b"\xef\x00\x00\xe0\x01\0\xea\x81a",
+        b"\xef\xbb",
+    ];
+
+    for ion_test in streams.iter() {
+        let mut parser = IonParser::new(*ion_test);
+
+        assert_eq!(
+            parser.consume_value().unwrap_err(),
+            IonParserError::BinaryError(ParsingError::InvalidStreamStart)
+        );
+    }
+}
+
+#[test]
+fn consume_list_iter_sums_a_big_list() {
+    let list = IonValue::List((0..100_000).map(IonValue::Integer).collect());
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(list);
+    encoder.add(IonValue::String("next".to_string()));
+    let ion_test = encoder.encode();
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let sum: i64 = parser
+        .consume_list_iter()
+        .map(|value| match value.unwrap() {
+            IonValue::Integer(value) => value,
+            value => panic!("Unexpected value {:?}", value),
+        })
+        .sum();
+
+    assert_eq!(sum, (0..100_000).sum::<i64>());
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::String("next".to_string())
+    );
+}
+
+#[test]
+fn consume_list_iter_skips_nop_padding() {
+    // [1, <nop pad of 2 bytes>, 2], null.list
+    let ion_test = b"\xe0\x01\0\xea\xb6\x21\x01\x01\xff\x21\x02\xbf";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let values: Vec<IonValue> = parser.consume_list_iter().map(Result::unwrap).collect();
+
+    assert_eq!(values, vec![IonValue::Integer(1), IonValue::Integer(2)]);
+    assert_eq!(parser.consume_list_iter().count(), 0);
+}
+
+#[test]
+fn consume_list_iter_rejects_other_types() {
+    let ion_test = b"\xe0\x01\0\xea\x81a";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let values: Vec<Result<IonValue, IonParserError>> = parser.consume_list_iter().collect();
+
+    assert_eq!(
+        values,
+        vec![Err(IonParserError::UnexpectedValueType(ValueType::String))]
+    );
+}
+
+#[test]
+fn consume_struct_ordered_keeps_order_and_symbol_ids() {
+    // Local symbols: $10 = "b", $11 = "a". Struct {b: 1, a: 2}
+    let ion_test = b"\xe0\x01\0\xea\xe9\x81\x83\xd6\x87\xb4\x81b\x81a\xd6\x8a\x21\x01\x8b\x21\x02";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let fields = parser.consume_struct_ordered().unwrap();
+
+    // SymbolToken equality only compares the text when both have it.
+    assert_eq!(
+        fields,
+        vec![
+            (SymbolToken::from_text("b"), IonValue::Integer(1)),
+            (SymbolToken::from_text("a"), IonValue::Integer(2)),
+        ]
+    );
+    assert_eq!(fields[0].0.id, Some(10));
+    assert_eq!(fields[1].0.id, Some(11));
+}
+
+#[test]
+fn child_exceeding_parent_struct_is_an_error() {
+    // Struct of 3 bytes with the field $4 ("name") holding a string that
+    // claims 5 bytes. The stream has enough bytes for the string.
+    let ion_test = b"\xe0\x01\0\xea\xd3\x84\x85abcde";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::ChildExceedsParent)
+    );
+}
+
+#[test]
+fn child_exceeding_parent_list_is_an_error() {
+    // [[1, 2]] where the outer list says 3 bytes and the inner one 4.
+    let ion_test = b"\xe0\x01\0\xea\xb3\xb4\x21\x01\x21\x02";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::ChildExceedsParent)
+    );
+}
+
+#[test]
+fn timestamp_too_short_is_an_invalid_type_descriptor() {
+    // A timestamp of 1 byte can't hold both offset and year.
+    let ion_test = b"\xe0\x01\0\xea\x61\x80\x21\x01";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::InvalidTypeDescriptor(0x61))
+    );
+}
+
+#[test]
+fn int_sign_mismatch_is_an_error() {
+    assert_eq!(verify_int_sign(&IonValue::Integer(5), false), Ok(()));
+    assert_eq!(verify_int_sign(&IonValue::Integer(-5), true), Ok(()));
+    assert_eq!(verify_int_sign(&IonValue::Integer(0), false), Ok(()));
+
+    assert_eq!(
+        verify_int_sign(&IonValue::Integer(-5), false),
+        Err(ParsingError::IntSignMismatch)
+    );
+    assert_eq!(
+        verify_int_sign(&IonValue::BigInteger(BigInt::from(u64::MAX)), true),
+        Err(ParsingError::IntSignMismatch)
+    );
+}
+
+#[test]
+fn decode_empty_string() {
+    let ion_test = b"\xe0\x01\0\xea\x80";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::String("".to_string())
+    );
+}
+
+#[test]
+fn decode_null_string() {
+    let ion_test = b"\xe0\x01\0\xea\x8f";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::Null(NullIonValue::String)
+    );
+}
+
+#[test]
+fn decode_empty_symbol() {
+    let ion_value = IonValue::Symbol("".into());
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(ion_value.clone());
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    assert_eq!(parser.consume_value().unwrap().0, ion_value);
+}
+
+fn parse_long_length_value(body: &[u8]) -> IonValue {
+    let mut ion_test = vec![0xe0, 0x01, 0x00, 0xea];
+    ion_test.extend_from_slice(body);
+
+    let mut parser = IonParser::new(&ion_test[..]);
+    let (value, _) = parser.consume_value().unwrap();
+
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::BinaryError(ParsingError::NoDataToRead))
+    );
+
+    value
+}
+
+#[test]
+fn decode_long_length_string() {
+    assert_eq!(
+        parse_long_length_value(b"\x8e\x8eabcdefghijklmn"),
+        IonValue::String("abcdefghijklmn".to_string())
+    );
+}
+
+#[test]
+fn decode_long_length_clob_and_blob() {
+    let bytes: Vec<u8> = (0..14).collect();
+
+    let mut clob = vec![0x9e, 0x8e];
+    clob.extend(&bytes);
+    assert_eq!(
+        parse_long_length_value(&clob),
+        IonValue::Clob(bytes.clone())
+    );
+
+    let mut blob = vec![0xae, 0x8e];
+    blob.extend(&bytes);
+    assert_eq!(parse_long_length_value(&blob), IonValue::Blob(bytes));
+}
+
+#[test]
+fn decode_long_length_list_and_sexp() {
+    let elements = b"\x21\x01\x21\x02\x21\x03\x21\x04\x21\x05\x21\x06\x21\x07";
+    let expected: Vec<IonValue> = (1..=7).map(IonValue::Integer).collect();
+
+    let mut list = vec![0xbe, 0x8e];
+    list.extend_from_slice(elements);
+    assert_eq!(
+        parse_long_length_value(&list),
+        IonValue::List(expected.clone())
+    );
+
+    let mut sexp = vec![0xce, 0x8e];
+    sexp.extend_from_slice(elements);
+    assert_eq!(parse_long_length_value(&sexp), IonValue::SExpr(expected));
+}
+
+#[test]
+fn decode_long_length_struct() {
+    // Uses the system symbols name, version, imports, symbols and max_id.
+    let ion_test = b"\xde\x8e\x84\x21\x01\x85\x21\x02\x86\x21\x03\x87\x21\x04\x88\x20";
+
+    let mut expected = HashMap::new();
+    expected.insert("name".into(), IonValue::Integer(1));
+    expected.insert("version".into(), IonValue::Integer(2));
+    expected.insert("imports".into(), IonValue::Integer(3));
+    expected.insert("symbols".into(), IonValue::Integer(4));
+    expected.insert("max_id".into(), IonValue::Integer(0));
+
+    assert_eq!(
+        parse_long_length_value(ion_test),
+        IonValue::Struct(expected)
+    );
+}
+
+#[test]
+fn decode_long_length_int_and_decimal() {
+    let mut int = vec![0x2e, 0x8e, 0x01];
+    int.extend(&[0; 13]);
+    assert_eq!(
+        parse_long_length_value(&int),
+        IonValue::BigInteger(BigInt::from(1) << 104usize)
+    );
+
+    let mut int = vec![0x3e, 0x8e, 0x01];
+    int.extend(&[0; 13]);
+    assert_eq!(
+        parse_long_length_value(&int),
+        IonValue::BigInteger(-(BigInt::from(1) << 104usize))
+    );
+
+    // Exponent 0 and a 13 bytes coefficient.
+    let mut decimal = vec![0x5e, 0x8e, 0x80, 0x01];
+    decimal.extend(&[0; 12]);
+    assert_eq!(
+        parse_long_length_value(&decimal),
+        IonValue::Decimal(BigDecimal::new(BigInt::from(1) << 96usize, 0))
+    );
+}
+
+#[test]
+fn decode_long_length_timestamp() {
+    // 2019-01-01T00:00:00.000000001Z with a 5 bytes fraction coefficient.
+    let ion_test = b"\x6e\x8e\x80\x0f\xe3\x81\x81\x80\x80\x80\xc9\x00\x00\x00\x00\x01";
+
+    assert_eq!(
+        parse_long_length_value(ion_test),
+        IonValue::DateTime(
+            DateTime::parse_from_rfc3339("2019-01-01T00:00:00.000000001+00:00").unwrap()
+        )
+    );
+}
+
+#[test]
+fn local_table_fields_are_resolved_with_the_system_table() {
+    // $ion_symbol_table::{symbols: ["Make", "Model"]} with the field
+    // `symbols` as the system symbol id 7, then the symbols $10 and $11.
+    let mut ion_test = vec![0xe0, 0x01, 0x00, 0xea];
+    ion_test.extend_from_slice(b"\xee\x90\x81\x83\xdd\x87\xbb\x84Make\x85Model");
+    ion_test.extend_from_slice(b"\x71\x0a\x71\x0b");
+
+    // $ion_symbol_table::{imports: $ion_symbol_table, symbols: ["Year"]},
+    // where `imports` is the id 6 and its value the id 3, so it appends to
+    // the current table. Then the symbols $10 and $12.
+    ion_test.extend_from_slice(b"\xed\x81\x83\xda\x86\x71\x03\x87\xb5\x84Year");
+    ion_test.extend_from_slice(b"\x71\x0a\x71\x0c");
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Symbol(SymbolToken::new(Some("Make".into()), Some(10))),
+            IonValue::Symbol(SymbolToken::new(Some("Model".into()), Some(11))),
+            IonValue::Symbol(SymbolToken::new(Some("Make".into()), Some(10))),
+            IonValue::Symbol(SymbolToken::new(Some("Year".into()), Some(12))),
+        ]
+    );
+}
+
+#[test]
+fn fields_with_the_same_text_in_import_and_local_table() {
+    // $ion_symbol_table::{
+    //     imports: [{name: "cars", version: 1, max_id: 1}],
+    //     symbols: ["Make"],
+    // }
+    // so "Make" is $10 (imported) and $11 (local). Then {$10: 1, $11: 2}
+    // and [$10, $11].
+    let mut ion_test = vec![0xe0, 0x01, 0x00, 0xea];
+    ion_test.extend_from_slice(b"\xee\x9a\x81\x83\xde\x96");
+    ion_test.extend_from_slice(b"\x86\xbd\xdc\x84\x84cars\x85\x21\x01\x88\x21\x01");
+    ion_test.extend_from_slice(b"\x87\xb5\x84Make");
+    ion_test.extend_from_slice(b"\xd6\x8a\x21\x01\x8b\x21\x02");
+    ion_test.extend_from_slice(b"\xb4\x71\x0a\x71\x0b");
+
+    let config = IonParserConfig {
+        preserve_struct_order: true,
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+
+    parser
+        .with_shared_table("cars".to_string(), 1, &["Make".to_string()])
+        .unwrap();
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::OrderedStruct(vec![
+                ("Make".into(), IonValue::Integer(1)),
+                ("Make".into(), IonValue::Integer(2)),
+            ]),
+            IonValue::List(vec![
+                IonValue::Symbol(SymbolToken::new(Some("Make".into()), Some(10))),
+                IonValue::Symbol(SymbolToken::new(Some("Make".into()), Some(11))),
+            ]),
+        ]
+    );
+}
+
+// Returns one byte per read, like a slow socket.
+struct OneByteReader<'a>(&'a [u8]);
+
+impl std::io::Read for OneByteReader<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        if self.0.is_empty() || buffer.is_empty() {
+            return Ok(0);
+        }
+
+        buffer[0] = self.0[0];
+        self.0 = &self.0[1..];
+
+        Ok(1)
+    }
+}
+
+#[test]
+fn decode_from_reader_with_partial_reads() {
+    let ion_value = IonValue::Struct(hashmap!(
+        "Model".to_string() => IonValue::String("CLK 350".to_string()),
+        "Price".to_string() => IonValue::Decimal(BigDecimal::new(BigInt::from(4_599_999), 2)),
+        "Sold".to_string() => IonValue::DateTime(
+            DateTime::parse_from_rfc3339("2019-03-14T15:09:26.25+01:00").unwrap()
+        ),
+        "Vin".to_string() => IonValue::Blob((0..40).collect())
+    ));
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(ion_value.clone());
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(OneByteReader(&bytes));
+
+    assert_eq!(parser.consume_all().unwrap(), vec![ion_value]);
+}
+
+#[test]
+fn parser_stats() {
+    // NOP padding, then [1, [2, (3)], "a", null, null.string]
+    let ion_test = b"\xe0\x01\0\xea\x00\xbc\x21\x01\xb5\x21\x02\xc2\x21\x03\x81a\x0f\x8f";
+
+    let config = IonParserConfig {
+        collect_stats: true,
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+    parser.consume_all().unwrap();
+
+    let stats = parser.stats();
+
+    let mut expected_counts = HashMap::new();
+    expected_counts.insert(ValueType::List, 2);
+    expected_counts.insert(ValueType::SExpr, 1);
+    expected_counts.insert(ValueType::PositiveInt, 3);
+    expected_counts.insert(ValueType::String, 2);
+    expected_counts.insert(ValueType::Null, 1);
+
+    assert_eq!(stats.value_counts, expected_counts);
+    assert_eq!(stats.max_depth, 3);
+    assert_eq!(stats.total_bytes, ion_test.len());
+    assert_eq!(stats.symbol_table_updates, 0);
+}
+
+#[test]
+fn parser_stats_count_symbol_tables() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Symbol("Model".into()));
+    let bytes = encoder.encode();
+
+    let config = IonParserConfig {
+        collect_stats: true,
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&bytes[..], config);
+    parser.consume_all().unwrap();
+
+    assert_eq!(parser.stats().symbol_table_updates, 1);
+    assert_eq!(parser.stats().value_counts[&ValueType::Symbol], 1);
+
+    let mut parser = IonParser::new(&bytes[..]);
+    parser.consume_all().unwrap();
+
+    assert_eq!(parser.stats(), &ParseStats::default());
+}
+
+#[test]
+fn decode_negative_zero_int_is_an_error() {
+    for ion_test in &[
+        &b"\xe0\x01\0\xea\x30"[..],
+        &b"\xe0\x01\0\xea\x32\x00\x00"[..],
+    ] {
+        let mut parser = IonParser::new(*ion_test);
+
+        assert_eq!(
+            parser.consume_value().unwrap_err(),
+            IonParserError::BinaryError(ParsingError::NegativeZeroInt)
+        );
+    }
+
+    let mut parser = IonParser::new(&b"\xe0\x01\0\xea\x20"[..]);
+
+    assert_eq!(parser.consume_value().unwrap().0, IonValue::Integer(0));
+}
+
+#[test]
+fn short_length_annotation_with_two_symbols() {
+    // name::version::7 (system symbols 4 and 5) followed by the int 0. The
+    // annotation body is 5 bytes: annot_length, two symbols and the int.
+    let ion_test = b"\xe0\x01\0\xea\xe5\x82\x84\x85\x21\x07\x20";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap(),
+        (
+            IonValue::Annotation(
+                vec!["name".into(), "version".into()],
+                Box::new(IonValue::Integer(7))
+            ),
+            6
+        )
+    );
+    assert_eq!(parser.consume_value().unwrap().0, IonValue::Integer(0));
+}
+
+#[test]
+fn short_length_annotation_shorter_than_its_value() {
+    // Same annotation, but the short length says 4 bytes while the wrapped
+    // value ends at byte 5.
+    let ion_test = b"\xe0\x01\0\xea\xe4\x82\x84\x85\x21\x07";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::BadAnnotationLength
+    );
+}
+
+fn parse_decimal_with_limit(
+    decimal: &str,
+    max_decimal_digits: Option<usize>,
+) -> Result<IonValue, IonParserError> {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Decimal(BigDecimal::from_str(decimal).unwrap()));
+    let ion_test = encoder.encode();
+
+    let config = IonParserConfig {
+        max_decimal_digits,
+        ..IonParserConfig::default()
+    };
+
+    IonParser::with_config(&ion_test[..], config)
+        .consume_value()
+        .map(|(value, _)| value)
+}
+
+#[test]
+fn decimal_over_max_digits_is_rejected() {
+    assert_eq!(
+        parse_decimal_with_limit("-12345678901.5", Some(10)).unwrap_err(),
+        IonParserError::DecimalTooLarge
+    );
+}
+
+#[test]
+fn decimal_under_max_digits_is_parsed() {
+    assert_eq!(
+        parse_decimal_with_limit("-123456789.5", Some(10)).unwrap(),
+        IonValue::Decimal(BigDecimal::from_str("-123456789.5").unwrap())
+    );
+    assert_eq!(
+        parse_decimal_with_limit("-12345678901.5", None).unwrap(),
+        IonValue::Decimal(BigDecimal::from_str("-12345678901.5").unwrap())
+    );
+}
+
+#[test]
+fn exceeds_decimal_digits_around_powers_of_ten() {
+    assert!(!exceeds_decimal_digits(&BigInt::from(0), 1));
+    assert!(exceeds_decimal_digits(&BigInt::from(0), 0));
+
+    let mut power = BigInt::from(1);
+
+    for digits in 1..200 {
+        for value in &[power.clone(), -power.clone(), power.clone() * 10 - 1] {
+            assert!(!exceeds_decimal_digits(value, digits));
+            assert!(exceeds_decimal_digits(value, digits - 1));
+        }
+
+        power *= 10;
+    }
+}
+
+#[test]
+fn decimal_with_huge_coefficient_is_rejected_before_reading_it() {
+    let coefficient_len = 4 * 1024 * 1024;
+    let length = coefficient_len + 1;
+
+    // Decimal with a VarUInt length, the exponent 0 and the coefficient.
+    let mut ion_test = vec![0xe0, 0x01, 0x00, 0xea, 0x5e];
+    ion_test.extend_from_slice(&[
+        (length >> 21) as u8 & 0x7f,
+        (length >> 14) as u8 & 0x7f,
+        (length >> 7) as u8 & 0x7f,
+        length as u8 & 0x7f | 0x80,
+        0x80,
+    ]);
+    ion_test.resize(ion_test.len() + coefficient_len, 0x7f);
+
+    let start = Instant::now();
+
+    assert_eq!(
+        IonParser::new(&ion_test[..]).consume_value().unwrap_err(),
+        IonParserError::DecimalTooLarge
+    );
+
+    // The bit length is enough to reject an already built coefficient too.
+    let coefficient = BigInt::from_bytes_be(Sign::Plus, &ion_test[10..]);
+    assert!(exceeds_decimal_digits(
+        &coefficient,
+        DEFAULT_MAX_DECIMAL_DIGITS
+    ));
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn ordered_struct_with_varuint_length() {
+    // 0xD1 means an ordered struct whose length follows as a VarUInt (6
+    // here), not a struct with 1 byte: {name: 1, version: 2}, then the int 3.
+    let ion_test = b"\xe0\x01\0\xea\xd1\x86\x84\x21\x01\x85\x21\x02\x21\x03";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap(),
+        (
+            IonValue::Struct(hashmap!(
+                "name".to_string() => IonValue::Integer(1),
+                "version".to_string() => IonValue::Integer(2)
+            )),
+            8
+        )
+    );
+    assert_eq!(parser.consume_value().unwrap().0, IonValue::Integer(3));
+}
+
+#[test]
+fn empty_ordered_struct_with_varuint_length() {
+    let ion_test = b"\xe0\x01\0\xea\xd1\x80";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::EmptyOrderedStruct
+    );
+}
+
+#[test]
+fn consume_value_with_annotations_splits_the_annotations() {
+    let annotated = IonValue::Annotation(
+        vec!["qldb_doc".into(), "v1".into()],
+        Box::new(IonValue::Integer(5)),
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(annotated);
+    encoder.add(IonValue::String("plain".to_string()));
+    let ion_test = encoder.encode();
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value_with_annotations().unwrap(),
+        (IonValue::Integer(5), vec!["qldb_doc".into(), "v1".into()])
+    );
+    assert_eq!(
+        parser.consume_value_with_annotations().unwrap(),
+        (IonValue::String("plain".to_string()), vec![])
+    );
+    assert_eq!(
+        parser.consume_value_with_annotations().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::NoDataToRead)
+    );
+}
+
+#[test]
+fn consume_all_applies_symbol_tables_between_values() {
+    // 1, then $ion_symbol_table::{symbols: ["Year"]} and then {$10: 2019}.
+    let mut ion_test = vec![0xe0, 0x01, 0x00, 0xea];
+    ion_test.extend_from_slice(b"\x21\x01");
+    ion_test.extend_from_slice(b"\xea\x81\x83\xd7\x87\xb5\x84Year");
+    ion_test.extend_from_slice(b"\xd4\x8a\x22\x07\xe3");
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Integer(1),
+            IonValue::Struct(hashmap!(
+                "Year".to_string() => IonValue::Integer(2019)
+            )),
+        ]
+    );
+}
+
+// Writes the symbol table and the symbols by id, as the encoder looks up
+// every symbol in its table, which is slow for this amount of them.
+fn encode_many_symbols(count: usize) -> (Vec<IonValue>, Vec<u8>) {
+    let texts: Vec<String> = (0..count)
+        .map(|index| format!("symbol_{}", index))
+        .collect();
+
+    let table = IonValue::Annotation(
+        vec!["$ion_symbol_table".into()],
+        Box::new(IonValue::Struct(hashmap!(
+            "symbols".to_string() => IonValue::List(
+                texts.iter().cloned().map(IonValue::String).collect()
+            )
+        ))),
+    );
+
+    // The encoder only writes the ids of system and imported symbols, so the
+    // list of ids is written by hand: a two bytes symbol for each one.
+    let ids: Vec<u8> = (0..count)
+        .flat_map(|index| {
+            let id = (10 + index) as u16;
+            vec![0x72, (id >> 8) as u8, id as u8]
+        })
+        .collect();
+
+    let mut encoder = IonEncoder::new();
+    let mut bytes = vec![0xe0, 0x01, 0x00, 0xea];
+    bytes.append(&mut encoder.encode_value(&table));
+    bytes.append(&mut encode_blob(11, &ids));
+
+    let symbols = texts
+        .into_iter()
+        .map(|text| IonValue::Symbol(SymbolToken::from_text(text)))
+        .collect();
+
+    (symbols, bytes)
+}
+
+#[test]
+fn decode_huge_symbol_table_from_partial_reads() {
+    let (symbols, bytes) = encode_many_symbols(50_000);
+
+    let config = IonParserConfig {
+        max_symbols: Some(50_000),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(OneByteReader(&bytes), config);
+
+    let values = match parser.consume_all().unwrap().pop() {
+        Some(IonValue::List(values)) => values,
+        value => panic!("Expected a list, found {:?}", value),
+    };
+
+    assert_eq!(values, symbols);
+
+    for (index, value) in values.iter().enumerate() {
+        match value {
+            IonValue::Symbol(symbol) => assert_eq!(symbol.id, Some(10 + index as u64)),
+            value => panic!("Expected a symbol, found {:?}", value),
+        }
+    }
+}
+
+#[test]
+fn decode_symbol_table_over_max_symbols() {
+    let (_, bytes) = encode_many_symbols(50_000);
+
+    let config = IonParserConfig {
+        max_symbols: Some(49_999),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(OneByteReader(&bytes), config);
+
+    assert_eq!(
+        parser.consume_all().unwrap_err(),
+        IonParserError::TooManySymbols(49_999)
+    );
+}
+
+#[test]
+fn max_symbols_counts_unknown_imports() {
+    // $ion_symbol_table::{imports: [{name: "cars", version: 1, max_id: 1000}]}
+    // without the table, so it would be filled with 1000 placeholders.
+    let mut ion_test = vec![0xe0, 0x01, 0x00, 0xea];
+    ion_test.extend_from_slice(b"\xee\x95\x81\x83\xde\x91");
+    ion_test.extend_from_slice(b"\x86\xbe\x8e\xdd\x84\x84cars\x85\x21\x01\x88\x22\x03\xe8");
+
+    let config = IonParserConfig {
+        max_symbols: Some(999),
+        ..IonParserConfig::default()
+    };
+
+    let mut parser = IonParser::with_config(&ion_test[..], config);
+
+    assert_eq!(
+        parser.consume_all().unwrap_err(),
+        IonParserError::TooManySymbols(999)
+    );
+}
+
+#[test]
+fn truncated_symbol_table_returns_specific_error() {
+    // $ion_symbol_table::{symbols: ["Year"]} cut inside "Year".
+    let ion_test = b"\xe0\x01\0\xea\xea\x81\x83\xd7\x87\xb5\x84Ye";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::TruncatedSymbolTable
+    );
+}
+
+#[test]
+fn truncated_value_after_symbol_table_is_not_a_truncated_table() {
+    // The same table, complete, and then a string cut in the middle.
+    let ion_test = b"\xe0\x01\0\xea\xea\x81\x83\xd7\x87\xb5\x84Year\x84Ye";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::NotEnoughtDataToRead(2))
+    );
+}
+
+#[test]
+fn parse_single_value() {
+    let ion_test = b"\xe0\x01\0\xea\x21\x05";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(parser.parse_single().unwrap(), IonValue::Integer(5));
+}
+
+#[test]
+fn parse_single_fails_with_trailing_data() {
+    let ion_test = b"\xe0\x01\0\xea\x21\x05\x21\x06";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.parse_single().unwrap_err(),
+        IonParserError::TrailingData
+    );
+}
+
+#[test]
+fn parse_single_allows_trailing_nop_padding() {
+    let ion_test = b"\xe0\x01\0\xea\x21\x05\x00\x02\xff\xff";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(parser.parse_single().unwrap(), IonValue::Integer(5));
+}
+
+#[test]
+fn appending_table_continues_ids_of_seeded_context() {
+    // The 9 system symbols ($ion ... $ion_shared_symbol_table) plus 11 local
+    // ones, so the context has 20 symbols: ids from 1 to 20.
+    let mut context = SymbolContext::new();
+
+    for index in 0..11 {
+        context.insert_symbol(&format!("seeded_{}", index));
+    }
+
+    // No IVM. $ion_symbol_table::{imports: $ion_symbol_table, symbols: ["new"]}
+    // followed by the symbol 21 and the symbol 20.
+    let ion_test = b"\xec\x81\x83\xd9\x86\x71\x03\x87\xb4\x83new\x71\x15\x71\x14";
+
+    let mut parser = IonParser::from_reader_with_context(&ion_test[..], context);
+    parser.assume_version(IonVersion::V1_0);
+
+    assert_eq!(
+        parser.consume_all().unwrap(),
+        vec![
+            IonValue::Symbol(SymbolToken::new(Some("new".into()), Some(21))),
+            IonValue::Symbol(SymbolToken::new(Some("seeded_10".into()), Some(20))),
+        ]
+    );
+}
+
+#[test]
+fn scratch_buffer_is_reused_between_parsers() {
+    let value = IonValue::List(vec![
+        IonValue::String("a".repeat(300)),
+        IonValue::String("short".to_string()),
+        IonValue::BigInteger(BigInt::from(u64::MAX) * BigInt::from(u64::MAX)),
+        IonValue::Integer(-12),
+    ]);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::with_scratch(&bytes[..], Vec::new());
+    assert_eq!(parser.consume_all().unwrap(), vec![value.clone()]);
+    let mut scratch = parser.into_scratch();
+
+    let capacity = scratch.capacity();
+    let pointer = scratch.as_ptr();
+
+    assert!(capacity >= 300);
+
+    for _ in 0..100 {
+        let mut parser = IonParser::with_scratch(&bytes[..], scratch);
+        assert_eq!(parser.consume_all().unwrap(), vec![value.clone()]);
+        scratch = parser.into_scratch();
+
+        assert_eq!(scratch.capacity(), capacity);
+        assert_eq!(scratch.as_ptr(), pointer);
+    }
+}
+
+#[test]
+fn timestamp_with_out_of_range_offset() {
+    // Offset of -2000 minutes, 2000-01-01T00:00Z
+    let ion_test = b"\xe0\x01\0\xea\x68\x4f\xd0\x0f\xd0\x81\x81\x80\x80";
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::InvalidTimestampOffset
+    );
+}
+
+#[test]
+fn timestamp_offset_boundaries() {
+    // 24:00 is not a valid offset, as the hours of the offset go up to 23.
+    for offset in [b"\x0b\xa0", b"\x4b\xa0"] {
+        let mut ion_test = b"\xe0\x01\0\xea\x68".to_vec();
+        ion_test.extend_from_slice(offset);
+        ion_test.extend_from_slice(b"\x0f\xd0\x81\x81\x80\x80");
+
+        let mut parser = IonParser::new(&ion_test[..]);
+
+        assert_eq!(
+            parser.consume_value().unwrap_err(),
+            IonParserError::InvalidTimestampOffset
+        );
+    }
+
+    for (offset, minutes) in [(b"\x0b\x9f", 1439), (b"\x4b\x9f", -1439)] {
+        let mut ion_test = b"\xe0\x01\0\xea\x68".to_vec();
+        ion_test.extend_from_slice(offset);
+        ion_test.extend_from_slice(b"\x0f\xd0\x81\x81\x80\x80");
+
+        let mut parser = IonParser::new(&ion_test[..]);
+        let value = parser.consume_value().unwrap().0;
+
+        assert_eq!(value.offset_minutes(), Some(minutes));
+    }
+}
+
+#[test]
+fn consume_list_into_reuses_the_vector() {
+    let first: Vec<IonValue> = (0..100).map(IonValue::Integer).collect();
+    let second: Vec<IonValue> = (0..50).map(|i| IonValue::Integer(-i)).collect();
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::List(first.clone()));
+    encoder.add(IonValue::List(second.clone()));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    let mut out = Vec::new();
+
+    parser.consume_list_into(&mut out).unwrap();
+    assert_eq!(out, first);
+
+    let capacity = out.capacity();
+    let pointer = out.as_ptr();
+
+    out.clear();
+    parser.consume_list_into(&mut out).unwrap();
+    assert_eq!(out, second);
+
+    assert_eq!(out.capacity(), capacity);
+    assert_eq!(out.as_ptr(), pointer);
+}
+
+// Timestamp at UTC of the given year (2 bytes VarUInt), month and day.
+fn encode_date(year: &[u8; 2], month: u8, day: u8) -> Vec<u8> {
+    let mut ion_test = b"\xe0\x01\0\xea\x65\x80".to_vec();
+    ion_test.extend_from_slice(year);
+    ion_test.push(0x80 | month);
+    ion_test.push(0x80 | day);
+    ion_test
+}
+
+#[test]
+fn timestamp_with_month_13() {
+    let ion_test = encode_date(b"\x0f\xd1", 13, 1);
+
+    assert_eq!(
+        IonParser::new(&ion_test[..]).consume_value().unwrap_err(),
+        IonParserError::InvalidTimestampComponent(2001, 13, 1)
+    );
+}
+
+#[test]
+fn timestamp_with_day_32() {
+    let ion_test = encode_date(b"\x0f\xd1", 1, 32);
+
+    assert_eq!(
+        IonParser::new(&ion_test[..]).consume_value().unwrap_err(),
+        IonParserError::InvalidTimestampComponent(2001, 1, 32)
+    );
+}
+
+#[test]
+fn timestamp_with_leap_day() {
+    let ion_test = encode_date(b"\x0f\xd1", 2, 29);
+
+    assert_eq!(
+        IonParser::new(&ion_test[..]).consume_value().unwrap_err(),
+        IonParserError::InvalidTimestampComponent(2001, 2, 29)
+    );
+
+    let ion_test = encode_date(b"\x0f\xd0", 2, 29);
+    let value = IonParser::new(&ion_test[..]).consume_value().unwrap().0;
+
+    assert_eq!(value.year(), Some(2000));
+    assert_eq!(value.month(), Some(2));
+    assert_eq!(value.day(), Some(29));
+}
+
+#[test]
+fn struct_cursor_stopped_early_skips_the_rest() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::OrderedStruct(vec![
+        ("first".into(), IonValue::Integer(1)),
+        (
+            "second".into(),
+            IonValue::List(vec![IonValue::String("nested".to_string())]),
+        ),
+        ("third".into(), IonValue::Integer(3)),
+    ]));
+    encoder.add(IonValue::String("next".to_string()));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    let first = parser.struct_cursor().next().unwrap().unwrap();
+    assert_eq!(first, ("first".into(), IonValue::Integer(1)));
+
+    assert_eq!(
+        parser.consume_value().unwrap().0,
+        IonValue::String("next".to_string())
+    );
+}
+
+#[test]
+fn struct_cursor_returns_all_fields_in_order() {
+    let fields = vec![
+        ("b".into(), IonValue::Integer(2)),
+        ("a".into(), IonValue::Integer(1)),
+    ];
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::OrderedStruct(fields.clone()));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    let values: Vec<(Arc<str>, IonValue)> = parser.struct_cursor().map(Result::unwrap).collect();
+
+    assert_eq!(values, fields);
+    assert_eq!(
+        parser.consume_value().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::NoDataToRead)
+    );
+}
+
+#[test]
+fn struct_cursor_dropped_after_field_without_text_skips_the_rest() {
+    // A local table whose only symbol ($10) has no text, a struct with the
+    // fields $10 and "name" ($4), and the integer 42.
+    let ion_test = [
+        0xe0, 0x01, 0x00, 0xea, 0xe6, 0x81, 0x83, 0xd3, 0x87, 0xb1, 0x0f, 0xd6, 0x8a, 0x21, 0x01,
+        0x84, 0x21, 0x02, 0x21, 0x2a,
+    ];
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    let mut cursor = parser.struct_cursor();
+    assert_eq!(
+        cursor.next(),
+        Some(Err(IonParserError::SymbolNotFoundInTable))
+    );
+    assert_eq!(cursor.next(), None);
+    drop(cursor);
+
+    assert_eq!(parser.consume_value().unwrap().0, IonValue::Integer(42));
+}
+
+#[test]
+fn list_cursor_stopped_early_skips_the_rest() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::List((0..10).map(IonValue::Integer).collect()));
+    encoder.add(IonValue::Integer(42));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    let values: Vec<IonValue> = parser.list_cursor().take(2).map(Result::unwrap).collect();
+    assert_eq!(values, vec![IonValue::Integer(0), IonValue::Integer(1)]);
+
+    assert_eq!(parser.consume_value().unwrap().0, IonValue::Integer(42));
+}
+
+#[test]
+fn consume_ivm_only_stream() {
+    let ion_test = [0xe0, 0x01, 0x00, 0xea];
+
+    assert_eq!(IonParser::new(&ion_test[..]).consume_all(), Ok(vec![]));
+
+    let mut parser = IonParser::new(&ion_test[..]);
+
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::BinaryError(ParsingError::NoDataToRead))
+    );
+    assert!(parser.stats().value_counts.is_empty());
+}
+
+#[test]
+fn consume_repeated_ivms_only_stream() {
+    let ion_test = [0xe0, 0x01, 0x00, 0xea, 0xe0, 0x01, 0x00, 0xea];
+
+    assert_eq!(IonParser::new(&ion_test[..]).consume_all(), Ok(vec![]));
+}
+
+fn encode_with_nop_before_table(values: &[IonValue]) -> Vec<u8> {
+    let mut encoder = IonEncoder::new();
+
+    for value in values {
+        encoder.add(value.clone());
+    }
+
+    let mut bytes = encoder.encode();
+    bytes.insert(4, 0x00);
+    bytes
+}
+
+#[test]
+fn symbol_table_bytes_can_be_replayed() {
+    let values = vec![
+        IonValue::Symbol("sedan".into()),
+        IonValue::Struct(hashmap!("Model".to_string() => IonValue::Symbol("coupe".into()))),
+    ];
+    let bytes = encode_with_nop_before_table(&values);
+
+    let tables = Rc::new(RefCell::new(vec![]));
+    let tables_in_callback = tables.clone();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    parser.on_symbol_table(Box::new(move |table| {
+        tables_in_callback.borrow_mut().push(table.to_vec());
+    }));
+
+    assert_eq!(parser.consume_all().unwrap(), values);
+
+    let tables = tables.borrow();
+    assert_eq!(tables.len(), 1);
+
+    // Only the table, without the version marker and the NOP padding.
+    let table = &tables[0];
+    assert_eq!(&bytes[5..5 + table.len()], &table[..]);
+
+    let mut replayed = vec![0xe0, 0x01, 0x00, 0xea];
+    replayed.extend_from_slice(table);
+    replayed.extend_from_slice(&bytes[5 + table.len()..]);
+
+    let mut replayed_parser = IonParser::new(&replayed[..]);
+
+    assert_eq!(replayed_parser.consume_all().unwrap(), values);
+    assert_eq!(
+        replayed_parser.into_context().dump_all_local_symbols(),
+        parser.into_context().dump_all_local_symbols()
+    );
+}
+
+#[test]
+fn symbol_table_bytes_before_a_cursor() {
+    let values = vec![IonValue::List(vec![IonValue::Symbol("sedan".into())])];
+    let bytes = encode_with_nop_before_table(&values);
+
+    let tables = Rc::new(RefCell::new(vec![]));
+    let tables_in_callback = tables.clone();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    parser.on_symbol_table(Box::new(move |table| {
+        tables_in_callback.borrow_mut().push(table.to_vec());
+    }));
+
+    let elements: Vec<IonValue> = parser.list_cursor().map(Result::unwrap).collect();
+
+    assert_eq!(elements, vec![IonValue::Symbol("sedan".into())]);
+
+    let tables = tables.borrow();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(&bytes[5..5 + tables[0].len()], &tables[0][..]);
+}
+
+#[test]
+fn consume_nested_structs_and_null_struct() {
+    let value = IonValue::Struct(hashmap!(
+        "car".to_string() => IonValue::Struct(hashmap!(
+            "Year".to_string() => IonValue::Integer(2019),
+            "Extras".to_string() => IonValue::Null(NullIonValue::Struct)
+        )),
+        "Model".to_string() => IonValue::String("CLK 350".to_string())
+    ));
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![value]
+    );
+
+    let null_struct = [0xe0, 0x01, 0x00, 0xea, 0xdf];
+
+    assert_eq!(
+        IonParser::new(&null_struct[..]).consume_all().unwrap(),
+        vec![IonValue::Null(NullIonValue::Struct)]
+    );
+}
+
+#[test]
+fn consume_lists_and_sexps() {
+    let ion_test = [
+        0xe0, 0x01, 0x00, 0xea, // IVM
+        0xb0, // []
+        0xc0, // ()
+        0xbf, // null.list
+        0xcf, // null.sexp
+        0xb4, 0xc3, 0x21, 0x01, 0xb0, // [(1 [])]
+    ];
+
+    assert_eq!(
+        IonParser::new(&ion_test[..]).consume_all().unwrap(),
+        vec![
+            IonValue::List(vec![]),
+            IonValue::SExpr(vec![]),
+            IonValue::Null(NullIonValue::List),
+            IonValue::Null(NullIonValue::SExpr),
+            IonValue::List(vec![IonValue::SExpr(vec![
+                IonValue::Integer(1),
+                IonValue::List(vec![])
+            ])]),
+        ]
+    );
+}
+
+#[test]
+fn consume_list_with_element_longer_than_the_list() {
+    // A list of 1 byte with an int of 2 bytes inside.
+    let ion_test = [0xe0, 0x01, 0x00, 0xea, 0xb1, 0x22, 0x01, 0x02];
+
+    assert_eq!(
+        IonParser::new(&ion_test[..]).consume_all(),
+        Err(IonParserError::BinaryError(
+            ParsingError::ChildExceedsParent
+        ))
+    );
+}
+
+#[test]
+fn consume_decimals() {
+    let ion_test = [
+        0xe0, 0x01, 0x00, 0xea, // IVM
+        0x50, // 0d0
+        0x52, 0xc2, 0x7b, // 1.23
+        0x52, 0xc1, 0xfb, // -12.3
+        0x52, 0x82, 0x05, // 5d2
+        0x52, 0x80, 0x80, // -0d0
+        0x51, 0xc3, // 0d-3
+    ];
+
+    // Negative zero is not supported, -0d0 is read as 0d0.
+    assert_eq!(
+        IonParser::new(&ion_test[..]).consume_all().unwrap(),
+        vec![
+            IonValue::Decimal(BigDecimal::from(0)),
+            IonValue::Decimal(BigDecimal::from_str("1.23").unwrap()),
+            IonValue::Decimal(BigDecimal::from_str("-12.3").unwrap()),
+            IonValue::Decimal(BigDecimal::from(500)),
+            IonValue::Decimal(BigDecimal::from(0)),
+            IonValue::Decimal(BigDecimal::from(0)),
+        ]
+    );
+}
+
+#[test]
+fn values_iterates_until_the_end_of_the_stream() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Integer(1));
+    encoder.add(IonValue::String("two".to_string()));
+    encoder.add(IonValue::Bool(true));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+    let values: Result<Vec<IonValue>, IonParserError> = parser.values().collect();
+
+    assert_eq!(
+        values,
+        Ok(vec![
+            IonValue::Integer(1),
+            IonValue::String("two".to_string()),
+            IonValue::Bool(true),
+        ])
+    );
+    assert_eq!(parser.values().next(), None);
+}
+
+#[test]
+fn values_stops_after_an_error() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Integer(1));
+    encoder.add(IonValue::String("truncated".to_string()));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..bytes.len() - 1]);
+    let mut values = parser.values();
+
+    assert_eq!(values.next(), Some(Ok(IonValue::Integer(1))));
+    assert!(matches!(values.next(), Some(Err(_))));
+    assert_eq!(values.next(), None);
+}
+
+#[test]
+fn decode_symbols_sharing_the_symbol_table_text() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::List(vec![
+        IonValue::Symbol("Make".into()),
+        IonValue::Symbol("Make".into()),
+        IonValue::Symbol("Make".into()),
+    ]));
+    let bytes = encoder.encode();
+
+    let mut parser = IonParser::new(&bytes[..]);
+
+    let texts: Vec<Arc<str>> = match parser.consume_value().unwrap().0 {
+        IonValue::List(values) => values
+            .into_iter()
+            .map(|value| match value {
+                IonValue::Symbol(symbol) => symbol.text.unwrap(),
+                value => panic!("Expected a symbol, found {:?}", value),
+            })
+            .collect(),
+        value => panic!("Expected a list, found {:?}", value),
+    };
+
+    assert!(texts.iter().all(|text| Arc::ptr_eq(text, &texts[0])));
+
+    // The three symbols and the one of the parser symbol table.
+    assert_eq!(Arc::strong_count(&texts[0]), 4);
+
+    drop(parser);
+
+    assert_eq!(Arc::strong_count(&texts[0]), 3);
+}
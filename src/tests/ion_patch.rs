@@ -0,0 +1,145 @@
+use crate::hashmap;
+use crate::{IonExtractionError, IonParserError, IonPatch, IonValue, PathSegment};
+
+fn build_document() -> IonValue {
+    IonValue::Struct(hashmap!(
+        "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+        "Owner".to_string() => IonValue::Struct(hashmap!(
+            "Name".to_string() => IonValue::String("Jane".to_string()),
+            "Phones".to_string() => IonValue::List(vec![
+                IonValue::String("555-0100".to_string()),
+                IonValue::String("555-0101".to_string()),
+            ])
+        ))
+    ))
+}
+
+#[test]
+fn apply_several_patches_to_a_nested_document() {
+    let mut document = build_document();
+
+    IonPatch::new()
+        .set(
+            &["Owner".into(), "Name".into()],
+            IonValue::String("John".to_string()),
+        )
+        .set(
+            &["Owner".into(), "Phones".into(), 1.into()],
+            IonValue::String("555-0199".to_string()),
+        )
+        .set(
+            &["Engine".into(), "Specs".into(), "Cylinders".into()],
+            IonValue::Integer(6),
+        )
+        .remove(&["Owner".into(), "Phones".into(), 0.into()])
+        .remove(&["VIN".into()])
+        .apply(&mut document)
+        .unwrap();
+
+    assert_eq!(
+        document,
+        IonValue::Struct(hashmap!(
+            "Owner".to_string() => IonValue::Struct(hashmap!(
+                "Name".to_string() => IonValue::String("John".to_string()),
+                "Phones".to_string() => IonValue::List(vec![
+                    IonValue::String("555-0199".to_string()),
+                ])
+            )),
+            "Engine".to_string() => IonValue::Struct(hashmap!(
+                "Specs".to_string() => IonValue::Struct(hashmap!(
+                    "Cylinders".to_string() => IonValue::Integer(6)
+                ))
+            ))
+        ))
+    );
+}
+
+#[test]
+fn set_with_an_empty_path_replaces_the_value() {
+    let mut document = build_document();
+
+    IonPatch::new()
+        .set(&[], IonValue::Integer(1))
+        .apply(&mut document)
+        .unwrap();
+
+    assert_eq!(document, IonValue::Integer(1));
+}
+
+#[test]
+fn set_keeps_ordered_struct_order() {
+    let mut document = IonValue::OrderedStruct(vec![
+        ("b".into(), IonValue::Integer(1)),
+        ("a".into(), IonValue::Integer(2)),
+    ]);
+
+    IonPatch::new()
+        .set(&["b".into()], IonValue::Integer(3))
+        .set(&["c".into(), "d".into()], IonValue::Integer(4))
+        .apply(&mut document)
+        .unwrap();
+
+    assert_eq!(
+        document,
+        IonValue::OrderedStruct(vec![
+            ("b".into(), IonValue::Integer(3)),
+            ("a".into(), IonValue::Integer(2)),
+            (
+                "c".into(),
+                IonValue::OrderedStruct(vec![("d".into(), IonValue::Integer(4))])
+            ),
+        ])
+    );
+}
+
+#[test]
+fn failed_patch_leaves_the_value_untouched() {
+    let mut document = build_document();
+
+    let result = IonPatch::new()
+        .remove(&["VIN".into()])
+        .remove(&["Owner".into(), "Phones".into(), 5.into()])
+        .apply(&mut document);
+
+    assert_eq!(result, Err(IonParserError::IndexOutOfBounds(5)));
+    assert_eq!(document, build_document());
+}
+
+#[test]
+fn patch_errors() {
+    let mut document = build_document();
+
+    assert_eq!(
+        IonPatch::new()
+            .remove(&["Model".into()])
+            .apply(&mut document),
+        Err(IonParserError::FieldNotFound("Model".to_string()))
+    );
+
+    assert_eq!(
+        IonPatch::new().remove(&[]).apply(&mut document),
+        Err(IonParserError::EmptyPath)
+    );
+
+    let result = IonPatch::new()
+        .set(&["VIN".into(), "Check".into()], IonValue::Bool(true))
+        .apply(&mut document);
+
+    assert!(matches!(
+        result,
+        Err(IonParserError::ValueExtractionFailure(
+            IonExtractionError::TypeNotSupported(IonValue::String(_))
+        ))
+    ));
+
+    let result = IonPatch::new()
+        .set(&[PathSegment::Index(0)], IonValue::Bool(true))
+        .apply(&mut document);
+
+    assert!(matches!(
+        result,
+        Err(IonParserError::ValueExtractionFailure(
+            IonExtractionError::TypeNotSupported(IonValue::Struct(_))
+        ))
+    ));
+}
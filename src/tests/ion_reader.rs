@@ -0,0 +1,51 @@
+use crate::hashmap;
+use crate::{read, IonEncoder, IonParserError, IonValue, ParsingError};
+
+#[test]
+fn read_detects_binary_without_losing_bytes() {
+    let values = vec![
+        IonValue::Struct(hashmap!(
+            "Make".to_string() => IonValue::String("Mercedes".to_string())
+        )),
+        IonValue::Integer(2019),
+    ];
+
+    let mut encoder = IonEncoder::new();
+
+    for value in &values {
+        encoder.add(value.clone());
+    }
+
+    let bytes = encoder.encode();
+
+    let mut reader = read(&bytes[..]).unwrap();
+
+    assert_eq!(reader.read_all().unwrap(), values);
+}
+
+#[test]
+fn read_detects_text() {
+    let text = b"{Make: \"Mercedes\"} 2019";
+
+    let mut reader = read(&text[..]).unwrap();
+
+    assert_eq!(
+        reader.read_all().unwrap(),
+        vec![
+            IonValue::Struct(hashmap!(
+                "Make".to_string() => IonValue::String("Mercedes".to_string())
+            )),
+            IonValue::Integer(2019),
+        ]
+    );
+}
+
+#[test]
+fn read_empty_and_truncated_binary() {
+    assert_eq!(read(&b""[..]).unwrap().read_all().unwrap(), vec![]);
+
+    assert_eq!(
+        read(&b"\xe0\x01"[..]).unwrap().next_value().unwrap_err(),
+        IonParserError::BinaryError(ParsingError::NotEnoughtDataToRead(1))
+    );
+}
@@ -0,0 +1,137 @@
+use crate::hashmap;
+use crate::{infer_schema, IonSchema, IonValue, NullIonValue, SchemaType, ValidationError};
+use std::collections::BTreeSet;
+
+fn types(types: &[SchemaType]) -> BTreeSet<SchemaType> {
+    types.iter().cloned().collect()
+}
+
+#[test]
+fn infer_schema_of_three_structs() {
+    let samples = vec![
+        IonValue::Struct(hashmap!(
+            "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+            "Year".to_string() => IonValue::Integer(2019),
+            "Owner".to_string() => IonValue::Struct(hashmap!(
+                "Name".to_string() => IonValue::String("Jane".to_string())
+            ))
+        )),
+        IonValue::Struct(hashmap!(
+            "VIN".to_string() => IonValue::String("1N4AL11D75C109151".to_string()),
+            "Year".to_string() => IonValue::Null(NullIonValue::Integer),
+            "Tags".to_string() => IonValue::List(vec![
+                IonValue::Symbol("used".into()),
+                IonValue::String("red".to_string()),
+            ])
+        )),
+        IonValue::Annotation(
+            vec!["car".into()],
+            Box::new(IonValue::Struct(hashmap!(
+                "VIN".to_string() => IonValue::String("KM8SRDHF6EU074761".to_string()),
+                "Year".to_string() => IonValue::Integer(2015),
+                "Owner".to_string() => IonValue::Struct(hashmap!(
+                    "Name".to_string() => IonValue::String("John".to_string()),
+                    "Phone".to_string() => IonValue::String("555-0100".to_string())
+                ))
+            ))),
+        ),
+    ];
+
+    let schema = infer_schema(&samples);
+
+    assert_eq!(schema.types, types(&[SchemaType::Struct]));
+    assert_eq!(schema.elements, None);
+
+    let names: Vec<&str> = schema.fields.keys().map(String::as_str).collect();
+    assert_eq!(names, vec!["Owner", "Tags", "VIN", "Year"]);
+
+    let vin = &schema.fields["VIN"];
+    assert!(vin.required);
+    assert_eq!(vin.schema.types, types(&[SchemaType::String]));
+
+    let year = &schema.fields["Year"];
+    assert!(year.required);
+    assert_eq!(
+        year.schema.types,
+        types(&[SchemaType::Null, SchemaType::Integer])
+    );
+
+    let tags = &schema.fields["Tags"];
+    assert!(!tags.required);
+    assert_eq!(tags.schema.types, types(&[SchemaType::List]));
+    assert_eq!(
+        tags.schema.elements.as_ref().unwrap().types,
+        types(&[SchemaType::String, SchemaType::Symbol])
+    );
+
+    let owner = &schema.fields["Owner"];
+    assert!(!owner.required);
+    assert!(owner.schema.fields["Name"].required);
+    assert!(!owner.schema.fields["Phone"].required);
+}
+
+#[test]
+fn infer_schema_counts_repeated_fields_once() {
+    let samples = vec![
+        IonValue::OrderedStruct(vec![
+            ("a".into(), IonValue::Integer(1)),
+            ("a".into(), IonValue::Bool(true)),
+        ]),
+        IonValue::Struct(hashmap!("b".to_string() => IonValue::Integer(1))),
+    ];
+
+    let schema = infer_schema(&samples);
+
+    assert!(!schema.fields["a"].required);
+    assert_eq!(
+        schema.fields["a"].schema.types,
+        types(&[SchemaType::Bool, SchemaType::Integer])
+    );
+    assert!(infer_schema(&[]).types.is_empty());
+}
+
+#[test]
+fn validate_reports_every_violation() {
+    let schema = IonSchema::new()
+        .required("VIN", SchemaType::String)
+        .required("Year", SchemaType::Integer)
+        .optional("Color", SchemaType::String);
+
+    let car = IonValue::Struct(hashmap!(
+        "Year".to_string() => IonValue::String("2019".to_string()),
+        "Color".to_string() => IonValue::Null(NullIonValue::String)
+    ));
+
+    assert_eq!(
+        schema.validate(&car),
+        Err(vec![
+            ValidationError::MissingField("VIN".to_string()),
+            ValidationError::WrongType {
+                field: "Year".to_string(),
+                expected: SchemaType::Integer,
+                found: SchemaType::String,
+            },
+        ])
+    );
+}
+
+#[test]
+fn validate_accepts_matching_struct() {
+    let schema = IonSchema::new()
+        .required("VIN", SchemaType::String)
+        .optional("Year", SchemaType::Integer);
+
+    let car = IonValue::Annotation(
+        vec!["car".into()],
+        Box::new(IonValue::Struct(hashmap!(
+            "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+            "Make".to_string() => IonValue::String("Mercedes".to_string())
+        ))),
+    );
+
+    assert_eq!(schema.validate(&car), Ok(()));
+    assert_eq!(
+        schema.validate(&IonValue::Integer(1)),
+        Err(vec![ValidationError::NotAStruct(SchemaType::Integer)])
+    );
+}
@@ -0,0 +1,195 @@
+use crate::hashmap;
+use crate::{
+    from_reader, to_value, to_vec, to_writer, Annotated, IonParser, IonParserConfig,
+    IonParserError, IonValue, NullIonValue, SymbolToken,
+};
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+
+enum Shape {
+    Point,
+    Circle(f64),
+}
+
+impl Serialize for Shape {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Shape::Point => serializer.serialize_unit_variant("Shape", 0, "Point"),
+            Shape::Circle(radius) => {
+                serializer.serialize_newtype_variant("Shape", 1, "Circle", radius)
+            }
+        }
+    }
+}
+
+struct Bytes(Vec<u8>);
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Car {
+    model: String,
+    year: i64,
+    engine: Engine,
+    color: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Engine {
+    cylinders: i64,
+    hybrid: bool,
+}
+
+#[test]
+fn serialize_map_as_struct() {
+    let mut map = HashMap::new();
+    map.insert("a".to_string(), vec![Some(1), None]);
+
+    let bytes = to_vec(&map).unwrap();
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::List(vec![
+                IonValue::Integer(1),
+                IonValue::Null(NullIonValue::Null),
+            ])
+        ))]
+    );
+
+    let value: HashMap<String, Vec<Option<i64>>> = from_reader(&bytes[..]).unwrap();
+
+    assert_eq!(value, map);
+}
+
+#[test]
+fn serialize_scalars() {
+    assert_eq!(
+        to_value(&("text", true, 1.5, u64::MAX)).unwrap(),
+        IonValue::List(vec![
+            IonValue::String("text".to_string()),
+            IonValue::Bool(true),
+            IonValue::Float(1.5),
+            IonValue::BigInteger(BigInt::from(u64::MAX)),
+        ])
+    );
+}
+
+#[test]
+fn serialize_bytes_as_blob() {
+    assert_eq!(
+        to_value(&Bytes(vec![1, 2, 3])).unwrap(),
+        IonValue::Blob(vec![1, 2, 3])
+    );
+}
+
+#[test]
+fn serialize_enums() {
+    assert_eq!(
+        to_value(&Shape::Point).unwrap(),
+        IonValue::Symbol(SymbolToken::from_text("Point"))
+    );
+    assert_eq!(
+        to_value(&Shape::Circle(2.0)).unwrap(),
+        IonValue::Struct(hashmap!("Circle".to_string() => IonValue::Float(2.0)))
+    );
+}
+
+#[test]
+fn serialize_annotations() {
+    let annotated = Annotated {
+        annotations: vec!["meters".to_string()],
+        value: 3,
+    };
+
+    let mut bytes = vec![];
+    to_writer(&mut bytes, &annotated).unwrap();
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![IonValue::Annotation(
+            vec!["meters".into()],
+            Box::new(IonValue::Integer(3))
+        )]
+    );
+
+    let value: Annotated<i64> = from_reader(&bytes[..]).unwrap();
+
+    assert_eq!(value, annotated);
+
+    let unannotated = Annotated {
+        annotations: vec![],
+        value: 3,
+    };
+
+    assert_eq!(to_value(&unannotated).unwrap(), IonValue::Integer(3));
+}
+
+#[test]
+fn serialize_map_with_non_string_keys() {
+    let mut map = HashMap::new();
+    map.insert(1, 2);
+
+    assert!(matches!(
+        to_value(&map),
+        Err(IonParserError::Serialization(_))
+    ));
+}
+
+#[test]
+fn serialize_derived_struct_in_field_order() {
+    let car = Car {
+        model: "CLK 350".to_string(),
+        year: 2007,
+        engine: Engine {
+            cylinders: 6,
+            hybrid: false,
+        },
+        color: None,
+        tags: vec!["coupe".to_string()],
+    };
+
+    let expected = IonValue::OrderedStruct(vec![
+        ("model".into(), IonValue::String("CLK 350".to_string())),
+        ("year".into(), IonValue::Integer(2007)),
+        (
+            "engine".into(),
+            IonValue::OrderedStruct(vec![
+                ("cylinders".into(), IonValue::Integer(6)),
+                ("hybrid".into(), IonValue::Bool(false)),
+            ]),
+        ),
+        ("color".into(), IonValue::Null(NullIonValue::Null)),
+        (
+            "tags".into(),
+            IonValue::List(vec![IonValue::String("coupe".to_string())]),
+        ),
+    ]);
+
+    assert_eq!(to_value(&car).unwrap(), expected);
+
+    let bytes = to_vec(&car).unwrap();
+
+    // The fields are written in order, so they are read back in order too.
+    let config = IonParserConfig {
+        preserve_struct_order: true,
+        ..Default::default()
+    };
+    let parsed = IonParser::with_config(&bytes[..], config)
+        .consume_value()
+        .unwrap()
+        .0;
+
+    assert_eq!(parsed, expected);
+    assert_eq!(to_vec(&car).unwrap(), bytes);
+
+    let value: Car = from_reader(&bytes[..]).unwrap();
+
+    assert_eq!(value, car);
+}
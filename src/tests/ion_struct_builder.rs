@@ -0,0 +1,50 @@
+use crate::hashmap;
+use crate::{IonValue, StructBuilder};
+
+#[test]
+fn struct_builder_normalizes_keys() {
+    let value = StructBuilder::new()
+        .with_key_normalizer(|key| key.trim().to_string())
+        .insert(" a ", IonValue::Integer(1))
+        .insert("b", IonValue::Integer(2))
+        .build();
+
+    assert_eq!(
+        value,
+        IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::Integer(1),
+            "b".to_string() => IonValue::Integer(2)
+        ))
+    );
+}
+
+#[test]
+fn struct_builder_normalizes_keys_inserted_before_the_normalizer() {
+    let value = StructBuilder::new()
+        .insert(" a ", IonValue::Integer(1))
+        .insert("a", IonValue::Integer(2))
+        .with_key_normalizer(|key| key.trim().to_string())
+        .build_ordered();
+
+    assert_eq!(
+        value,
+        IonValue::OrderedStruct(vec![
+            ("a".into(), IonValue::Integer(1)),
+            ("a".into(), IonValue::Integer(2)),
+        ])
+    );
+}
+
+#[test]
+fn struct_builder_without_normalizer_keeps_keys() {
+    let value = StructBuilder::new()
+        .insert(" a ", IonValue::Integer(1))
+        .build();
+
+    assert_eq!(
+        value,
+        IonValue::Struct(hashmap!(
+            " a ".to_string() => IonValue::Integer(1)
+        ))
+    );
+}
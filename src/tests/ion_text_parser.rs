@@ -0,0 +1,420 @@
+use crate::hashmap;
+use crate::{
+    IonParserConfig, IonParserError, IonTextParser, IonTextWriter, IonTimestamp, IonValue,
+    NullIonValue, ParsingError, SymbolToken, TimestampPrecision,
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate};
+use num_bigint::BigInt;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+fn parse(text: &str) -> Vec<IonValue> {
+    IonTextParser::new(text).consume_all().unwrap()
+}
+
+fn parse_one(text: &str) -> IonValue {
+    let mut values = parse(text);
+    assert_eq!(values.len(), 1);
+    values.remove(0)
+}
+
+#[test]
+fn parse_nulls_and_bools() {
+    assert_eq!(
+        parse("null null.int null.timestamp null.struct true false"),
+        vec![
+            IonValue::Null(NullIonValue::Null),
+            IonValue::Null(NullIonValue::Integer),
+            IonValue::Null(NullIonValue::DateTime),
+            IonValue::Null(NullIonValue::Struct),
+            IonValue::Bool(true),
+            IonValue::Bool(false),
+        ]
+    );
+}
+
+#[test]
+fn parse_integers() {
+    assert_eq!(
+        parse("0 -12 1_000 0x1F -0xff 0b101 9223372036854775808"),
+        vec![
+            IonValue::Integer(0),
+            IonValue::Integer(-12),
+            IonValue::Integer(1000),
+            IonValue::Integer(31),
+            IonValue::Integer(-255),
+            IonValue::Integer(5),
+            IonValue::BigInteger(BigInt::from_str("9223372036854775808").unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn parse_floats() {
+    let values = parse("1.5e0 -2E3 +inf -inf nan");
+
+    assert_eq!(values[0], IonValue::Float(1.5));
+    assert_eq!(values[1], IonValue::Float(-2000.0));
+    assert_eq!(values[2], IonValue::Float(f64::INFINITY));
+    assert_eq!(values[3], IonValue::Float(f64::NEG_INFINITY));
+    assert!(matches!(values[4], IonValue::Float(value) if value.is_nan()));
+}
+
+#[test]
+fn parse_decimals() {
+    assert_eq!(
+        parse("1.5 -0.25 2. 12d2 1.23D-4 1_000.5"),
+        vec![
+            IonValue::Decimal(BigDecimal::from_str("1.5").unwrap()),
+            IonValue::Decimal(BigDecimal::from_str("-0.25").unwrap()),
+            IonValue::Decimal(BigDecimal::from_str("2").unwrap()),
+            IonValue::Decimal(BigDecimal::from_str("12e2").unwrap()),
+            IonValue::Decimal(BigDecimal::from_str("1.23e-4").unwrap()),
+            IonValue::Decimal(BigDecimal::from_str("1000.5").unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn parse_invalid_numbers() {
+    for text in &["012", "1__0", "1_", "0x", "1.5x", "1e", "12abc"] {
+        assert!(
+            matches!(
+                IonTextParser::new(*text).consume_value(),
+                Err(IonParserError::InvalidText { position: 0, .. })
+            ),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn parse_timestamps() {
+    assert_eq!(
+        parse("2007-02-23T12:14:33.079-08:00 2007-02-23T12:14Z"),
+        vec![
+            IonValue::DateTime(
+                DateTime::parse_from_rfc3339("2007-02-23T12:14:33.079-08:00").unwrap()
+            ),
+            IonValue::DateTime(DateTime::parse_from_rfc3339("2007-02-23T12:14:00Z").unwrap()),
+        ]
+    );
+
+    assert_eq!(
+        parse("2007T 2007-02T 2007-02-23 2007-02-23T12:14-00:00"),
+        vec![
+            IonValue::DateTimeUnknownOffset(
+                NaiveDate::from_ymd_opt(2007, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            ),
+            IonValue::DateTimeUnknownOffset(
+                NaiveDate::from_ymd_opt(2007, 2, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            ),
+            IonValue::DateTimeUnknownOffset(
+                NaiveDate::from_ymd_opt(2007, 2, 23)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            ),
+            IonValue::DateTimeUnknownOffset(
+                NaiveDate::from_ymd_opt(2007, 2, 23)
+                    .unwrap()
+                    .and_hms_opt(12, 14, 0)
+                    .unwrap()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn parse_timestamps_keeping_the_precision() {
+    let config = IonParserConfig {
+        preserve_timestamp_precision: true,
+        ..IonParserConfig::default()
+    };
+
+    let values = IonTextParser::with_config("2007-02T 2007-02-23T12:14:33.50+01:00", config)
+        .consume_all()
+        .unwrap();
+
+    let month = NaiveDate::from_ymd_opt(2007, 2, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let fraction = NaiveDate::from_ymd_opt(2007, 2, 23)
+        .unwrap()
+        .and_hms_milli_opt(11, 14, 33, 500)
+        .unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            IonValue::Timestamp(IonTimestamp::new(month, None, TimestampPrecision::Month).unwrap()),
+            IonValue::Timestamp(
+                IonTimestamp::new(fraction, Some(60), TimestampPrecision::Fraction(2)).unwrap()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn parse_invalid_timestamps() {
+    assert_eq!(
+        IonTextParser::new("2001-02-29").consume_value(),
+        Err(IonParserError::InvalidTimestampComponent(2001, 2, 29))
+    );
+
+    for text in &[
+        "2007-",
+        "2007-02",
+        "2007-02-23T12Z",
+        "2007-02-23T12:14",
+        "0000T",
+    ] {
+        assert!(
+            matches!(
+                IonTextParser::new(*text).consume_value(),
+                Err(IonParserError::InvalidText { .. })
+            ),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn parse_strings() {
+    assert_eq!(
+        parse(r#""a\tb\"cé\U0001F600\x41" '''long ''' /* comment */ '''string''' "😀""#),
+        vec![
+            IonValue::String("a\tb\"c\u{e9}\u{1F600}A".to_string()),
+            IonValue::String("long string".to_string()),
+            IonValue::String("\u{1F600}".to_string()),
+        ]
+    );
+
+    assert!(matches!(
+        IonTextParser::new("\"unterminated").consume_value(),
+        Err(IonParserError::InvalidText { position: 13, .. })
+    ));
+}
+
+#[test]
+fn parse_symbols() {
+    assert_eq!(
+        parse("abc 'hello world' $ion ( a + b )"),
+        vec![
+            IonValue::Symbol(SymbolToken::from_text("abc")),
+            IonValue::Symbol(SymbolToken::from_text("hello world")),
+            IonValue::Symbol(SymbolToken::new(Some("$ion".into()), Some(1))),
+            IonValue::SExpr(vec![
+                IonValue::Symbol(SymbolToken::from_text("a")),
+                IonValue::Symbol(SymbolToken::from_text("+")),
+                IonValue::Symbol(SymbolToken::from_text("b")),
+            ]),
+        ]
+    );
+
+    assert!(matches!(
+        IonTextParser::new("[a + b]").consume_value(),
+        Err(IonParserError::InvalidText { position: 3, .. })
+    ));
+}
+
+#[test]
+fn parse_symbol_ids_with_local_symbol_table() {
+    let text = "$ion_1_0 $ion_symbol_table::{symbols: [\"first\", \"second\"]} $10 \
+                $ion_symbol_table::{imports: $ion_symbol_table, symbols: [\"third\"]} $12";
+
+    assert_eq!(
+        parse(text),
+        vec![
+            IonValue::Symbol(SymbolToken::from_text("first")),
+            IonValue::Symbol(SymbolToken::from_text("third")),
+        ]
+    );
+
+    assert_eq!(
+        IonTextParser::new("$10").consume_value(),
+        Err(IonParserError::SymbolIdNotDefined)
+    );
+}
+
+#[test]
+fn parse_symbol_ids_with_imports_of_unknown_shared_tables() {
+    let text = "$ion_symbol_table::{ \
+                    imports: [{name: \"cars\", version: 2, max_id: 3}, {name: \"$ion\"}], \
+                    symbols: [\"local\"] \
+                } $12 $13";
+
+    assert_eq!(
+        parse(text),
+        vec![
+            IonValue::Symbol(SymbolToken::from_id(12)),
+            IonValue::Symbol(SymbolToken::from_text("local")),
+        ]
+    );
+
+    assert!(matches!(
+        IonTextParser::new("$ion_symbol_table::{imports: [{name: \"cars\"}]} 1").consume_value(),
+        Err(IonParserError::InvalidText { .. })
+    ));
+
+    let config = IonParserConfig {
+        max_symbols: Some(100),
+        ..Default::default()
+    };
+    let mut parser = IonTextParser::with_config(
+        "$ion_symbol_table::{imports: [{name: \"cars\", max_id: 1000000000000}]} 1",
+        config,
+    );
+
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::TooManySymbols(100))
+    );
+}
+
+#[test]
+fn parse_quoted_version_marker_as_a_symbol() {
+    let table = "$ion_symbol_table::{symbols: [\"first\"]}";
+
+    assert_eq!(
+        parse(&format!("{} '$ion_1_0' $2 $10", table)),
+        vec![
+            IonValue::Symbol(SymbolToken::from_text("$ion_1_0")),
+            IonValue::Symbol(SymbolToken::from_text("$ion_1_0")),
+            IonValue::Symbol(SymbolToken::from_text("first")),
+        ]
+    );
+
+    // The unquoted one resets the symbol table.
+    assert_eq!(
+        IonTextParser::new(format!("{} $ion_1_0 $10", table)).consume_all(),
+        Err(IonParserError::SymbolIdNotDefined)
+    );
+}
+
+#[test]
+fn parse_annotations() {
+    assert_eq!(
+        parse_one("a::'b c'::  $ion::[1]"),
+        IonValue::Annotation(
+            vec!["a".into(), "b c".into(), "$ion".into()],
+            Box::new(IonValue::List(vec![IonValue::Integer(1)]))
+        )
+    );
+}
+
+#[test]
+fn parse_containers() {
+    let text = r#"
+        // Line comment
+        {
+            Model: "CLK 350",
+            'Type': Sedan,
+            "Year": 2019,
+            Tags: [a, (b c),],
+            Empty: {},
+        }
+    "#;
+
+    assert_eq!(
+        parse_one(text),
+        IonValue::Struct(hashmap!(
+            "Model".to_string() => IonValue::String("CLK 350".to_string()),
+            "Type".to_string() => IonValue::Symbol(SymbolToken::from_text("Sedan")),
+            "Year".to_string() => IonValue::Integer(2019),
+            "Tags".to_string() => IonValue::List(vec![
+                IonValue::Symbol(SymbolToken::from_text("a")),
+                IonValue::SExpr(vec![
+                    IonValue::Symbol(SymbolToken::from_text("b")),
+                    IonValue::Symbol(SymbolToken::from_text("c")),
+                ]),
+            ]),
+            "Empty".to_string() => IonValue::Struct(HashMap::new())
+        ))
+    );
+
+    let config = IonParserConfig {
+        preserve_struct_order: true,
+        ..IonParserConfig::default()
+    };
+
+    assert_eq!(
+        IonTextParser::with_config("{b: 1, a: 2}", config).consume_value(),
+        Ok(IonValue::OrderedStruct(vec![
+            ("b".into(), IonValue::Integer(1)),
+            ("a".into(), IonValue::Integer(2)),
+        ]))
+    );
+}
+
+#[test]
+fn parse_lobs() {
+    assert_eq!(
+        parse(r#"{{ aGVs bG8= }} {{}} {{ "clob\x00" }} {{ '''a''' '''b''' }}"#),
+        vec![
+            IonValue::Blob(b"hello".to_vec()),
+            IonValue::Blob(vec![]),
+            IonValue::Clob(b"clob\0".to_vec()),
+            IonValue::Clob(b"ab".to_vec()),
+        ]
+    );
+
+    assert!(matches!(
+        IonTextParser::new("{{ aGVsbG8 }}").consume_value(),
+        Err(IonParserError::InvalidText { .. })
+    ));
+}
+
+#[test]
+fn parse_max_depth() {
+    let config = IonParserConfig {
+        max_depth: Some(2),
+        ..IonParserConfig::default()
+    };
+
+    assert_eq!(
+        IonTextParser::with_config("[[[1]]]", config).consume_value(),
+        Err(IonParserError::MaxDepthExceeded(2))
+    );
+}
+
+#[test]
+fn parse_end_of_text() {
+    let mut parser = IonTextParser::new("1 // The end");
+
+    assert_eq!(parser.consume_value(), Ok(IonValue::Integer(1)));
+    assert_eq!(
+        parser.consume_value(),
+        Err(IonParserError::BinaryError(ParsingError::NoDataToRead))
+    );
+}
+
+#[test]
+fn parse_text_writer_output() {
+    let value = IonValue::List(vec![
+        IonValue::Annotation(vec!["a b".into()], Box::new(IonValue::Integer(-3))),
+        IonValue::String("line\nbreak".to_string()),
+        IonValue::Decimal(BigDecimal::from_str("1.50").unwrap()),
+        IonValue::Float(0.5),
+        IonValue::Blob(vec![0, 255, 7]),
+        IonValue::SExpr(vec![
+            IonValue::Symbol(SymbolToken::from_text("null")),
+            IonValue::Symbol(SymbolToken::from_text("<=")),
+        ]),
+        IonValue::DateTime(DateTime::parse_from_rfc3339("2021-05-01T10:20:30.5+02:00").unwrap()),
+    ]);
+
+    let text = IonTextWriter::new().write_value(&value);
+
+    assert_eq!(parse_one(&text), value);
+}
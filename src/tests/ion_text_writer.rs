@@ -0,0 +1,233 @@
+use crate::hashmap;
+use crate::{
+    IonParserConfig, IonTextParser, IonTextWriter, IonTextWriterOptions, IonValue, ListFormat,
+    NullIonValue, SymbolToken,
+};
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use std::str::FromStr;
+
+#[test]
+fn write_sexp_with_operator_symbols() {
+    let mut writer = IonTextWriter::new();
+
+    writer.add(IonValue::SExpr(vec![
+        IonValue::Symbol("a".into()),
+        IonValue::Symbol("+".into()),
+        IonValue::Symbol("b".into()),
+    ]));
+
+    assert_eq!(writer.write(), "(a + b)");
+}
+
+#[test]
+fn write_operator_symbols_quoted_outside_sexp() {
+    let writer = IonTextWriter::new();
+
+    let value = IonValue::List(vec![
+        IonValue::Symbol("a".into()),
+        IonValue::Symbol("+".into()),
+        IonValue::Annotation(vec!["<=".into()], Box::new(IonValue::Symbol("b".into()))),
+    ]);
+
+    assert_eq!(writer.write_value(&value), "[a, '+', '<='::b]");
+}
+
+#[test]
+fn write_symbols_that_need_quotes() {
+    let writer = IonTextWriter::new();
+
+    let value = IonValue::SExpr(vec![
+        IonValue::Symbol("null".into()),
+        IonValue::Symbol("$10".into()),
+        IonValue::Symbol("hello world".into()),
+        IonValue::Symbol("+a".into()),
+        IonValue::Symbol(SymbolToken::from_id(10)),
+    ]);
+
+    assert_eq!(
+        writer.write_value(&value),
+        "('null' '$10' 'hello world' '+a' $10)"
+    );
+}
+
+#[test]
+fn write_scalars() {
+    let mut writer = IonTextWriter::new();
+
+    writer.add(IonValue::Null(NullIonValue::Integer));
+    writer.add(IonValue::Bool(true));
+    writer.add(IonValue::Integer(-42));
+    writer.add(IonValue::Float(1.5));
+    writer.add(IonValue::Float(f64::NEG_INFINITY));
+    writer.add(IonValue::String("say \"hi\"\n".to_string()));
+    writer.add(IonValue::Clob(b"a\"\x00".to_vec()));
+    writer.add(IonValue::Blob(b"hello".to_vec()));
+
+    let expected = [
+        "null.int",
+        "true",
+        "-42",
+        "1.5e0",
+        "-inf",
+        "\"say \\\"hi\\\"\\n\"",
+        "{{\"a\\\"\\x00\"}}",
+        "{{aGVsbG8=}}",
+    ];
+
+    assert_eq!(writer.write(), expected.join("\n"));
+}
+
+#[test]
+fn write_struct_sorted_by_key() {
+    let writer = IonTextWriter::new();
+
+    let value = IonValue::Struct(hashmap!(
+        "Year".to_string() => IonValue::Integer(2019),
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "body type".to_string() => IonValue::Symbol("sedan".into())
+    ));
+
+    assert_eq!(
+        writer.write_value(&value),
+        "{Make: \"Mercedes\", Year: 2019, 'body type': sedan}"
+    );
+}
+
+#[test]
+fn write_decimals_in_canonical_form() {
+    let decimal = |text: &str| IonValue::Decimal(BigDecimal::from_str(text).unwrap());
+
+    assert_eq!(decimal("1.5e3").to_string(), "15d2");
+    assert_eq!(
+        IonValue::Decimal(BigDecimal::new(BigInt::from(100), 2)).to_string(),
+        "100d-2"
+    );
+    assert_eq!(decimal("-1.5").to_string(), "-15d-1");
+    assert_eq!(decimal("42").to_string(), "42.");
+    assert_eq!(decimal("0").to_string(), "0.");
+    // BigDecimal has no negative zero, so -0d0 turns into 0d0
+    assert_eq!(decimal("-0").to_string(), "0.");
+}
+
+fn write_with_list_format(list_format: ListFormat, value: &IonValue) -> String {
+    let writer = IonTextWriter::with_options(IonTextWriterOptions {
+        list_format,
+        ..IonTextWriterOptions::default()
+    });
+
+    writer.write_value(value)
+}
+
+#[test]
+fn write_list_formats() {
+    let value = IonValue::List(vec![
+        IonValue::Integer(1),
+        IonValue::Integer(2),
+        IonValue::Integer(3),
+    ]);
+
+    assert_eq!(
+        write_with_list_format(ListFormat::Compact, &value),
+        "[1,2,3]"
+    );
+    assert_eq!(
+        write_with_list_format(ListFormat::Spaced, &value),
+        "[1, 2, 3]"
+    );
+    assert_eq!(
+        write_with_list_format(ListFormat::MultiLine, &value),
+        "[\n  1,\n  2,\n  3\n]"
+    );
+    assert_eq!(IonTextWriter::new().write_value(&value), "[1, 2, 3]");
+}
+
+#[test]
+fn write_nested_multi_line_lists() {
+    let value = IonValue::List(vec![
+        IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+        IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::List(vec![IonValue::Integer(3)])
+        )),
+        IonValue::List(vec![]),
+        IonValue::SExpr(vec![IonValue::Integer(4), IonValue::Integer(5)]),
+    ]);
+
+    assert_eq!(
+        write_with_list_format(ListFormat::MultiLine, &value),
+        "[\n  [\n    1,\n    2\n  ],\n  {a: [\n      3\n    ]},\n  [],\n  (4 5)\n]"
+    );
+    assert_eq!(
+        write_with_list_format(ListFormat::Compact, &value),
+        "[[1,2],{a: [3]},[],(4 5)]"
+    );
+}
+
+fn layout_document() -> IonValue {
+    IonValue::OrderedStruct(vec![
+        ("name".into(), IonValue::String("CLK 350".to_string())),
+        (
+            "tags".into(),
+            IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+        ),
+        (
+            "expr".into(),
+            IonValue::Annotation(
+                vec!["op".into()],
+                Box::new(IonValue::SExpr(vec![
+                    IonValue::Symbol("+".into()),
+                    IonValue::Integer(3),
+                ])),
+            ),
+        ),
+        ("empty".into(), IonValue::OrderedStruct(vec![])),
+    ])
+}
+
+#[test]
+fn write_compact_layout() {
+    let writer = IonTextWriter::with_options(IonTextWriterOptions::compact());
+
+    assert_eq!(
+        writer.write_value(&layout_document()),
+        "{name:\"CLK 350\",tags:[1,2],expr:op::(+ 3),empty:{}}"
+    );
+}
+
+#[test]
+fn write_pretty_layout() {
+    let writer = IonTextWriter::with_options(IonTextWriterOptions::pretty());
+
+    assert_eq!(
+        writer.write_value(&layout_document()),
+        "{\n  name: \"CLK 350\",\n  tags: [\n    1,\n    2\n  ],\n  expr: op::(\n    +\n    3\n  ),\n  empty: {}\n}"
+    );
+}
+
+#[test]
+fn write_layouts_read_back() {
+    let document = layout_document();
+
+    for options in [
+        IonTextWriterOptions::default(),
+        IonTextWriterOptions::compact(),
+        IonTextWriterOptions::pretty(),
+    ] {
+        let mut writer = IonTextWriter::with_options(options);
+        writer.add(document.clone());
+        writer.add(IonValue::Integer(1));
+
+        let text = writer.write();
+        let config = IonParserConfig {
+            preserve_struct_order: true,
+            ..IonParserConfig::default()
+        };
+        let values = IonTextParser::with_config(text, config)
+            .consume_all()
+            .unwrap();
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0], document);
+        assert_eq!(values[1], IonValue::Integer(1));
+    }
+}
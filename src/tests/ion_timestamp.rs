@@ -0,0 +1,192 @@
+use crate::{
+    IonEncoder, IonHash, IonParser, IonParserConfig, IonParserError, IonTimestamp, IonValue,
+    TimestampPrecision,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use sha2::Sha256;
+
+fn utc(year: i32, month: u32, day: u32, hms: (u32, u32, u32), nano: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_nano_opt(hms.0, hms.1, hms.2, nano)
+        .unwrap()
+}
+
+fn timestamp(utc: NaiveDateTime, offset: Option<i32>, precision: TimestampPrecision) -> IonValue {
+    IonValue::Timestamp(IonTimestamp::new(utc, offset, precision).unwrap())
+}
+
+fn parse_preserving_precision(bytes: &[u8]) -> Vec<IonValue> {
+    let config = IonParserConfig {
+        preserve_timestamp_precision: true,
+        ..IonParserConfig::default()
+    };
+
+    IonParser::with_config(bytes, config).consume_all().unwrap()
+}
+
+fn build_timestamps() -> Vec<IonValue> {
+    vec![
+        timestamp(
+            utc(2007, 1, 1, (0, 0, 0), 0),
+            None,
+            TimestampPrecision::Year,
+        ),
+        timestamp(
+            utc(2007, 2, 1, (0, 0, 0), 0),
+            None,
+            TimestampPrecision::Month,
+        ),
+        timestamp(
+            utc(2007, 2, 23, (0, 0, 0), 0),
+            None,
+            TimestampPrecision::Day,
+        ),
+        timestamp(
+            utc(2007, 2, 23, (11, 14, 0), 0),
+            Some(60),
+            TimestampPrecision::Minute,
+        ),
+        timestamp(
+            utc(2007, 2, 23, (12, 14, 33), 0),
+            Some(0),
+            TimestampPrecision::Second,
+        ),
+        timestamp(
+            utc(2007, 2, 23, (12, 14, 33), 100_000_000),
+            Some(-300),
+            TimestampPrecision::Fraction(3),
+        ),
+        timestamp(
+            utc(2007, 2, 23, (12, 14, 33), 0),
+            None,
+            TimestampPrecision::Fraction(2),
+        ),
+    ]
+}
+
+#[test]
+fn timestamps_keep_their_precision() {
+    // 2007T
+    let bytes = [0xe0, 0x01, 0x00, 0xea, 0x63, 0xc0, 0x0f, 0xd7];
+
+    assert_eq!(
+        parse_preserving_precision(&bytes),
+        vec![timestamp(
+            utc(2007, 1, 1, (0, 0, 0), 0),
+            None,
+            TimestampPrecision::Year
+        )]
+    );
+
+    assert_eq!(
+        IonParser::new(&bytes[..]).consume_all().unwrap(),
+        vec![IonValue::DateTimeUnknownOffset(utc(
+            2007,
+            1,
+            1,
+            (0, 0, 0),
+            0
+        ))]
+    );
+}
+
+#[test]
+fn timestamps_round_trip_byte_for_byte() {
+    for value in build_timestamps() {
+        let mut encoder = IonEncoder::new();
+        encoder.add(value.clone());
+        let bytes = encoder.encode();
+
+        let parsed = parse_preserving_precision(&bytes);
+        assert_eq!(parsed, vec![value.clone()]);
+
+        let mut encoder = IonEncoder::new();
+        encoder.add(parsed[0].clone());
+        assert_eq!(encoder.encode(), bytes, "{:?}", value);
+    }
+}
+
+#[test]
+fn timestamps_with_other_precision_hash_differently() {
+    let timestamps = build_timestamps();
+    let year = &timestamps[0];
+    let same_instant = timestamp(utc(2007, 1, 1, (0, 0, 0), 0), None, TimestampPrecision::Day);
+
+    assert_ne!(year, &same_instant);
+    assert_ne!(
+        IonHash::digest::<Sha256>(year),
+        IonHash::digest::<Sha256>(&same_instant)
+    );
+    assert_eq!(
+        IonHash::digest::<Sha256>(year),
+        IonHash::digest::<Sha256>(&year.clone())
+    );
+}
+
+#[test]
+fn timestamp_fraction_keeps_its_digits() {
+    let value = &build_timestamps()[5];
+    let (coefficient, exponent) = value.fractional_seconds().unwrap().as_bigint_and_exponent();
+
+    assert_eq!((coefficient.to_string(), exponent), ("100".to_string(), 3));
+}
+
+#[test]
+fn timestamps_as_ion_text() {
+    let texts: Vec<String> = build_timestamps()
+        .iter()
+        .map(|value| value.to_ion_text().unwrap())
+        .collect();
+
+    assert_eq!(
+        texts,
+        vec![
+            "2007T",
+            "2007-02T",
+            "2007-02-23",
+            "2007-02-23T12:14+01:00",
+            "2007-02-23T12:14:33Z",
+            "2007-02-23T07:14:33.100-05:00",
+            "2007-02-23T12:14:33.00-00:00",
+        ]
+    );
+}
+
+#[test]
+fn timestamp_with_components_after_the_precision() {
+    assert_eq!(
+        IonTimestamp::new(
+            utc(2007, 2, 23, (12, 14, 0), 0),
+            None,
+            TimestampPrecision::Day
+        ),
+        Err(IonParserError::InvalidTimestampPrecision)
+    );
+    assert_eq!(
+        IonTimestamp::new(
+            utc(2007, 2, 23, (12, 14, 33), 0),
+            Some(0),
+            TimestampPrecision::Fraction(10)
+        ),
+        Err(IonParserError::InvalidTimestampPrecision)
+    );
+    assert_eq!(
+        IonTimestamp::new(
+            utc(2007, 2, 23, (12, 14, 0), 0),
+            Some(1440),
+            TimestampPrecision::Minute
+        ),
+        Err(IonParserError::InvalidTimestampOffset)
+    );
+}
+
+#[test]
+fn timestamp_from_datetime_uses_the_precision_without_data_loss() {
+    let value =
+        IonTimestamp::from(DateTime::parse_from_rfc3339("2007-02-23T12:14:33.25+01:00").unwrap());
+
+    assert_eq!(value.precision(), TimestampPrecision::Fraction(2));
+    assert_eq!(value.offset_minutes(), Some(60));
+    assert_eq!(value.utc(), &utc(2007, 2, 23, (11, 14, 33), 250_000_000));
+}
@@ -0,0 +1,1069 @@
+use crate::hashmap;
+use crate::{
+    FlattenOptions, IndexNotation, IonEncoder, IonHash, IonParser, IonParserConfig, IonParserError,
+    IonTimestamp, IonValue, NullIonValue, PathSegment, SymbolToken, TimestampPrecision,
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use num_bigint::BigInt;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn build_car_struct() -> IonValue {
+    IonValue::Struct(hashmap!(
+        "Model".to_string() => IonValue::String("CLK 350".to_string()),
+        "Type".to_string() => IonValue::String("Sedan".to_string()),
+        "Color".to_string() => IonValue::String("White".to_string()),
+        "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "Year".to_string() => IonValue::Integer(2019),
+        "Mileage".to_string() => IonValue::Null(NullIonValue::Integer)
+    ))
+}
+
+#[test]
+fn try_from_null_returns_unexpected_null() {
+    let value = IonValue::Null(NullIonValue::Integer);
+
+    assert_eq!(
+        i64::try_from(&value).unwrap_err(),
+        IonParserError::UnexpectedNull(NullIonValue::Integer)
+    );
+    assert_eq!(
+        String::try_from(IonValue::Null(NullIonValue::String)).unwrap_err(),
+        IonParserError::UnexpectedNull(NullIonValue::String)
+    );
+}
+
+#[test]
+fn get_as_struct_field() {
+    let value = build_car_struct();
+
+    assert_eq!(value.get_as::<i64>("Year"), Ok(2019));
+    assert_eq!(value.get_as::<String>("Model"), Ok("CLK 350".to_string()));
+    assert_eq!(
+        value.get_as::<i64>("Mileage"),
+        Err(IonParserError::UnexpectedNull(NullIonValue::Integer))
+    );
+    assert_eq!(
+        value.get_as::<i64>("Doors"),
+        Err(IonParserError::FieldNotFound("Doors".to_string()))
+    );
+}
+
+#[test]
+fn get_opt_as_struct_field() {
+    let value = build_car_struct();
+
+    assert_eq!(value.get_opt_as::<i64>("Year"), Ok(Some(2019)));
+    assert_eq!(value.get_opt_as::<i64>("Mileage"), Ok(None));
+    assert!(value.get_opt_as::<i64>("Model").is_err());
+}
+
+#[test]
+fn debug_truncates_big_blobs() {
+    let value = IonValue::Blob(vec![7; 1024 * 1024]);
+
+    let output = format!("{:?}", value);
+
+    assert!(output.starts_with("Blob([7, 7, 7, "));
+    assert!(output.ends_with("7, ...(1048576 bytes)])"));
+    assert!(output.len() < 300);
+}
+
+#[test]
+fn debug_truncates_long_strings_at_char_boundaries() {
+    let value = IonValue::List(vec![
+        IonValue::String("ñ".repeat(10)),
+        IonValue::Clob(b"short".to_vec()),
+    ]);
+
+    assert_eq!(
+        format!("{:.5?}", value),
+        "List([String(\"ññ\"...(20 bytes)), Clob([115, 104, 111, 114, 116])])"
+    );
+}
+
+#[test]
+fn debug_keeps_small_values_untouched() {
+    let value = IonValue::Annotation(
+        vec!["a".into()],
+        Box::new(IonValue::SExpr(vec![
+            IonValue::Float(1.5),
+            IonValue::String("hi".to_string()),
+        ])),
+    );
+
+    let expected = "Annotation([\"a\"], SExpr([Float(1.5), String(\"hi\")]))";
+
+    assert_eq!(format!("{:?}", value), expected);
+    assert_eq!(format!("{:.2?}", value), expected);
+}
+
+#[test]
+fn get_ci_ignores_key_case() {
+    let car = build_car_struct();
+
+    assert_eq!(
+        car.get_ci("model"),
+        Some(&IonValue::String("CLK 350".to_string()))
+    );
+    assert_eq!(car.get_ci("vin"), car.get_ci("VIN"));
+    assert_eq!(car.get_ci("Wheels"), None);
+    assert_eq!(IonValue::Integer(1).get_ci("model"), None);
+}
+
+#[test]
+fn get_ci_prefers_exact_match() {
+    let value = IonValue::OrderedStruct(vec![
+        ("MODEL".into(), IonValue::Integer(1)),
+        ("model".into(), IonValue::Integer(2)),
+        ("Model".into(), IonValue::Integer(3)),
+    ]);
+
+    assert_eq!(value.get_ci("Model"), Some(&IonValue::Integer(3)));
+    assert_eq!(value.get_ci("mOdEl"), Some(&IonValue::Integer(1)));
+}
+
+#[test]
+fn timestamp_from_unix_millis_round_trip() {
+    let millis = 1_600_000_000_123;
+
+    let value = IonValue::timestamp_from_unix_millis(millis).unwrap();
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    let decoded = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+
+    assert_eq!(decoded, value);
+
+    match decoded {
+        IonValue::DateTime(datetime) => assert_eq!(datetime.timestamp_millis(), millis),
+        value => panic!("Unexpected value {:?}", value),
+    }
+}
+
+#[test]
+fn timestamp_from_unix_seconds_with_offset() {
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+
+    let utc = IonValue::timestamp_from_unix_seconds(1_600_000_000).unwrap();
+    let local = IonValue::timestamp_from_unix_seconds_with_offset(1_600_000_000, offset).unwrap();
+
+    assert_eq!(
+        utc,
+        IonValue::DateTime(DateTime::parse_from_rfc3339("2020-09-13T12:26:40+00:00").unwrap())
+    );
+    assert_eq!(
+        local,
+        IonValue::DateTime(DateTime::parse_from_rfc3339("2020-09-13T14:26:40+02:00").unwrap())
+    );
+    assert_eq!(
+        IonValue::timestamp_from_unix_seconds(i64::MAX),
+        Err(IonParserError::DateValueTooBig)
+    );
+}
+
+#[test]
+fn merge_replaces_nested_structs() {
+    let base = IonValue::Struct(hashmap!(
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "Engine".to_string() => IonValue::Struct(hashmap!(
+            "Type".to_string() => IonValue::String("V6".to_string())
+        ))
+    ));
+
+    let overlay = IonValue::Struct(hashmap!(
+        "Engine".to_string() => IonValue::Struct(hashmap!(
+            "Cylinders".to_string() => IonValue::Integer(6)
+        ))
+    ));
+
+    assert_eq!(
+        base.merge(&overlay),
+        IonValue::Struct(hashmap!(
+            "Make".to_string() => IonValue::String("Mercedes".to_string()),
+            "Engine".to_string() => IonValue::Struct(hashmap!(
+                "Cylinders".to_string() => IonValue::Integer(6)
+            ))
+        ))
+    );
+}
+
+#[test]
+fn merge_deep_unions_nested_structs() {
+    let base = IonValue::Struct(hashmap!(
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "Colors".to_string() => IonValue::List(vec![IonValue::String("White".to_string())]),
+        "Engine".to_string() => IonValue::Struct(hashmap!(
+            "Type".to_string() => IonValue::String("V6".to_string()),
+            "Power".to_string() => IonValue::Integer(200)
+        ))
+    ));
+
+    let overlay = IonValue::Struct(hashmap!(
+        "Colors".to_string() => IonValue::List(vec![IonValue::String("Black".to_string())]),
+        "Engine".to_string() => IonValue::Struct(hashmap!(
+            "Cylinders".to_string() => IonValue::Integer(6),
+            "Power".to_string() => IonValue::Integer(250)
+        ))
+    ));
+
+    assert_eq!(
+        base.merge_deep(&overlay),
+        IonValue::Struct(hashmap!(
+            "Make".to_string() => IonValue::String("Mercedes".to_string()),
+            "Colors".to_string() => IonValue::List(vec![IonValue::String("Black".to_string())]),
+            "Engine".to_string() => IonValue::Struct(hashmap!(
+                "Type".to_string() => IonValue::String("V6".to_string()),
+                "Cylinders".to_string() => IonValue::Integer(6),
+                "Power".to_string() => IonValue::Integer(250)
+            ))
+        ))
+    );
+
+    assert_eq!(base.merge_deep(&IonValue::Integer(1)), IonValue::Integer(1));
+}
+
+#[test]
+fn merge_deep_keeps_ordered_struct_order() {
+    let base = IonValue::OrderedStruct(vec![
+        ("b".into(), IonValue::Integer(1)),
+        ("a".into(), IonValue::Integer(2)),
+    ]);
+
+    let overlay = IonValue::OrderedStruct(vec![
+        ("c".into(), IonValue::Integer(3)),
+        ("b".into(), IonValue::Integer(4)),
+    ]);
+
+    assert_eq!(
+        base.merge_deep(&overlay),
+        IonValue::OrderedStruct(vec![
+            ("b".into(), IonValue::Integer(4)),
+            ("a".into(), IonValue::Integer(2)),
+            ("c".into(), IonValue::Integer(3)),
+        ])
+    );
+}
+
+#[test]
+fn as_sorted_map_iterates_keys_in_order() {
+    let value = IonValue::Struct(hashmap!(
+        "Year".to_string() => IonValue::Integer(2019),
+        "Color".to_string() => IonValue::String("White".to_string()),
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string())
+    ));
+
+    let map = value.as_sorted_map().unwrap();
+
+    let keys: Vec<&str> = map.keys().map(|key| &**key).collect();
+
+    assert_eq!(keys, vec!["Color", "Make", "VIN", "Year"]);
+    assert_eq!(map["Year"], &IonValue::Integer(2019));
+
+    assert_eq!(IonValue::Integer(1).as_sorted_map(), None);
+}
+
+#[test]
+fn as_sorted_map_keeps_the_first_duplicated_key() {
+    let value = IonValue::OrderedStruct(vec![
+        ("b".into(), IonValue::Integer(1)),
+        ("a".into(), IonValue::Integer(2)),
+        ("b".into(), IonValue::Integer(3)),
+    ]);
+
+    let map = value.as_sorted_map().unwrap();
+
+    assert_eq!(
+        map.into_iter().collect::<Vec<(Arc<str>, &IonValue)>>(),
+        vec![
+            ("a".into(), &IonValue::Integer(2)),
+            ("b".into(), &IonValue::Integer(1)),
+        ]
+    );
+}
+
+#[test]
+fn get_path_follows_fields_and_indexes() {
+    let value = IonValue::Struct(hashmap!(
+        "Owner".to_string() => IonValue::OrderedStruct(vec![(
+            "Phones".into(),
+            IonValue::List(vec![
+                IonValue::String("555-0100".to_string()),
+                IonValue::SExpr(vec![IonValue::Integer(7)]),
+            ]),
+        )])
+    ));
+
+    let path: Vec<PathSegment> = vec!["Owner".into(), "Phones".into(), 1.into(), 0.into()];
+
+    assert_eq!(value.get_path(&path), Some(&IonValue::Integer(7)));
+    assert_eq!(value.get_path(&[]), Some(&value));
+    assert_eq!(value.get_path(&["Owner".into(), 0.into()]), None);
+    assert_eq!(value.get_path(&["Model".into()]), None);
+}
+
+#[test]
+fn from_binary_parses_one_value() {
+    let mut encoder = IonEncoder::new();
+    encoder.add(IonValue::Symbol("Model".into()));
+    let bytes = encoder.encode();
+
+    assert_eq!(
+        IonValue::from_binary(&bytes),
+        Ok(IonValue::Symbol("Model".into()))
+    );
+}
+
+#[test]
+fn from_binary_fails_with_trailing_data() {
+    let ion_test = b"\xe0\x01\0\xea\x21\x01\x21\x02";
+
+    assert_eq!(
+        IonValue::from_binary(ion_test),
+        Err(IonParserError::TrailingData)
+    );
+
+    let ion_test = b"\xe0\x01\0\xea\x21\x01\xf3";
+
+    assert_eq!(
+        IonValue::from_binary(ion_test),
+        Err(IonParserError::TrailingData)
+    );
+
+    assert_eq!(
+        IonValue::from_binary_all(b"\xe0\x01\0\xea\x21\x01\x21\x02"),
+        Ok(vec![IonValue::Integer(1), IonValue::Integer(2)])
+    );
+}
+
+fn parse_preserving_precision(bytes: &[u8]) -> IonValue {
+    let config = IonParserConfig {
+        preserve_timestamp_precision: true,
+        ..Default::default()
+    };
+
+    IonParser::with_config(bytes, config)
+        .consume_value()
+        .unwrap()
+        .0
+}
+
+#[test]
+fn timestamp_accessors_of_a_year_only_timestamp() {
+    // 2019T, which is encoded as 0x63 0x80 0x0f 0xe3
+    let value = parse_preserving_precision(b"\xe0\x01\0\xea\x63\x80\x0f\xe3");
+
+    assert_eq!(value.year(), Some(2019));
+    assert_eq!(value.month(), None);
+    assert_eq!(value.day(), None);
+    assert_eq!(value.hour(), None);
+    assert_eq!(value.minute(), None);
+    assert_eq!(value.second(), None);
+    assert_eq!(value.fractional_seconds(), None);
+    assert_eq!(value.offset_minutes(), Some(0));
+
+    // Without the precision the missing components get the defaults.
+    let value = IonValue::from_binary(b"\xe0\x01\0\xea\x63\x80\x0f\xe3").unwrap();
+
+    assert_eq!(value.year(), Some(2019));
+    assert_eq!(value.month(), Some(1));
+    assert_eq!(value.second(), Some(0));
+}
+
+#[test]
+fn timestamp_accessors_of_a_minute_precision_timestamp() {
+    let utc = NaiveDate::from_ymd_opt(2007, 2, 23)
+        .unwrap()
+        .and_hms_opt(12, 14, 0)
+        .unwrap();
+    let value =
+        IonValue::Timestamp(IonTimestamp::new(utc, Some(0), TimestampPrecision::Minute).unwrap());
+
+    assert_eq!(value.year(), Some(2007));
+    assert_eq!(value.month(), Some(2));
+    assert_eq!(value.day(), Some(23));
+    assert_eq!(value.hour(), Some(12));
+    assert_eq!(value.minute(), Some(14));
+    assert_eq!(value.second(), None);
+    assert_eq!(value.fractional_seconds(), None);
+    assert_eq!(value.offset_minutes(), Some(0));
+}
+
+#[test]
+fn timestamp_accessors_of_a_full_precision_timestamp() {
+    let value =
+        IonValue::DateTime(DateTime::parse_from_rfc3339("2021-03-14T15:09:26.25-05:30").unwrap());
+
+    assert_eq!(value.year(), Some(2021));
+    assert_eq!(value.month(), Some(3));
+    assert_eq!(value.day(), Some(14));
+    assert_eq!(value.hour(), Some(15));
+    assert_eq!(value.minute(), Some(9));
+    assert_eq!(value.second(), Some(26));
+    assert_eq!(
+        value.fractional_seconds(),
+        Some(BigDecimal::from_str("0.25").unwrap())
+    );
+    assert_eq!(value.offset_minutes(), Some(-330));
+
+    assert_eq!(IonValue::Integer(2021).year(), None);
+    assert_eq!(IonValue::Integer(2021).fractional_seconds(), None);
+}
+
+#[test]
+fn timestamp_to_rfc3339_and_ion_text() {
+    let value =
+        IonValue::DateTime(DateTime::parse_from_rfc3339("2021-03-14T15:09:26.25-05:30").unwrap());
+
+    assert_eq!(
+        value.to_rfc3339(),
+        Some("2021-03-14T15:09:26.250-05:30".to_string())
+    );
+    assert_eq!(
+        value.to_ion_text(),
+        Some("2021-03-14T15:09:26.250-05:30".to_string())
+    );
+
+    assert_eq!(IonValue::Integer(2021).to_rfc3339(), None);
+    assert_eq!(IonValue::Integer(2021).to_ion_text(), None);
+}
+
+#[test]
+fn timestamp_to_rfc3339_of_a_year_only_timestamp() {
+    let value = parse_preserving_precision(b"\xe0\x01\0\xea\x63\x80\x0f\xe3");
+
+    assert_eq!(value.to_rfc3339(), None);
+    assert_eq!(value.to_ion_text(), Some("2019T".to_string()));
+
+    // 2019T gets the defaults for the missing components when parsed
+    // without its precision, so both have every component.
+    let value = IonValue::from_binary(b"\xe0\x01\0\xea\x63\x80\x0f\xe3").unwrap();
+
+    assert_eq!(
+        value.to_rfc3339(),
+        Some("2019-01-01T00:00:00+00:00".to_string())
+    );
+    assert_eq!(
+        value.to_ion_text(),
+        Some("2019-01-01T00:00:00+00:00".to_string())
+    );
+}
+
+fn build_flatten_document() -> IonValue {
+    IonValue::Struct(hashmap!(
+        "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+        "Owner".to_string() => IonValue::Struct(hashmap!(
+            "Name".to_string() => IonValue::String("Jane".to_string()),
+            "Phone".to_string() => IonValue::Null(NullIonValue::String)
+        )),
+        "Items".to_string() => IonValue::List(vec![
+            IonValue::Annotation(vec!["usd".into()], Box::new(IonValue::Integer(10))),
+            IonValue::Struct(hashmap!(
+                "Name".to_string() => IonValue::String("Mats".to_string())
+            )),
+        ]),
+        "Empty".to_string() => IonValue::List(vec![])
+    ))
+}
+
+#[test]
+fn flatten_nested_document() {
+    assert_eq!(
+        build_flatten_document().flatten(),
+        hashmap!(
+            "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+            "Owner.Name".to_string() => IonValue::String("Jane".to_string()),
+            "Owner.Phone".to_string() => IonValue::Null(NullIonValue::String),
+            "Items.0".to_string() => IonValue::Integer(10),
+            "Items.1.Name".to_string() => IonValue::String("Mats".to_string())
+        )
+    );
+}
+
+#[test]
+fn flatten_with_options() {
+    let options = FlattenOptions {
+        separator: "/".to_string(),
+        index_notation: IndexNotation::Brackets,
+    };
+
+    assert_eq!(
+        build_flatten_document().flatten_with(&options),
+        hashmap!(
+            "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+            "Owner/Name".to_string() => IonValue::String("Jane".to_string()),
+            "Owner/Phone".to_string() => IonValue::Null(NullIonValue::String),
+            "Items[0]".to_string() => IonValue::Integer(10),
+            "Items[1]/Name".to_string() => IonValue::String("Mats".to_string())
+        )
+    );
+
+    let repeated = IonValue::OrderedStruct(vec![
+        ("a".into(), IonValue::Integer(1)),
+        ("a".into(), IonValue::Integer(2)),
+    ]);
+
+    assert_eq!(
+        repeated.flatten(),
+        hashmap!("a".to_string() => IonValue::Integer(1))
+    );
+    assert_eq!(
+        IonValue::Integer(1).flatten(),
+        hashmap!("".to_string() => IonValue::Integer(1))
+    );
+}
+
+#[test]
+fn from_pairs_keeps_the_order() {
+    let pairs = vec![
+        ("Year".to_string(), IonValue::Integer(2019)),
+        ("Make".to_string(), IonValue::String("Mercedes".to_string())),
+        ("Color".to_string(), IonValue::String("White".to_string())),
+    ];
+
+    let value = IonValue::from_pairs(pairs.clone());
+
+    assert_eq!(
+        value,
+        IonValue::OrderedStruct(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect()
+        )
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    let config = IonParserConfig {
+        preserve_struct_order: true,
+        ..IonParserConfig::default()
+    };
+
+    let parsed = IonParser::with_config(&bytes[..], config)
+        .consume_value()
+        .unwrap()
+        .0;
+
+    assert_eq!(parsed, value);
+}
+
+#[test]
+fn reorder_to_match_follows_template_order() {
+    let value = IonValue::Struct(hashmap!(
+        "VIN".to_string() => IonValue::String("1C4RJFAG0FC625797".to_string()),
+        "Year".to_string() => IonValue::Integer(2019),
+        "Make".to_string() => IonValue::String("Mercedes".to_string()),
+        "Color".to_string() => IonValue::String("White".to_string()),
+        "Owner".to_string() => IonValue::Struct(hashmap!(
+            "Phone".to_string() => IonValue::String("555-0100".to_string()),
+            "Name".to_string() => IonValue::String("Jane".to_string())
+        ))
+    ));
+
+    let template = IonValue::from_pairs(vec![
+        ("Make".to_string(), IonValue::Null(NullIonValue::Null)),
+        (
+            "Owner".to_string(),
+            IonValue::from_pairs(vec![
+                ("Name".to_string(), IonValue::Null(NullIonValue::Null)),
+                ("Phone".to_string(), IonValue::Null(NullIonValue::Null)),
+            ]),
+        ),
+        ("Year".to_string(), IonValue::Null(NullIonValue::Null)),
+        ("Missing".to_string(), IonValue::Null(NullIonValue::Null)),
+    ]);
+
+    let reordered = value.reorder_to_match(&template);
+
+    assert_eq!(
+        reordered,
+        IonValue::from_pairs(vec![
+            ("Make".to_string(), IonValue::String("Mercedes".to_string())),
+            (
+                "Owner".to_string(),
+                IonValue::from_pairs(vec![
+                    ("Name".to_string(), IonValue::String("Jane".to_string())),
+                    (
+                        "Phone".to_string(),
+                        IonValue::String("555-0100".to_string())
+                    ),
+                ]),
+            ),
+            ("Year".to_string(), IonValue::Integer(2019)),
+            ("Color".to_string(), IonValue::String("White".to_string())),
+            (
+                "VIN".to_string(),
+                IonValue::String("1C4RJFAG0FC625797".to_string())
+            ),
+        ])
+    );
+}
+
+#[test]
+fn reorder_to_match_reorders_inside_lists() {
+    let value = IonValue::List(vec![
+        IonValue::from_pairs(vec![
+            ("b".to_string(), IonValue::Integer(2)),
+            ("a".to_string(), IonValue::Integer(1)),
+        ]),
+        IonValue::from_pairs(vec![
+            ("b".to_string(), IonValue::Integer(4)),
+            ("a".to_string(), IonValue::Integer(3)),
+        ]),
+    ]);
+
+    let template = IonValue::List(vec![IonValue::from_pairs(vec![
+        ("a".to_string(), IonValue::Null(NullIonValue::Null)),
+        ("b".to_string(), IonValue::Null(NullIonValue::Null)),
+    ])]);
+
+    assert_eq!(
+        value.reorder_to_match(&template),
+        IonValue::List(vec![
+            IonValue::from_pairs(vec![
+                ("a".to_string(), IonValue::Integer(1)),
+                ("b".to_string(), IonValue::Integer(2)),
+            ]),
+            IonValue::from_pairs(vec![
+                ("b".to_string(), IonValue::Integer(4)),
+                ("a".to_string(), IonValue::Integer(3)),
+            ]),
+        ])
+    );
+}
+
+#[test]
+fn ct_eq_matches_eq() {
+    let values = vec![
+        IonValue::Blob(vec![1, 2, 3, 4]),
+        IonValue::Blob(vec![1, 2, 3, 5]),
+        IonValue::Blob(vec![1, 2, 3]),
+        IonValue::Blob(vec![]),
+        IonValue::Clob(vec![1, 2, 3, 4]),
+        IonValue::String("secret".to_string()),
+        IonValue::String("secreT".to_string()),
+        IonValue::Integer(4),
+    ];
+
+    for a in &values {
+        for b in &values {
+            assert_eq!(a.ct_eq(b), a == b, "{:?} and {:?}", a, b);
+        }
+    }
+
+    assert!(IonValue::Blob(vec![1, 2, 3, 4]).ct_eq(&IonValue::Blob(vec![1, 2, 3, 4])));
+}
+
+fn build_select_document() -> IonValue {
+    IonValue::Struct(hashmap!(
+        "owner".to_string() => IonValue::Struct(hashmap!(
+            "name".to_string() => IonValue::String("Jane".to_string()),
+            "first.name".to_string() => IonValue::String("J".to_string()),
+            "items".to_string() => IonValue::List(vec![
+                IonValue::Struct(hashmap!(
+                    "name".to_string() => IonValue::String("Mats".to_string())
+                )),
+                IonValue::List(vec![IonValue::Integer(7)]),
+            ])
+        ))
+    ))
+}
+
+#[test]
+fn select_fields() {
+    let document = build_select_document();
+
+    assert_eq!(
+        document.select("owner.name"),
+        Some(&IonValue::String("Jane".to_string()))
+    );
+    assert_eq!(document.select(""), Some(&document));
+    assert_eq!(document.select("owner.missing"), None);
+}
+
+#[test]
+fn select_list_indexes() {
+    let document = build_select_document();
+
+    assert_eq!(
+        document.select("owner.items[0].name"),
+        Some(&IonValue::String("Mats".to_string()))
+    );
+    assert_eq!(
+        document.select("owner.items[1][0]"),
+        Some(&IonValue::Integer(7))
+    );
+    assert_eq!(document.select("owner.items[2]"), None);
+    assert_eq!(
+        IonValue::List(vec![IonValue::Integer(1)]).select("[0]"),
+        Some(&IonValue::Integer(1))
+    );
+}
+
+#[test]
+fn select_quoted_fields() {
+    let document = build_select_document();
+
+    assert_eq!(
+        document.select("owner.\"first.name\""),
+        Some(&IonValue::String("J".to_string()))
+    );
+    assert_eq!(
+        document.select("'owner'.'first.name'"),
+        Some(&IonValue::String("J".to_string()))
+    );
+    assert_eq!(document.select("owner.first.name"), None);
+
+    let quotes = IonValue::Struct(hashmap!(
+        "it's".to_string() => IonValue::Integer(1)
+    ));
+
+    assert_eq!(quotes.select("'it\\'s'"), Some(&IonValue::Integer(1)));
+}
+
+#[test]
+fn select_invalid_paths() {
+    let document = build_select_document();
+
+    for path in &[
+        ".owner",
+        "owner.",
+        "owner..name",
+        "owner.[0]",
+        "owner.items[]",
+        "owner.items[a]",
+        "owner.items[0",
+        "owner.\"name",
+        "owner.\"name\"x",
+    ] {
+        assert_eq!(document.select(path), None, "{}", path);
+    }
+}
+
+#[test]
+fn text_of_strings_and_symbols() {
+    let string = IonValue::String("Mercedes".to_string());
+    let symbol = IonValue::Symbol(SymbolToken::new(Some("Make".into()), Some(10)));
+
+    assert!(matches!(string.text(), Some(Cow::Borrowed("Mercedes"))));
+    assert!(matches!(symbol.text(), Some(Cow::Borrowed("Make"))));
+    assert_eq!(IonValue::Integer(1).text(), None);
+}
+
+#[test]
+fn text_of_unresolved_symbols() {
+    let symbol = IonValue::Symbol(SymbolToken::from_id(15));
+
+    match symbol.text() {
+        Some(Cow::Owned(text)) => assert_eq!(text, "$15"),
+        text => panic!("Expected an owned text, found {:?}", text),
+    }
+
+    assert_eq!(
+        IonValue::Symbol(SymbolToken::new(None, None)).text(),
+        Some(Cow::Borrowed("$0"))
+    );
+}
+
+fn build_fingerprint_values() -> Vec<IonValue> {
+    vec![
+        IonValue::Null(NullIonValue::Null),
+        IonValue::Null(NullIonValue::Integer),
+        IonValue::Bool(true),
+        IonValue::Bool(false),
+        IonValue::Integer(1),
+        IonValue::Integer(2),
+        IonValue::Float(1.5),
+        IonValue::Float(0.0),
+        IonValue::Decimal(BigDecimal::from_str("1.5").unwrap()),
+        IonValue::String("1".to_string()),
+        IonValue::Symbol("1".into()),
+        IonValue::Blob(vec![1]),
+        IonValue::Clob(vec![1]),
+        IonValue::List(vec![IonValue::Integer(1)]),
+        IonValue::SExpr(vec![IonValue::Integer(1)]),
+        IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+        IonValue::List(vec![IonValue::Integer(2), IonValue::Integer(1)]),
+        build_car_struct(),
+        IonValue::Annotation(vec!["a".into()], Box::new(IonValue::Integer(1))),
+    ]
+}
+
+#[test]
+fn structural_fingerprint_of_equal_values() {
+    for value in build_fingerprint_values() {
+        assert_eq!(
+            value.structural_fingerprint(),
+            value.clone().structural_fingerprint()
+        );
+    }
+
+    // Built separately, so the fields are iterated in other order.
+    assert_eq!(
+        build_car_struct().structural_fingerprint(),
+        build_car_struct().structural_fingerprint()
+    );
+    assert_eq!(
+        IonValue::Float(0.0).structural_fingerprint(),
+        IonValue::Float(-0.0).structural_fingerprint()
+    );
+    assert_eq!(
+        IonValue::Decimal(BigDecimal::from_str("1.50").unwrap()).structural_fingerprint(),
+        IonValue::Decimal(BigDecimal::from_str("1.5").unwrap()).structural_fingerprint()
+    );
+    assert_eq!(
+        IonValue::Symbol(SymbolToken::new(Some("a".into()), Some(10))).structural_fingerprint(),
+        IonValue::Symbol(SymbolToken::new(Some("a".into()), Some(11))).structural_fingerprint()
+    );
+}
+
+#[test]
+fn structural_fingerprint_of_different_values() {
+    let values = build_fingerprint_values();
+
+    for (index, a) in values.iter().enumerate() {
+        for b in &values[index + 1..] {
+            assert_ne!(a, b);
+            assert_ne!(
+                a.structural_fingerprint(),
+                b.structural_fingerprint(),
+                "{:?} and {:?}",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn structural_fingerprint_of_decimals_with_extreme_exponents() {
+    let decimal = |coefficient: i64, exponent: i64| {
+        IonValue::Decimal(BigDecimal::new(BigInt::from(coefficient), -exponent))
+    };
+
+    let biggest = decimal(1, i64::MAX);
+
+    assert_eq!(
+        biggest.structural_fingerprint(),
+        decimal(10, i64::MAX - 1).structural_fingerprint()
+    );
+    assert_ne!(
+        biggest.structural_fingerprint(),
+        decimal(1, i64::MAX - 1).structural_fingerprint()
+    );
+    assert_eq!(
+        decimal(0, i64::MAX).structural_fingerprint(),
+        decimal(0, i64::MIN + 1).structural_fingerprint()
+    );
+
+    let mut values = HashSet::new();
+    values.insert(biggest.clone());
+
+    assert!(values.contains(&biggest));
+}
+
+#[test]
+fn ion_value_as_hash_map_key() {
+    let mut counts = HashMap::new();
+
+    for value in [build_car_struct(), build_car_struct(), IonValue::Integer(1)] {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+
+    assert_eq!(counts.get(&build_car_struct()), Some(&2));
+    assert_eq!(counts.get(&IonValue::Integer(1)), Some(&1));
+}
+
+#[test]
+fn decimal_from_parts_builds_coefficient_and_exponent() {
+    let value = IonValue::decimal_from_parts(BigInt::from(123), -2).unwrap();
+
+    assert_eq!(
+        value,
+        IonValue::Decimal(BigDecimal::from_str("1.23").unwrap())
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value);
+
+    // Decimal with length 2, exponent VarInt -2 and coefficient Int 123.
+    assert!(encoder.encode().ends_with(b"\x52\xc2\x7b"));
+}
+
+#[test]
+fn decimal_from_parts_with_negative_coefficient_and_exponent() {
+    let value = IonValue::decimal_from_parts(BigInt::from(-123), -2).unwrap();
+
+    assert_eq!(
+        value,
+        IonValue::Decimal(BigDecimal::from_str("-1.23").unwrap())
+    );
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    assert!(bytes.ends_with(b"\x52\xc2\xfb"));
+    assert_eq!(IonValue::from_binary(&bytes).unwrap(), value);
+
+    assert_eq!(
+        IonValue::decimal_from_parts(BigInt::from(5), 3),
+        Ok(IonValue::Decimal(BigDecimal::from_str("5000").unwrap()))
+    );
+}
+
+#[test]
+fn decimal_from_parts_with_the_smallest_exponent() {
+    assert_eq!(
+        IonValue::decimal_from_parts(BigInt::from(1), i64::MIN),
+        Err(IonParserError::DecimalExponentTooBig)
+    );
+    assert_eq!(
+        IonValue::decimal_from_parts(BigInt::from(1), i64::MIN + 1),
+        Ok(IonValue::Decimal(BigDecimal::new(
+            BigInt::from(1),
+            i64::MAX
+        )))
+    );
+}
+
+#[test]
+fn as_tagged_splits_single_annotation() {
+    let inner = IonValue::Struct(hashmap!(
+        "a".to_string() => IonValue::Integer(1)
+    ));
+    let value = IonValue::Annotation(vec!["variant_a".into()], Box::new(inner.clone()));
+
+    assert_eq!(value.as_tagged(), Some(("variant_a", &inner)));
+}
+
+#[test]
+fn as_tagged_without_annotation_is_none() {
+    assert_eq!(IonValue::Integer(1).as_tagged(), None);
+}
+
+#[test]
+fn as_tagged_with_many_annotations_is_none() {
+    let value = IonValue::Annotation(
+        vec!["variant_a".into(), "variant_b".into()],
+        Box::new(IonValue::Integer(1)),
+    );
+
+    assert_eq!(value.as_tagged(), None);
+}
+
+#[test]
+fn unknown_offset_timestamp_round_trips() {
+    let utc = NaiveDate::from_ymd_opt(2011, 2, 20)
+        .unwrap()
+        .and_hms_opt(19, 30, 59)
+        .unwrap();
+    let value = IonValue::timestamp_with_unknown_offset(utc);
+
+    let mut encoder = IonEncoder::new();
+    encoder.add(value.clone());
+    let bytes = encoder.encode();
+
+    // The offset is the VarInt -0.
+    assert!(bytes.ends_with(b"\x68\xc0\x0f\xdb\x82\x94\x93\x9e\xbb"));
+
+    let parsed = IonValue::from_binary(&bytes).unwrap();
+
+    assert_eq!(parsed, value);
+    assert_eq!(parsed.offset_minutes(), None);
+    assert_eq!(parsed.hour(), Some(19));
+    assert_eq!(
+        parsed.to_rfc3339(),
+        Some("2011-02-20T19:30:59-00:00".to_string())
+    );
+    assert_eq!(parsed.to_string(), "2011-02-20T19:30:59-00:00");
+
+    let known_offset = IonValue::DateTime(DateTime::from(Utc.from_utc_datetime(&utc)));
+    assert_ne!(parsed, known_offset);
+    assert_ne!(
+        IonHash::default_digest(&parsed),
+        IonHash::default_digest(&known_offset)
+    );
+}
+
+#[test]
+fn unknown_offset_timestamp_is_not_converted_to_an_offset() {
+    let utc = NaiveDate::from_ymd_opt(2011, 2, 20)
+        .unwrap()
+        .and_hms_opt(19, 30, 59)
+        .unwrap();
+    let value = IonValue::timestamp_with_unknown_offset(utc);
+
+    assert_eq!(
+        DateTime::<FixedOffset>::try_from(&value).unwrap_err(),
+        IonParserError::TimestampWithUnknownOffset
+    );
+    assert_eq!(
+        DateTime::<Utc>::try_from(value).unwrap(),
+        Utc.from_utc_datetime(&utc)
+    );
+}
+
+#[test]
+fn timestamp_to_system_time_and_back() {
+    let datetime = DateTime::parse_from_rfc3339("2021-03-04T05:06:07.123456789+02:00").unwrap();
+    let value = IonValue::DateTime(datetime);
+
+    let system_time = SystemTime::try_from(&value).unwrap();
+
+    assert_eq!(
+        system_time,
+        UNIX_EPOCH + Duration::new(1_614_827_167, 123_456_789)
+    );
+
+    let round_trip = IonValue::from(system_time);
+
+    assert_eq!(
+        DateTime::<Utc>::try_from(&round_trip).unwrap(),
+        datetime.with_timezone(&Utc)
+    );
+    assert_eq!(SystemTime::try_from(round_trip).unwrap(), system_time);
+}
+
+#[test]
+fn timestamp_less_precise_than_seconds_to_system_time() {
+    let utc = NaiveDate::from_ymd_opt(2021, 3, 4)
+        .unwrap()
+        .and_hms_opt(5, 6, 0)
+        .unwrap();
+    let minutes = IonTimestamp::new(utc, Some(0), TimestampPrecision::Minute).unwrap();
+
+    assert_eq!(
+        SystemTime::try_from(IonValue::Timestamp(minutes)),
+        Err(IonParserError::TimestampWithoutSeconds)
+    );
+
+    let seconds = IonTimestamp::new(utc, Some(0), TimestampPrecision::Second).unwrap();
+
+    assert_eq!(
+        SystemTime::try_from(IonValue::Timestamp(seconds)),
+        Ok(UNIX_EPOCH + Duration::from_secs(1_614_834_360))
+    );
+}
+
+#[test]
+fn timestamp_before_1970_to_system_time() {
+    let value = IonValue::DateTime(DateTime::parse_from_rfc3339("1969-12-31T23:59:59Z").unwrap());
+
+    assert_eq!(
+        SystemTime::try_from(&value),
+        Err(IonParserError::TimestampBeforeUnixEpoch)
+    );
+}
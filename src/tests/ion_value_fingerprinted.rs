@@ -0,0 +1,41 @@
+use crate::hashmap;
+use crate::{FingerprintedIonValue, IonTextParser, IonValue};
+
+fn build_document(last: i64) -> IonValue {
+    IonValue::List(vec![
+        IonValue::Struct(hashmap!(
+            "Model".to_string() => IonValue::String("CLK 350".to_string()),
+            "Year".to_string() => IonValue::Integer(2019)
+        )),
+        IonValue::Integer(last),
+    ])
+}
+
+#[test]
+fn fingerprinted_values_compare_as_the_values() {
+    let a = FingerprintedIonValue::new(build_document(1));
+    let b = FingerprintedIonValue::new(build_document(1));
+    let c = FingerprintedIonValue::new(build_document(2));
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a, build_document(1));
+    assert_eq!(a.fingerprint(), build_document(1).structural_fingerprint());
+
+    // NaN is not equal to itself, even with the same fingerprint.
+    let nan = FingerprintedIonValue::new(IonValue::Float(f64::NAN));
+    assert_ne!(nan, nan.clone());
+}
+
+#[test]
+fn fingerprinted_parsed_decimal_with_huge_exponent() {
+    let document = IonTextParser::new("[1d9223372036854775807, 2]")
+        .consume_value()
+        .unwrap();
+
+    let a = FingerprintedIonValue::new(document.clone());
+    let b = FingerprintedIonValue::new(build_document(2));
+
+    assert_eq!(a.fingerprint(), document.structural_fingerprint());
+    assert_ne!(a, b);
+}
@@ -0,0 +1,157 @@
+use crate::hashmap;
+use crate::{IonParserError, IonValue, NullIonValue, OrdIonValue};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate};
+use num_bigint::BigInt;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+fn ord(value: IonValue) -> OrdIonValue {
+    OrdIonValue::new(value).unwrap()
+}
+
+#[test]
+fn btree_map_iterates_in_ion_order() {
+    let values = vec![
+        IonValue::Struct(hashmap!("a".to_string() => IonValue::Integer(1))),
+        IonValue::String("b".to_string()),
+        IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+        IonValue::Integer(10),
+        IonValue::Decimal(BigDecimal::from_str("1.5").unwrap()),
+        IonValue::Symbol("b".into()),
+        IonValue::List(vec![IonValue::Integer(1)]),
+        IonValue::BigInteger(BigInt::from(-3)),
+        IonValue::Null(NullIonValue::Null),
+        IonValue::Bool(false),
+        IonValue::Float(2.5),
+        IonValue::String("a".to_string()),
+    ];
+
+    let mut map = BTreeMap::new();
+
+    for (index, value) in values.into_iter().enumerate() {
+        map.insert(ord(value), index);
+    }
+
+    let sorted: Vec<IonValue> = map.into_keys().map(OrdIonValue::into_inner).collect();
+
+    assert_eq!(
+        sorted,
+        vec![
+            IonValue::Null(NullIonValue::Null),
+            IonValue::Bool(false),
+            IonValue::BigInteger(BigInt::from(-3)),
+            IonValue::Integer(10),
+            IonValue::Float(2.5),
+            IonValue::Decimal(BigDecimal::from_str("1.5").unwrap()),
+            IonValue::Symbol("b".into()),
+            IonValue::String("a".to_string()),
+            IonValue::String("b".to_string()),
+            IonValue::List(vec![IonValue::Integer(1)]),
+            IonValue::List(vec![IonValue::Integer(1), IonValue::Integer(2)]),
+            IonValue::Struct(hashmap!("a".to_string() => IonValue::Integer(1))),
+        ]
+    );
+}
+
+#[test]
+fn equal_values_are_the_same_key() {
+    let mut map = BTreeMap::new();
+
+    map.insert(ord(IonValue::Integer(1)), "first");
+    map.insert(ord(IonValue::BigInteger(BigInt::from(1))), "second");
+    map.insert(
+        ord(IonValue::Struct(hashmap!(
+            "a".to_string() => IonValue::Integer(1),
+            "b".to_string() => IonValue::Integer(2)
+        ))),
+        "third",
+    );
+    map.insert(
+        ord(IonValue::OrderedStruct(vec![
+            ("b".into(), IonValue::Integer(2)),
+            ("a".into(), IonValue::Integer(1)),
+        ])),
+        "fourth",
+    );
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.values().collect::<Vec<_>>(), vec![&"second", &"fourth"]);
+}
+
+#[test]
+fn annotated_value_goes_after_the_bare_value() {
+    let annotated = ord(IonValue::Annotation(
+        vec!["a".into()],
+        Box::new(IonValue::Integer(1)),
+    ));
+
+    assert!(ord(IonValue::Integer(1)) < annotated);
+    assert!(annotated < ord(IonValue::Integer(2)));
+}
+
+#[test]
+fn nan_is_rejected() {
+    assert_eq!(
+        OrdIonValue::new(IonValue::List(vec![IonValue::Float(f64::NAN)])).unwrap_err(),
+        IonParserError::NanNotOrderable
+    );
+}
+
+#[test]
+fn sort_decimals_and_timestamps() {
+    let decimal = |text: &str| IonValue::Decimal(BigDecimal::from_str(text).unwrap());
+    let timestamp = |text: &str| IonValue::DateTime(DateTime::parse_from_rfc3339(text).unwrap());
+    let unknown_offset = IonValue::DateTimeUnknownOffset(
+        NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap(),
+    );
+
+    let mut values = vec![
+        ord(timestamp("2021-01-01T12:00:00Z")),
+        ord(decimal("10.5")),
+        ord(timestamp("2021-01-01T12:00:00.5+01:00")),
+        ord(unknown_offset.clone()),
+        ord(decimal("-2")),
+        ord(timestamp("2020-12-31T23:59:59Z")),
+        ord(decimal("1.25")),
+        ord(IonValue::Integer(100)),
+    ];
+
+    values.sort();
+
+    let sorted: Vec<IonValue> = values.into_iter().map(OrdIonValue::into_inner).collect();
+
+    assert_eq!(
+        sorted,
+        vec![
+            IonValue::Integer(100),
+            decimal("-2"),
+            decimal("1.25"),
+            decimal("10.5"),
+            timestamp("2020-12-31T23:59:59Z"),
+            // 11:00:00.5 UTC
+            timestamp("2021-01-01T12:00:00.5+01:00"),
+            unknown_offset,
+            timestamp("2021-01-01T12:00:00Z"),
+        ]
+    );
+}
+
+#[test]
+fn decimals_and_timestamps_equal_by_value() {
+    assert_eq!(
+        ord(IonValue::Decimal(BigDecimal::from_str("1.0").unwrap())),
+        ord(IonValue::Decimal(BigDecimal::from_str("1.00").unwrap()))
+    );
+    assert_eq!(
+        ord(IonValue::DateTime(
+            DateTime::parse_from_rfc3339("2021-01-01T12:00:00Z").unwrap()
+        )),
+        ord(IonValue::DateTime(
+            DateTime::parse_from_rfc3339("2021-01-01T13:00:00+01:00").unwrap()
+        ))
+    );
+}
@@ -0,0 +1,83 @@
+use crate::{IonValue, IonValueRc};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[test]
+fn into_shared_shares_subtrees_without_copying() {
+    let subtree = IonValue::List(vec![
+        IonValue::String("shared".to_string()),
+        IonValue::Integer(42),
+    ])
+    .into_shared();
+
+    let subtree_list = match &subtree {
+        IonValueRc::List(values) => values.clone(),
+        _ => panic!("Expected a list"),
+    };
+
+    let mut first = HashMap::new();
+    first.insert("data".into(), subtree.clone());
+    let first = IonValueRc::Struct(Rc::new(first));
+
+    let mut second = HashMap::new();
+    second.insert("data".into(), subtree);
+    let second = IonValueRc::Struct(Rc::new(second));
+
+    assert_eq!(Rc::strong_count(&subtree_list), 3);
+
+    match (first.get("data"), second.get("data")) {
+        (Some(IonValueRc::List(a)), Some(IonValueRc::List(b))) => {
+            assert!(Rc::ptr_eq(a, b));
+            assert!(Rc::ptr_eq(a, &subtree_list));
+        }
+        _ => panic!("Expected both parents to have the list"),
+    }
+
+    let first_copy = first.clone();
+    assert_eq!(Rc::strong_count(&subtree_list), 3);
+    assert_eq!(first_copy, first);
+}
+
+#[test]
+fn into_shared_round_trips() {
+    let mut inner = HashMap::new();
+    inner.insert("Year".into(), IonValue::Integer(2019));
+
+    let value = IonValue::OrderedStruct(vec![
+        ("Make".into(), IonValue::String("Mercedes".to_string())),
+        (
+            "Info".into(),
+            IonValue::Annotation(
+                vec!["car".into()],
+                Box::new(IonValue::List(vec![IonValue::Struct(inner)])),
+            ),
+        ),
+    ]);
+
+    let shared = value.clone().into_shared();
+
+    assert_eq!(
+        shared.get("Make"),
+        Some(&IonValueRc::String("Mercedes".to_string()))
+    );
+    assert_eq!(shared.get("Missing"), None);
+    assert_eq!(shared.to_ion_value(), value);
+}
+
+#[test]
+fn shared_get_path() {
+    let value = IonValue::from_pairs(vec![(
+        "items".to_string(),
+        IonValue::List(vec![IonValue::from_pairs(vec![(
+            "name".to_string(),
+            IonValue::Integer(1),
+        )])]),
+    )])
+    .into_shared();
+
+    assert_eq!(
+        value.get_path(&["items".into(), 0.into(), "name".into()]),
+        Some(&IonValueRc::Integer(1))
+    );
+    assert_eq!(value.get_path(&["items".into(), 1.into()]), None);
+}
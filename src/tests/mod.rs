@@ -2,9 +2,24 @@ mod bad_tests;
 mod binary_encoder;
 mod binary_parser;
 mod good_tests;
+mod ion_deserializer;
 mod ion_encoder;
 mod ion_hash;
+mod ion_incremental_parser;
 mod ion_parser;
+mod ion_patch;
+mod ion_reader;
+mod ion_schema;
+mod ion_serializer;
+mod ion_struct_builder;
+mod ion_text_parser;
+mod ion_text_writer;
+mod ion_timestamp;
+mod ion_value;
+mod ion_value_fingerprinted;
+mod ion_value_ord;
+mod ion_value_rc;
+mod symbol_table;
 
 #[macro_use]
 mod test_utils;
@@ -0,0 +1,120 @@
+use crate::symbol_table::Symbol;
+use crate::{IonEncoder, IonParser, IonValue, SymbolContext, SymbolContextError, SymbolToken};
+
+#[test]
+fn symbol_token_text_only() {
+    let token = SymbolToken::from_text("Model");
+
+    assert_eq!(token.text(), Some("Model"));
+    assert_eq!(token.id, None);
+    assert_eq!(token, SymbolToken::from("Model"));
+}
+
+#[test]
+fn symbol_token_id_only() {
+    let token = SymbolToken::from_id(15);
+
+    assert_eq!(token.text(), None);
+    assert_eq!(token.id, Some(15));
+    assert_eq!(token, SymbolToken::from_id(15));
+    assert_ne!(token, SymbolToken::from_id(16));
+    assert_ne!(token, SymbolToken::new(Some("$15".into()), Some(15)));
+}
+
+#[test]
+fn symbol_token_text_and_id() {
+    let token = SymbolToken::new(Some("Model".into()), Some(10));
+
+    assert_eq!(token.text(), Some("Model"));
+    assert_eq!(token.id, Some(10));
+
+    // Symbols with text are compared by text
+    assert_eq!(token, SymbolToken::from_text("Model"));
+    assert_eq!(token, SymbolToken::new(Some("Model".into()), Some(25)));
+    assert_ne!(token, SymbolToken::new(Some("Type".into()), Some(10)));
+}
+
+#[test]
+fn parsed_symbol_token_has_text_and_id() {
+    let mut encoder = IonEncoder::new();
+
+    encoder.add(IonValue::Symbol("Model".into()));
+
+    let bytes = encoder.encode();
+
+    let value = IonParser::new(&bytes[..]).consume_value().unwrap().0;
+
+    match value {
+        IonValue::Symbol(token) => {
+            assert_eq!(token.text(), Some("Model"));
+            assert_eq!(token.id, Some(10));
+        }
+        _ => panic!("Expected a symbol, found: {:?}", value),
+    }
+}
+
+#[test]
+fn encode_symbol_token_without_text() {
+    let mut encoder = IonEncoder::new();
+
+    let bytes = encoder.encode_value(&IonValue::Symbol(SymbolToken::from_id(4)));
+
+    assert_eq!(bytes, vec![0x71, 0x04]);
+}
+
+fn symbols(names: &[&str]) -> Vec<Symbol> {
+    names
+        .iter()
+        .map(|name| Symbol::Symbol((*name).into()))
+        .collect()
+}
+
+#[test]
+fn shared_table_versions_need_to_be_supersets() {
+    let mut context = SymbolContext::new();
+
+    context
+        .add_shared_table("cars".to_string(), 1, &symbols(&["Make", "Model"]))
+        .unwrap();
+
+    assert_eq!(
+        context.add_shared_table("cars".to_string(), 3, &symbols(&["Make", "Year"])),
+        Err(SymbolContextError::NewTableIsNotSuperSetOfPrevious)
+    );
+    assert_eq!(
+        context.add_shared_table("cars".to_string(), 3, &symbols(&["Make", "Model", "Year"])),
+        Ok(())
+    );
+    assert_eq!(
+        context.add_shared_table("cars".to_string(), 4, &symbols(&["Make"])),
+        Err(SymbolContextError::NewTableIsNotSuperSetOfPrevious)
+    );
+}
+
+#[test]
+fn shared_table_version_0() {
+    let mut context = SymbolContext::new();
+
+    assert_eq!(
+        context.add_shared_table("cars".to_string(), 0, &symbols(&["Make"])),
+        Ok(())
+    );
+}
+
+#[test]
+fn shared_table_huge_version_is_checked_against_the_previous_one() {
+    let mut context = SymbolContext::new();
+
+    context
+        .add_shared_table("big".to_string(), 1, &symbols(&["a"]))
+        .unwrap();
+
+    assert_eq!(
+        context.add_shared_table("big".to_string(), u32::MAX, &symbols(&["b"])),
+        Err(SymbolContextError::NewTableIsNotSuperSetOfPrevious)
+    );
+    assert_eq!(
+        context.add_shared_table("big".to_string(), u32::MAX, &symbols(&["a", "b"])),
+        Ok(())
+    );
+}
@@ -19,7 +19,7 @@ macro_rules! hashmap(
         {
             let mut m = ::std::collections::HashMap::new();
             $(
-                m.insert($key, $value);
+                m.insert($key.into(), $value);
             )+
             m
         }